@@ -2,7 +2,9 @@ import { path } from '@tauri-apps/api';
 import { writeTextFile } from '@tauri-apps/plugin-fs';
 import yaml from 'js-yaml';
 
+import { getDataPath } from '../../../shared';
+
 export async function SaveHistory(history: Record<string, string[]>) {
-  const yaml_route = await path.join(await path.appDataDir(), 'history');
+  const yaml_route = await path.join(await getDataPath(), 'history');
   await writeTextFile(yaml_route, yaml.dump(history));
 }
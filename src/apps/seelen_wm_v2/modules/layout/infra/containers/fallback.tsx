@@ -2,13 +2,13 @@ import { useSelector } from 'react-redux';
 
 import { Selectors } from '../../../shared/store/app';
 
-import { WmFallbackNode } from '../../domain';
+import { WmFallbackNode, WmStackNode } from '../../domain';
 
 import { cx } from '../../../../../shared/styles';
 import { LeafContainer } from './leaf';
 
 interface Props {
-  node: WmFallbackNode;
+  node: WmFallbackNode | WmStackNode;
 }
 
 export function FallbackContainer({ node }: Props) {
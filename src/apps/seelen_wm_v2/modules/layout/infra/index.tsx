@@ -18,7 +18,7 @@ export function Container({ node: _node }: { node: Node }) {
     return null;
   }
 
-  if (node.isFallback()) {
+  if (node.isFallback() || node.isStack()) {
     return <FallbackContainer node={node.inner} />;
   }
 
@@ -9,7 +9,7 @@ import { useSelector } from 'react-redux';
 import { BackgroundByLayersV2 } from '../../../components/BackgroundByLayers/infra';
 import { LAZY_CONSTANTS, updatePreviews } from '../../shared/utils/infra';
 
-import { Selectors } from '../../shared/store/app';
+import { isElevatedProgram, Selectors } from '../../shared/store/app';
 import { parseCommand } from 'src/apps/shared/Command';
 import { useIcon, useWindowFocusChange } from 'src/apps/shared/hooks';
 
@@ -38,6 +38,7 @@ export const UserApplication = memo(({ item, onAssociatedViewOpenChanged }: Prop
   const [blockUntil, setBlockUntil] = useState(moment(new Date()));
 
   const devTools = useSelector(Selectors.devTools);
+  const elevatedItems = useSelector(Selectors.elevatedItems);
   const settings = useSelector(Selectors.settings);
 
   const iconSrc =
@@ -143,7 +144,9 @@ export const UserApplication = memo(({ item, onAssociatedViewOpenChanged }: Prop
               let window = item.windows[0];
               if (!window) {
                 const { program, args } = parseCommand(item.relaunchCommand);
-                invoke(SeelenCommand.Run, { program, args });
+                const elevated = isElevatedProgram(elevatedItems, program);
+                // Todo replace this when added to SeelenCommand
+                invoke('launch_item', { program, args, elevated }).catch(console.error);
               } else {
                 invoke(SeelenCommand.WegToggleWindowState, { hwnd: window.handle });
               }
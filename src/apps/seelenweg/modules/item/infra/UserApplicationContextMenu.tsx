@@ -6,7 +6,7 @@ import { TFunction } from 'i18next';
 
 import { store } from '../../shared/store/infra';
 
-import { isPinnedApp, RootActions } from '../../shared/store/app';
+import { isElevatedProgram, isPinnedApp, RootActions } from '../../shared/store/app';
 import { parseCommand } from 'src/apps/shared/Command';
 
 import { PinnedWegItem, TemporalWegItem } from '../../shared/store/domain';
@@ -19,6 +19,8 @@ export function getUserApplicationContextMenu(
   devTools: boolean,
 ): ItemType[] {
   const isPinned = isPinnedApp(item);
+  const { program, args } = parseCommand(item.relaunchCommand);
+  const isElevated = isElevatedProgram(store.getState().elevatedItems, program);
 
   const menu: MenuProps['items'] = [];
 
@@ -62,9 +64,15 @@ export function getUserApplicationContextMenu(
       key: 'weg_runas',
       label: t('app_menu.run_as'),
       icon: <Icon iconName="MdOutlineAdminPanelSettings" />,
+      onClick: () => invoke(SeelenCommand.RunAsAdmin, { program, args }),
+    },
+    {
+      key: 'weg_always_runas',
+      label: isElevated ? t('app_menu.never_run_as') : t('app_menu.always_run_as'),
+      icon: <Icon iconName="MdOutlineAdminPanelSettings" />,
       onClick: () => {
-        const { program, args } = parseCommand(item.relaunchCommand);
-        invoke(SeelenCommand.RunAsAdmin, { program, args });
+        // Todo replace this when added to SeelenCommand
+        invoke('set_item_elevated', { program, elevated: !isElevated }).catch(console.error);
       },
     },
   );
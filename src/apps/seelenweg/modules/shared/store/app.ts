@@ -9,6 +9,7 @@ import { savePinnedItems } from './storeApi';
 
 const initialState: RootState = {
   devTools: false,
+  elevatedItems: [],
   itemsOnLeft: [],
   itemsOnCenter: [],
   itemsOnRight: [],
@@ -88,3 +89,8 @@ export const isPinnedApp = (item: SwItem): item is PinnedWegItem => {
 export const isTemporalApp = (item: SwItem): item is TemporalWegItem => {
   return item.type === WegItemType.Temporal;
 };
+
+/** same normalization used by the background to store the elevated items */
+export const isElevatedProgram = (elevatedItems: string[], program: string): boolean => {
+  return elevatedItems.includes(program.trim().replace(/^"+|"+$/g, '').toLowerCase());
+};
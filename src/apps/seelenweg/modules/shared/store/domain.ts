@@ -29,6 +29,8 @@ export type SwItem = WegItem;
 
 export interface RootState extends IRootState<SeelenWegSettings> {
   devTools: boolean;
+  /** normalized program paths that are always launched as administrator */
+  elevatedItems: string[];
   itemsOnLeft: SwItem[];
   itemsOnCenter: SwItem[];
   itemsOnRight: SwItem[];
@@ -104,6 +104,9 @@ function loadSettingsToStore(_settings: Settings) {
   i18n.changeLanguage(settings.language || undefined);
   store.dispatch(RootActions.setSettings(settings.seelenweg));
   store.dispatch(RootActions.setDevTools(settings.devTools));
+  // app owned section of settings.json, not included on the lib types
+  const { elevatedItems = [] } = settings as typeof settings & { elevatedItems?: string[] };
+  store.dispatch(RootActions.setElevatedItems(elevatedItems));
   loadSettingsCSS(settings.seelenweg);
 }
 
@@ -1,4 +1,4 @@
-import { path } from '@tauri-apps/api';
+import { getDataPath } from '../../../../shared';
 
 export function filenameFromPath(path: string): string {
   const parts = path.split('\\');
@@ -6,5 +6,5 @@ export function filenameFromPath(path: string): string {
 }
 
 export async function getGeneratedFilesPath(): Promise<string> {
-  return await path.appDataDir();
+  return await getDataPath();
 }
@@ -1,7 +1,7 @@
 import { SeelenCommand } from '@seelen-ui/lib';
 import { path } from '@tauri-apps/api';
 import { invoke } from '@tauri-apps/api/core';
-import { Button, Switch } from 'antd';
+import { Button, InputNumber, Switch } from 'antd';
 import { useTranslation } from 'react-i18next';
 import { useDispatch, useSelector } from 'react-redux';
 
@@ -14,6 +14,7 @@ import { SettingsGroup, SettingsOption, SettingsSubGroup } from '../../component
 
 export function DeveloperTools() {
   const devTools = useSelector(newSelectors.devTools);
+  const winEvents = useSelector(newSelectors.winEvents);
 
   const dispatch = useDispatch();
   const { t } = useTranslation();
@@ -76,6 +77,16 @@ export function DeveloperTools() {
           <span>{t('devtools.simulate_fullscreen')}</span>
           <Switch onChange={simulateFullscreen} />
         </SettingsOption>
+        <SettingsOption>
+          <span>{t('devtools.win_events_coalescing')}</span>
+          <InputNumber
+            value={winEvents.coalesceMs}
+            onChange={(value) =>
+              dispatch(RootActions.setWinEvents({ ...winEvents, coalesceMs: value || 0 }))}
+            min={0}
+            max={1000}
+          />
+        </SettingsOption>
       </SettingsGroup>
     </>
   );
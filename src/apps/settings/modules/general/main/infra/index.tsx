@@ -1,4 +1,4 @@
-import { Input, Select, Switch, Tooltip } from 'antd';
+import { Input, InputNumber, Select, Switch, Tooltip } from 'antd';
 import { ChangeEvent, useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { useSelector } from 'react-redux';
@@ -22,6 +22,9 @@ export function General() {
   const autostartStatus = useSelector(RootSelectors.autostart);
   const language = useSelector(RootSelectors.language);
   const dateFormat = useSelector(RootSelectors.dateFormat);
+  const weather = useSelector(RootSelectors.weather);
+  const notifications = useSelector(RootSelectors.notifications);
+  const batteryAlerts = useSelector(RootSelectors.batteryAlerts);
 
   const { t } = useTranslation();
   const dispatch = useAppDispatch();
@@ -84,6 +87,53 @@ export function General() {
             onChange={onDateFormatChange}
           />
         </SettingsOption>
+        <SettingsOption>
+          <b>{t('general.weather')}</b>
+          <Switch
+            value={weather.enabled}
+            onChange={(enabled) => dispatch(RootActions.setWeather({ ...weather, enabled }))}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <b>{t('general.dnd_mode.label')}</b>
+          <Select
+            style={{ width: '200px' }}
+            value={notifications.dndMode}
+            options={[
+              { label: t('general.dnd_mode.priority_only'), value: 'PriorityOnly' },
+              { label: t('general.dnd_mode.alarms_only'), value: 'AlarmsOnly' },
+            ]}
+            onSelect={(dndMode) =>
+              dispatch(RootActions.setNotifications({ ...notifications, dndMode }))}
+          />
+        </SettingsOption>
+      </SettingsGroup>
+
+      <SettingsGroup>
+        <SettingsOption>
+          <b>{t('general.battery_alerts.threshold')}</b>
+          <InputNumber
+            value={batteryAlerts.lowBatteryThreshold}
+            onChange={(value) =>
+              dispatch(
+                RootActions.setBatteryAlerts({
+                  ...batteryAlerts,
+                  lowBatteryThreshold: value || 0,
+                }),
+              )}
+            min={0}
+            max={100}
+            style={{ width: '200px' }}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <b>{t('general.battery_alerts.auto_battery_saver')}</b>
+          <Switch
+            value={batteryAlerts.autoBatterySaver}
+            onChange={(autoBatterySaver) =>
+              dispatch(RootActions.setBatteryAlerts({ ...batteryAlerts, autoBatterySaver }))}
+          />
+        </SettingsOption>
       </SettingsGroup>
 
       <Colors />
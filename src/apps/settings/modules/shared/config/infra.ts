@@ -1,9 +1,11 @@
 import { path } from '@tauri-apps/api';
 
+import { getDataPath } from '../../../../shared';
+
 export const EnvConfig = {
   version: process.env.packageVersion,
 };
 
 export async function resolveDataPath(...sub: string[]) {
-  return await path.join(await path.appDataDir(), ...sub);
+  return await path.join(await getDataPath(), ...sub);
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 import { Settings } from '@seelen-ui/lib/types';
 import { pick } from 'lodash';
 
-import { RootState } from '../domain';
+import { ExtendedSettings, RootState } from '../domain';
 
 import { UserSettings } from '../../../../../../shared.interfaces';
 
@@ -17,7 +17,7 @@ export const StaticSettingsToState = (userSettings: UserSettings, state: RootSta
   };
 };
 
-export const StateToJsonSettings = (state: RootState): Settings => {
+export const StateToJsonSettings = (state: RootState): Settings & ExtendedSettings => {
   return pick(state, [
     'fancyToolbar',
     'windowManager',
@@ -35,5 +35,9 @@ export const StateToJsonSettings = (state: RootState): Settings => {
     'wall',
     'launcher',
     'custom',
+    'weather',
+    'notifications',
+    'batteryAlerts',
+    'winEvents',
   ]);
 };
@@ -1,3 +1,9 @@
 import { Settings } from '@seelen-ui/lib';
+import { invoke } from '@tauri-apps/api/core';
 
-export const defaultSettings = await Settings.default();
\ No newline at end of file
+import { ExtendedSettings } from '../domain';
+
+export const defaultSettings = await Settings.default();
+
+// Todo replace this when the lib Settings includes the extended sections
+export const defaultExtendedSettings: ExtendedSettings = await invoke('state_get_default_settings');
\ No newline at end of file
@@ -18,7 +18,7 @@ import { RootState } from '../domain';
 import { StateBuilder } from '../../../../../shared/StateBuilder';
 import { Route } from '../../../../components/navigation/routes';
 import i18n from '../../../../i18n';
-import { defaultSettings } from './default';
+import { defaultExtendedSettings, defaultSettings } from './default';
 
 const initialState: RootState = {
   lastLoaded: null,
@@ -28,6 +28,10 @@ const initialState: RootState = {
   seelenweg: defaultSettings.inner.seelenweg,
   wall: defaultSettings.inner.wall,
   launcher: defaultSettings.inner.launcher,
+  weather: defaultExtendedSettings.weather,
+  notifications: defaultExtendedSettings.notifications,
+  batteryAlerts: defaultExtendedSettings.batteryAlerts,
+  winEvents: defaultExtendedSettings.winEvents,
   windowManager: SeelenManagerSlice.getInitialState(),
   toBeSaved: false,
   toBeRestarted: false,
@@ -85,6 +89,10 @@ export const RootSlice = createSlice({
     setWall: toBeSaved(reducers.setWall),
     setLauncher: toBeSaved(reducers.setLauncher),
     setDevTools: toBeSaved(reducers.setDevTools),
+    setWeather: toBeSaved(reducers.setWeather),
+    setNotifications: toBeSaved(reducers.setNotifications),
+    setBatteryAlerts: toBeSaved(reducers.setBatteryAlerts),
+    setWinEvents: toBeSaved(reducers.setWinEvents),
     setUpdater: toBeSavedAndRestarted(reducers.setUpdater),
     setMonitors: toBeSaved(reducers.setMonitorsV2),
     setLanguage: (state, action: PayloadAction<string>) => {
@@ -3,7 +3,42 @@ import { AppConfig, IconPack, Placeholder, Plugin, Profile, Settings, Theme, Wid
 
 import { Route } from '../../../components/navigation/routes';
 
-export interface RootState extends Settings {
+export interface WeatherSettings {
+  /** if disabled no network request will be done at all */
+  enabled: boolean;
+  providerUrl: string;
+  /** seconds to reuse the cached value before fetching again */
+  ttl: number;
+}
+
+/** Focus Assist mode applied while do not disturb is enabled */
+export type DndMode = 'PriorityOnly' | 'AlarmsOnly';
+
+export interface NotificationsSettings {
+  dndMode: DndMode;
+}
+
+export interface BatteryAlertsSettings {
+  /** percent at which the `low-battery` event is emitted while discharging */
+  lowBatteryThreshold: number;
+  /** enable battery saver when the threshold is crossed */
+  autoBatterySaver: boolean;
+}
+
+export interface WinEventsSettings {
+  /** interval in milliseconds to coalesce bursts of window location changes, 0 disables it */
+  coalesceMs: number;
+}
+
+/** Sections of settings.json owned by the app on top of the lib `Settings` */
+export interface ExtendedSettings {
+  weather: WeatherSettings;
+  notifications: NotificationsSettings;
+  batteryAlerts: BatteryAlertsSettings;
+  winEvents: WinEventsSettings;
+}
+
+export interface RootState extends Settings, ExtendedSettings {
   lastLoaded: this | null;
   route: Route;
   toBeSaved: boolean;
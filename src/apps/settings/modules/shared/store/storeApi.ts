@@ -77,7 +77,8 @@ export class UserSettingsLoader {
     }
 
     if (this._withWallpaper) {
-      userSettings.wallpaper = await invoke(SeelenCommand.StateGetWallpaper);
+      const wallpaper = await invoke<{ path: string }>(SeelenCommand.StateGetWallpaper);
+      userSettings.wallpaper = wallpaper.path;
     }
 
     return userSettings;
@@ -12,6 +12,12 @@ export async function wasInstalledUsingMSIX(): Promise<boolean> {
   return invoke('is_appx_package');
 }
 
+/** User data folder, it differs from `path.appDataDir()` on portable mode */
+export async function getDataPath(): Promise<string> {
+  // Todo replace this when added to SeelenCommand
+  return invoke('get_data_path');
+}
+
 export async function isDev(): Promise<boolean> {
   return invoke(SeelenCommand.IsDevMode);
 }
@@ -3,6 +3,7 @@ import { writeTextFile } from '@tauri-apps/plugin-fs';
 import yaml from 'js-yaml';
 import { cloneDeep, debounce } from 'lodash';
 
+import { getDataPath } from '../../../shared';
 import { store } from '../shared/store/infra';
 
 import {
@@ -26,7 +27,7 @@ export const SavePlaceholderAsCustom = debounce(async () => {
   toBeSaved.info.filename = 'custom.yml';
 
   const filePath = await path.join(
-    await path.appDataDir(),
+    await getDataPath(),
     'placeholders',
     toBeSaved.info.filename,
   );
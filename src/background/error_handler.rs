@@ -52,6 +52,8 @@ define_app_errors!(
     Updater(tauri_plugin_updater::Error);
     WinScreenshot(win_screenshot::capture::WSError);
     EvalExpr(evalexpr::EvalexprError);
+    Zip(zip::result::ZipError);
+    Regex(regex::Error);
 );
 
 impl std::fmt::Debug for AppError {
@@ -103,6 +105,13 @@ impl std::fmt::Debug for AppError {
     }
 }
 
+impl AppError {
+    /// error message without the backtrace, intended to be shown to the user
+    pub fn message(&self) -> &str {
+        &self.msg
+    }
+}
+
 impl std::fmt::Display for AppError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self)
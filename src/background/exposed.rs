@@ -7,30 +7,65 @@ use tauri_plugin_shell::ShellExt;
 use crate::error_handler::Result;
 use crate::hook::HookManager;
 use crate::modules::input::Keyboard;
+use crate::modules::launch::{application::Launcher, domain::LaunchStatus};
 use crate::modules::virtual_desk::get_vd_manager;
 use crate::seelen::{get_app_handle, Seelen};
+use crate::seelen_bar::handler::*;
 use crate::seelen_rofi::handler::*;
+use crate::seelen_wall::dim::*;
+use crate::seelen_wall::fit_mode::*;
+use crate::seelen_wall::slideshow::*;
 use crate::seelen_weg::handler::*;
 use crate::seelen_weg::icon_extractor::{
     extract_and_save_icon_from_file, extract_and_save_icon_umid,
 };
 use crate::seelen_wm_v2::handler::*;
 use crate::state::infrastructure::*;
+use crate::system::brightness::schedule::*;
 use crate::system::brightness::*;
 use crate::utils::{
-    is_running_as_appx_package, is_virtual_desktop_supported as virtual_desktop_supported,
+    constants::SEELEN_COMMON, is_running_as_appx_package,
+    is_virtual_desktop_supported as virtual_desktop_supported,
 };
 use crate::windows_api::WindowsApi;
 use crate::winevent::{SyntheticFullscreenData, WinEvent};
-use crate::{log_error, utils};
+use crate::{log_error, trace_lock, utils};
 
+use crate::modules::bluetooth::infrastructure::*;
+use crate::modules::capture::infrastructure::*;
+use crate::modules::desktop_icons::infrastructure::*;
+use crate::modules::favorites::infrastructure::*;
+use crate::modules::file_watcher::infrastructure::*;
+use crate::modules::focus_assist::infrastructure::*;
+use crate::modules::fonts::infrastructure::*;
+use crate::modules::input::infrastructure::*;
+use crate::modules::jump_list::infrastructure::*;
+use crate::modules::launch::infrastructure::*;
+use crate::modules::marketplace::infrastructure::*;
 use crate::modules::media::infrastructure::*;
 use crate::modules::monitors::infrastructure::*;
 use crate::modules::network::infrastructure::*;
+use crate::modules::night_light::infrastructure::*;
 use crate::modules::notifications::infrastructure::*;
+use crate::modules::peripherals::infrastructure::*;
+use crate::modules::pip::infrastructure::*;
 use crate::modules::power::infrastructure::*;
+use crate::modules::presentation::infrastructure::*;
+use crate::modules::processes::infrastructure::*;
+use crate::modules::radios::infrastructure::*;
+use crate::modules::recycle_bin::infrastructure::*;
+use crate::modules::registry::infrastructure::*;
+use crate::modules::scheduled_tasks::infrastructure::*;
+use crate::modules::shortcuts::infrastructure::*;
+use crate::modules::show_desktop::infrastructure::*;
+use crate::modules::spatial_audio::infrastructure::*;
+use crate::modules::storage::infrastructure::*;
 use crate::modules::system_settings::infrastructure::*;
 use crate::modules::tray::infrastructure::*;
+use crate::modules::wallpaper::infrastructure::*;
+use crate::modules::weather::infrastructure::*;
+use crate::modules::window_snap::infrastructure::*;
+use crate::modules::window_style::infrastructure::*;
 
 #[tauri::command(async)]
 fn select_file_on_explorer(path: String) -> Result<()> {
@@ -53,46 +88,13 @@ fn open_file(path: String) -> Result<()> {
 }
 
 #[tauri::command(async)]
-async fn run_as_admin(program: String, args: Vec<String>) -> Result<()> {
-    let command = if args.is_empty() {
-        format!("Start-Process '{}' -Verb runAs", program)
-    } else {
-        format!(
-            "Start-Process '{}' -Verb runAs -ArgumentList '{}'",
-            program,
-            args.join(" ")
-        )
-    };
-    get_app_handle()
-        .shell()
-        .command("powershell")
-        .args([
-            "-NoProfile",
-            "-ExecutionPolicy",
-            "Bypass",
-            "-Command",
-            &command,
-        ])
-        .status()
-        .await?;
-    Ok(())
+fn run_as_admin(program: String, args: Vec<String>) -> Result<LaunchStatus> {
+    Launcher::run_as_admin(&program, &args)
 }
 
 #[tauri::command(async)]
 async fn run(program: String, args: Vec<String>) -> Result<()> {
-    // we create a link file to trick with explorer into a separated process
-    // and without elevation in case Seelen UI was running as admin
-    // this could take some delay like is creating a file but just are some milliseconds
-    // and this exposed funtion is intended to just run certain times
-    let lnk_file = WindowsApi::create_temp_shortcut(&program, &args.join(" "))?;
-    get_app_handle()
-        .shell()
-        .command("explorer")
-        .arg(&lnk_file)
-        .status()
-        .await?;
-    std::fs::remove_file(&lnk_file)?;
-    Ok(())
+    Launcher::run(&program, &args).await
 }
 
 #[tauri::command(async)]
@@ -105,6 +107,12 @@ fn is_appx_package() -> bool {
     is_running_as_appx_package()
 }
 
+/// user data folder, `%APPDATA%/<identifier>` or the `data` folder beside the executable on portable mode
+#[tauri::command(async)]
+fn get_data_path() -> PathBuf {
+    SEELEN_COMMON.data_path().to_path_buf()
+}
+
 #[tauri::command(async)]
 pub fn get_user_envs() -> HashMap<String, String> {
     std::env::vars().collect::<HashMap<String, String>>()
@@ -132,6 +140,16 @@ fn switch_workspace(idx: usize) -> Result<()> {
     get_vd_manager().switch_to(idx)
 }
 
+#[tauri::command(async)]
+fn vd_get_desktop_count() -> Result<usize> {
+    get_vd_manager().get_desktop_count()
+}
+
+#[tauri::command(async)]
+fn vd_set_desktop_count(n: usize, force: Option<bool>) -> Result<()> {
+    get_vd_manager().set_desktop_count(n, force.unwrap_or(false))
+}
+
 #[tauri::command(async)]
 fn send_keys(keys: String) -> Result<()> {
     Keyboard::new().send_keys(&keys)
@@ -164,6 +182,17 @@ fn simulate_fullscreen(webview: WebviewWindow<tauri::Wry>, value: bool) -> Resul
     Ok(())
 }
 
+#[tauri::command(async)]
+fn set_widget_monitor(widget_instance_id: String, device_id: String) -> Result<()> {
+    Seelen::set_widget_monitor(&widget_instance_id, &device_id)
+}
+
+/// lighter alternative to `restart`, only the webviews are recreated
+#[tauri::command(async)]
+fn reload_widgets() -> Result<()> {
+    Seelen::reload_widgets()
+}
+
 #[tauri::command(async)]
 async fn check_for_updates() -> Result<bool> {
     Ok(utils::updater::check_for_updates().await?.is_some())
@@ -188,32 +217,72 @@ pub fn register_invoke_handler(app_builder: Builder<Wry>) -> Builder<Wry> {
         run,
         is_dev_mode,
         is_appx_package,
+        get_data_path,
         open_file,
         run_as_admin,
+        launch_item,
+        launch_or_focus,
+        get_jump_list,
+        add_jump_list_task,
+        get_elevated_items,
+        set_item_elevated,
         select_file_on_explorer,
         is_virtual_desktop_supported,
         get_user_envs,
         show_app_settings,
         switch_workspace,
+        vd_get_desktop_count,
+        vd_set_desktop_count,
         send_keys,
         get_icon,
         get_system_colors,
+        get_window_accent_color,
+        get_night_light,
+        set_night_light,
         simulate_fullscreen,
         check_for_updates,
         install_last_available_update,
         get_connected_monitors,
+        get_monitor_work_area,
+        get_primary_monitor,
+        set_primary_monitor,
+        get_display_mode,
+        set_display_mode,
+        get_monitor_supported_modes,
+        set_monitor_mode,
+        get_lock_keys_state,
+        set_lock_key,
+        mouse_move,
+        mouse_click,
+        mouse_scroll,
+        get_cursor_pos,
+        window_from_point,
         // Seelen Settings
         set_auto_start,
         get_auto_start_status,
         state_get_icon_packs,
         state_get_themes,
+        set_theme_variant,
+        state_get_widget_themes,
+        set_widget_themes,
+        reorder_themes,
+        install_resource_from_url,
+        uninstall_resource,
+        set_resource_pinned,
         state_get_placeholders,
         state_get_layouts,
         state_get_weg_items,
         state_get_settings,
+        state_get_effective_settings,
+        state_get_settings_migrations,
+        get_settings_schema,
+        widget_config_get,
+        widget_config_set,
         state_get_default_settings,
         state_get_default_monitor_settings,
         state_write_settings,
+        state_prune_monitor_configs,
+        state_copy_monitor_config,
         state_write_weg_items,
         state_get_specific_apps_configurations,
         state_get_wallpaper,
@@ -225,6 +294,11 @@ pub fn register_invoke_handler(app_builder: Builder<Wry>) -> Builder<Wry> {
         // Media
         media_prev,
         media_toggle_play_pause,
+        media_seek,
+        get_volume_balance,
+        set_volume_balance,
+        get_spatial_audio_mode,
+        set_spatial_audio_mode,
         media_next,
         set_volume_level,
         media_toggle_mute,
@@ -232,21 +306,110 @@ pub fn register_invoke_handler(app_builder: Builder<Wry>) -> Builder<Wry> {
         // Brightness
         get_main_monitor_brightness,
         set_main_monitor_brightness,
+        get_brightness_schedule,
+        set_brightness_schedule,
+        set_brightness_schedule_enabled,
         // Power
         log_out,
         suspend,
+        hibernate,
+        can_hibernate,
+        schedule_power_action,
+        cancel_scheduled_power_action,
         restart,
+        reload_widgets,
+        set_widget_monitor,
         shutdown,
+        get_power_plans,
+        set_active_power_plan,
+        get_power_mode,
+        get_battery_saver,
+        set_battery_saver,
+        get_battery_alert_settings,
+        set_battery_alert_settings,
+        lock_workstation,
+        get_sessions,
+        log_off_session,
+        // Processes
+        get_processes,
+        kill_process,
+        set_process_priority,
+        set_process_efficiency_mode,
+        get_volumes,
+        recycle_bin_query,
+        recycle_bin_empty,
+        move_to_recycle_bin,
+        create_scheduled_task,
+        list_seelen_tasks,
+        set_scheduled_task_enabled,
+        delete_scheduled_task,
+        favorites_list,
+        favorites_add,
+        favorites_remove,
+        resolve_shortcut,
+        create_shortcut,
+        get_desktop_icons_visible,
+        set_desktop_icons_visible,
+        set_system_wallpaper,
+        get_wall_slideshow,
+        set_wall_slideshow,
+        wall_get_current_slide,
+        wall_next,
+        wall_previous,
+        wall_pause,
+        get_wall_fit_config,
+        set_wall_fit_mode,
+        get_wall_dim_config,
+        set_wall_dim_config,
         // SeelenWeg
         weg_get_items_for_widget,
+        get_window_counts,
+        minimize_app_windows,
+        restore_app_windows,
         weg_close_app,
         weg_kill_app,
         weg_toggle_window_state,
         weg_request_update_previews,
         weg_pin_item,
+        is_app_running,
+        set_native_taskbar_visible,
+        // Fancy Toolbar
+        register_app_bar,
+        unregister_app_bar,
         // Windows Manager
         set_window_position,
         request_focus,
+        get_windows_in_z_order,
+        get_mru_windows,
+        wm_pause,
+        wm_resume,
+        wm_toggle_float,
+        wm_stack,
+        wm_stack_cycle,
+        wm_get_layout_state,
+        wm_save_layout_preset,
+        wm_apply_layout_preset,
+        wm_get_metrics,
+        find_windows,
+        raise_app_windows,
+        flash_window,
+        is_window_elevated,
+        toggle_show_desktop,
+        set_window_opacity,
+        set_window_topmost,
+        toggle_window_topmost,
+        set_window_corner_preference,
+        set_window_border_color,
+        set_backdrop,
+        get_backdrop,
+        set_webview_click_through,
+        snap_window,
+        unsnap_window,
+        snap_window_to_named_zone,
+        pip_clone_window,
+        pip_move,
+        pip_stop,
+        get_snap_layouts,
         // App Launcher
         launcher_get_apps,
         // tray icons
@@ -259,9 +422,43 @@ pub fn register_invoke_handler(app_builder: Builder<Wry>) -> Builder<Wry> {
         wlan_stop_scanning,
         wlan_connect,
         wlan_disconnect,
+        // bluetooth
+        bt_get_devices,
+        bt_connect,
+        bt_disconnect,
+        get_peripheral_batteries,
+        // radios
+        get_airplane_mode,
+        set_airplane_mode,
         // notifications
         notifications_close,
         notifications_close_all,
+        notifications_get_dnd,
+        notifications_set_dnd,
+        get_focus_assist,
+        set_focus_assist,
+        get_presentation_state,
+        get_installed_fonts,
+        // weather
+        get_weather,
+        // marketplace
+        get_resource_index,
+        get_resource_index_url,
+        set_resource_index_url,
+        // screen recording
+        start_recording,
+        stop_recording,
+        ocr_region,
+        pick_color_at,
+        pick_color_at_cursor,
+        // file system
+        watch_path,
+        unwatch_path,
+        // registry
+        registry_read,
+        registry_write,
         language::get_system_languages,
+        language::get_current_input_language,
+        language::set_input_language,
     ])
 }
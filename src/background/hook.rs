@@ -1,8 +1,8 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     path::PathBuf,
     sync::{
-        atomic::{AtomicBool, AtomicIsize, Ordering},
+        atomic::{AtomicBool, AtomicIsize, AtomicU32, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
     thread::JoinHandle,
@@ -12,15 +12,18 @@ use std::{
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
+use schemars::JsonSchema;
 use seelen_core::handlers::SeelenEvent;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tauri::Emitter;
 use windows::Win32::{
-    Foundation::HWND,
+    Foundation::{HWND, LPARAM, WPARAM},
+    System::Threading::GetCurrentThreadId,
     UI::{
         Accessibility::{SetWinEventHook, UnhookWinEvent, HWINEVENTHOOK},
         WindowsAndMessaging::{
-            DispatchMessageW, GetMessageW, TranslateMessage, EVENT_MAX, EVENT_MIN, MSG,
+            DispatchMessageW, GetMessageW, KillTimer, PeekMessageW, PostThreadMessageW, SetTimer,
+            TranslateMessage, EVENT_MAX, EVENT_MIN, MSG, PM_NOREMOVE, WM_APP, WM_USER,
         },
     },
 };
@@ -30,7 +33,12 @@ use crate::{
     log_error,
     modules::{
         input::{domain::Point, Mouse},
+        pip::application::PictureInPicture,
+        presentation::application::PresentationMode,
+        show_desktop::application::ShowDesktop,
+        theme_variables::application::ThemeVariables,
         virtual_desk::{get_vd_manager, VirtualDesktopEvent, VirtualDesktopManager},
+        window_snap::application::WindowSnap,
     },
     seelen::{get_app_handle, Seelen, SEELEN},
     seelen_weg::SeelenWeg,
@@ -46,16 +54,149 @@ lazy_static! {
     static ref HOOK_MANAGER_SKIPPER: Arc<Mutex<HookManagerSkipper>> = Arc::new(Mutex::new(HookManagerSkipper::default()));
     // Last active window omitting all the seelen overlays
     pub static ref LAST_ACTIVE_NOT_SEELEN: AtomicIsize = AtomicIsize::new(WindowsApi::get_foreground_window().0 as _);
+    // Most recently focused windows first, omitting all the seelen overlays
+    static ref MRU_WINDOWS: Mutex<VecDeque<isize>> = Mutex::new(VecDeque::new());
 }
 
+const MRU_CAPACITY: usize = 64;
+
 pub static LOG_WIN_EVENTS: AtomicBool = AtomicBool::new(false);
 
+/// Overrides the coalescing interval of the settings until restart, `u64::MAX` means no override.
+pub static WIN_EVENT_COALESCE_OVERRIDE: AtomicU64 = AtomicU64::new(u64::MAX);
+
+const DEFAULT_COALESCE_MS: u64 = 30;
+const MAX_COALESCE_MS: u64 = 1000;
+
+/// thread running the WinEvent hook, all the events are dispatched on it
+static HOOK_THREAD_ID: AtomicU32 = AtomicU32::new(0);
+/// posted to the hook thread when events are emitted from other threads
+const WM_DISPATCH_QUEUED: u32 = WM_APP + 1;
+/// id of the timer flushing the coalesced events, 0 if not running
+static FLUSH_TIMER: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WinEventsConfig {
+    /// interval in milliseconds to coalesce bursts of events, 0 disables it
+    pub coalesce_ms: u64,
+}
+
+impl Default for WinEventsConfig {
+    fn default() -> Self {
+        Self {
+            coalesce_ms: DEFAULT_COALESCE_MS,
+        }
+    }
+}
+
+struct CoalescedEvent {
+    event: WinEvent,
+    origin: isize,
+    /// end of the current interval
+    due: Instant,
+    /// a repeat arrived during the interval and should be dispatched when it ends
+    trailing: bool,
+}
+
+/// Throttles the same event on the same window, the first one of a burst is dispatched
+/// immediately and the repeats are merged into a single dispatch at the end of the interval.
+#[derive(Default)]
+struct Coalescer {
+    pending: Vec<CoalescedEvent>,
+}
+
+impl Coalescer {
+    /// returns true if the event should be dispatched right now
+    fn on_event(
+        &mut self,
+        event: WinEvent,
+        origin: isize,
+        now: Instant,
+        interval: Duration,
+    ) -> bool {
+        match self
+            .pending
+            .iter_mut()
+            .find(|pending| pending.event == event && pending.origin == origin)
+        {
+            Some(pending) => {
+                pending.trailing = true;
+                false
+            }
+            None => {
+                self.pending.push(CoalescedEvent {
+                    event,
+                    origin,
+                    due: now + interval,
+                    trailing: false,
+                });
+                true
+            }
+        }
+    }
+
+    /// Trailing events of the ended intervals, each of them opens a new interval so a
+    /// continuous burst is still limited to one dispatch by interval.
+    fn take_due(&mut self, now: Instant, interval: Duration) -> Vec<(WinEvent, isize)> {
+        let mut due = Vec::new();
+        self.pending.retain_mut(|pending| {
+            if pending.due > now {
+                return true;
+            }
+            if !pending.trailing {
+                return false;
+            }
+            due.push((pending.event, pending.origin));
+            pending.trailing = false;
+            pending.due = now + interval;
+            true
+        });
+        due
+    }
+
+    /// Closes the intervals of the window, returning the held trailing events.
+    fn take_window(&mut self, origin: isize) -> Vec<(WinEvent, isize)> {
+        let mut held = Vec::new();
+        self.pending.retain(|pending| {
+            if pending.origin != origin {
+                return true;
+            }
+            if pending.trailing {
+                held.push((pending.event, pending.origin));
+            }
+            false
+        });
+        held
+    }
+
+    fn next_due(&self) -> Option<Instant> {
+        self.pending.iter().map(|pending| pending.due).min()
+    }
+}
+
+lazy_static! {
+    static ref COALESCED: Mutex<Coalescer> = Mutex::new(Coalescer::default());
+    /// events emitted from other threads waiting to be dispatched by the hook thread
+    static ref QUEUED: Mutex<VecDeque<(WinEvent, isize)>> = Mutex::new(VecDeque::new());
+}
+
+/// interval to coalesce bursts of events, zero disables it
+fn coalesce_interval() -> Duration {
+    let ms = match WIN_EVENT_COALESCE_OVERRIDE.load(Ordering::Relaxed) {
+        u64::MAX => FULL_STATE.load().extended_settings().win_events.coalesce_ms,
+        ms => ms,
+    };
+    Duration::from_millis(ms.min(MAX_COALESCE_MS))
+}
+
 #[derive(Serialize, Clone)]
 pub struct FocusedApp {
     hwnd: isize,
     title: String,
     name: String,
     exe: Option<PathBuf>,
+    elevated: Option<bool>,
 }
 
 #[derive(Debug)]
@@ -151,6 +292,31 @@ impl HookManager {
         );
     }
 
+    fn update_mru(event: WinEvent, window: &Window) {
+        let addr = window.address();
+        match event {
+            WinEvent::SystemForeground if !window.is_seelen_overlay() => {
+                let mut mru = trace_lock!(MRU_WINDOWS);
+                mru.retain(|w| *w != addr);
+                mru.push_front(addr);
+                mru.truncate(MRU_CAPACITY);
+            }
+            WinEvent::ObjectDestroy => {
+                trace_lock!(MRU_WINDOWS).retain(|w| *w != addr);
+            }
+            _ => {}
+        }
+    }
+
+    /// Windows ordered by last time focused, most recent first
+    pub fn mru_windows() -> Vec<Window> {
+        trace_lock!(MRU_WINDOWS)
+            .iter()
+            .map(|addr| Window::from(*addr))
+            .filter(|w| w.is_window())
+            .collect()
+    }
+
     fn _event(event: WinEvent, origin: HWND) {
         Self::log_event(event, origin);
 
@@ -164,8 +330,16 @@ impl HookManager {
         }
 
         let window = Window::from(origin);
+        Self::update_mru(event, &window);
+        ShowDesktop::on_win_event(event, &window);
+        PictureInPicture::on_win_event(event, &window);
+        WindowSnap::on_win_event(event, &window);
+        PresentationMode::on_win_event(event);
         if event == WinEvent::SystemForeground && !window.is_seelen_overlay() {
             LAST_ACTIVE_NOT_SEELEN.store(origin.0 as _, Ordering::Relaxed);
+            if let Ok(name) = window.app_display_name() {
+                ThemeVariables::on_focused_app_change(&name);
+            }
         }
 
         if event == WinEvent::ObjectFocus || event == WinEvent::SystemForeground {
@@ -179,6 +353,7 @@ impl HookManager {
                         .app_display_name()
                         .unwrap_or(String::from("Error on App Name")),
                     exe: window.exe().ok(),
+                    elevated: window.process().is_elevated(),
                 },
             ));
         }
@@ -240,7 +415,100 @@ impl HookManager {
         }
     }
 
+    /// Only location changes are coalesced. Focus events are never held as a late dispatch
+    /// could arrive after another window took the focus, and lifecycle events like
+    /// create/destroy are always dispatched immediately.
+    fn is_coalescable(event: WinEvent) -> bool {
+        event == WinEvent::ObjectLocationChange
+    }
+
+    /// Coalesces rapid repeats of the same event on the same window, the first event of a burst
+    /// is dispatched immediately and the repeats during the interval are merged into a single
+    /// dispatch at the end of it. Events emitted from other threads are sent to the hook thread
+    /// so handlers always run on the same thread.
     pub fn emit_event(event: WinEvent, origin: HWND) {
+        let hook_thread = HOOK_THREAD_ID.load(Ordering::Acquire);
+        if hook_thread != 0 && hook_thread != unsafe { GetCurrentThreadId() } {
+            trace_lock!(QUEUED).push_back((event, origin.0 as isize));
+            let posted = unsafe {
+                PostThreadMessageW(hook_thread, WM_DISPATCH_QUEUED, WPARAM(0), LPARAM(0))
+            };
+            if let Err(err) = posted {
+                log::error!("Failed to send WinEvent to the hook thread: {:?}", err);
+            }
+            return;
+        }
+
+        let interval = coalesce_interval();
+        if interval.is_zero() {
+            return HookManager::dispatch(event, origin);
+        }
+
+        if !Self::is_coalescable(event) {
+            // held events of the same window are delivered first to preserve the order
+            let held = trace_lock!(COALESCED).take_window(origin.0 as isize);
+            for (held_event, held_origin) in held {
+                HookManager::dispatch(held_event, HWND(held_origin as _));
+            }
+            return HookManager::dispatch(event, origin);
+        }
+
+        let dispatch_now =
+            trace_lock!(COALESCED).on_event(event, origin.0 as isize, Instant::now(), interval);
+        if !dispatch_now {
+            return;
+        }
+
+        // intervals have the same length, a running timer already ends before the new one
+        if FLUSH_TIMER.load(Ordering::Acquire) == 0 {
+            Self::schedule_flush();
+        }
+        HookManager::dispatch(event, origin);
+    }
+
+    /// Arms the timer of the hook thread for the end of the next interval, or stops it if
+    /// there is nothing left to flush.
+    fn schedule_flush() {
+        let timer = FLUSH_TIMER.load(Ordering::Acquire);
+        let next_due = trace_lock!(COALESCED).next_due();
+        let Some(due) = next_due else {
+            if timer != 0 {
+                let _ = unsafe { KillTimer(HWND::default(), timer) };
+                FLUSH_TIMER.store(0, Ordering::Release);
+            }
+            return;
+        };
+        let elapse = due.saturating_duration_since(Instant::now()).as_millis() as u32;
+        // with an existing id the timer is replaced instead of creating a new one
+        let timer = unsafe {
+            SetTimer(
+                HWND::default(),
+                timer,
+                elapse.max(1),
+                Some(Self::flush_coalesced),
+            )
+        };
+        FLUSH_TIMER.store(timer, Ordering::Release);
+    }
+
+    /// Dispatches the trailing events of the ended intervals, called by the message loop
+    /// of the hook thread.
+    unsafe extern "system" fn flush_coalesced(_hwnd: HWND, _msg: u32, _id: usize, _time: u32) {
+        let due = trace_lock!(COALESCED).take_due(Instant::now(), coalesce_interval());
+        for (event, origin) in due {
+            HookManager::dispatch(event, HWND(origin as _));
+        }
+        Self::schedule_flush();
+    }
+
+    fn dispatch_queued() {
+        let queued = std::mem::take(&mut *trace_lock!(QUEUED));
+        for (event, origin) in queued {
+            HookManager::emit_event(event, HWND(origin as _));
+        }
+    }
+
+    fn dispatch(event: WinEvent, origin: HWND) {
         HookManager::_event(event, origin);
         if let Ok(synthetics) = event.get_synthetics(origin) {
             for synthetic_event in synthetics {
@@ -368,10 +636,17 @@ pub fn register_win_hook() -> Result<()> {
     spawn_named_thread("WinEventHook", move || unsafe {
         SetWinEventHook(EVENT_MIN, EVENT_MAX, None, Some(win_event_hook), 0, 0, 0);
         let mut msg: MSG = MSG::default();
+        // forces the creation of the message queue before other threads post to it
+        let _ = PeekMessageW(&mut msg, HWND::default(), WM_USER, WM_USER, PM_NOREMOVE);
+        HOOK_THREAD_ID.store(GetCurrentThreadId(), Ordering::Release);
         loop {
             if !GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
                 break;
             };
+            if msg.message == WM_DISPATCH_QUEUED {
+                HookManager::dispatch_queued();
+                continue;
+            }
             let _ = TranslateMessage(&msg);
             DispatchMessageW(&msg);
         }
@@ -401,3 +676,84 @@ pub fn register_win_hook() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(30);
+
+    #[test]
+    fn focus_events_are_never_coalesced() {
+        assert!(HookManager::is_coalescable(WinEvent::ObjectLocationChange));
+        assert!(!HookManager::is_coalescable(WinEvent::SystemForeground));
+        assert!(!HookManager::is_coalescable(WinEvent::ObjectFocus));
+    }
+
+    #[test]
+    fn first_event_of_a_burst_is_dispatched_immediately() {
+        let mut coalescer = Coalescer::default();
+        let now = Instant::now();
+        assert!(coalescer.on_event(WinEvent::ObjectLocationChange, 1, now, INTERVAL));
+        assert!(coalescer.take_due(now + INTERVAL, INTERVAL).is_empty());
+        assert!(coalescer.next_due().is_none());
+    }
+
+    #[test]
+    fn burst_is_merged_into_a_single_trailing_dispatch() {
+        let mut coalescer = Coalescer::default();
+        let start = Instant::now();
+        let mut dispatched = 0;
+        for ms in 0..20 {
+            let now = start + Duration::from_millis(ms);
+            if coalescer.on_event(WinEvent::ObjectLocationChange, 1, now, INTERVAL) {
+                dispatched += 1;
+            }
+        }
+        assert_eq!(dispatched, 1);
+
+        let end = start + INTERVAL;
+        assert!(coalescer
+            .take_due(end - Duration::from_millis(1), INTERVAL)
+            .is_empty());
+        assert_eq!(
+            coalescer.take_due(end, INTERVAL),
+            vec![(WinEvent::ObjectLocationChange, 1)]
+        );
+        // a quiet interval after the trailing dispatch closes the burst
+        assert!(coalescer.take_due(end + INTERVAL, INTERVAL).is_empty());
+        assert!(coalescer.next_due().is_none());
+    }
+
+    #[test]
+    fn windows_and_events_are_coalesced_independently() {
+        let mut coalescer = Coalescer::default();
+        let now = Instant::now();
+        assert!(coalescer.on_event(WinEvent::ObjectLocationChange, 1, now, INTERVAL));
+        assert!(coalescer.on_event(WinEvent::ObjectLocationChange, 2, now, INTERVAL));
+        assert!(coalescer.on_event(WinEvent::ObjectNameChange, 1, now, INTERVAL));
+        assert!(!coalescer.on_event(WinEvent::ObjectNameChange, 1, now, INTERVAL));
+    }
+
+    #[test]
+    fn take_window_returns_only_held_events_of_the_window() {
+        let mut coalescer = Coalescer::default();
+        let now = Instant::now();
+        coalescer.on_event(WinEvent::ObjectLocationChange, 1, now, INTERVAL);
+        coalescer.on_event(WinEvent::ObjectLocationChange, 1, now, INTERVAL);
+        coalescer.on_event(WinEvent::ObjectNameChange, 1, now, INTERVAL);
+        coalescer.on_event(WinEvent::ObjectLocationChange, 2, now, INTERVAL);
+        coalescer.on_event(WinEvent::ObjectLocationChange, 2, now, INTERVAL);
+
+        assert_eq!(
+            coalescer.take_window(1),
+            vec![(WinEvent::ObjectLocationChange, 1)]
+        );
+        // the interval of the window is closed so the next event is dispatched immediately
+        assert!(coalescer.on_event(WinEvent::ObjectLocationChange, 1, now, INTERVAL));
+        assert_eq!(
+            coalescer.take_due(now + INTERVAL, INTERVAL),
+            vec![(WinEvent::ObjectLocationChange, 2)]
+        );
+    }
+}
@@ -105,11 +105,16 @@ impl SeelenInstanceContainer {
 
         for (id, widget) in &settings.widgets {
             // Todo: filter by widget settings (enabled state)
-            if self.widgets.contains_key(id) || widget.html.is_none() {
+            if self.widgets.contains_key(id)
+                || widget.html.is_none()
+                || !WidgetInstance::should_load_on(&id.to_string(), &self.id)
+            {
                 continue;
             }
-            self.widgets
-                .insert(id.clone(), WidgetInstance::load(widget.clone())?);
+            self.widgets.insert(
+                id.clone(),
+                WidgetInstance::load(widget.clone(), &self.monitor)?,
+            );
         }
 
         Ok(())
@@ -36,12 +36,17 @@ use modules::{
         application::{attach_console, is_just_getting_info, SEELEN_COMMAND_LINE},
         Client,
     },
+    desktop_icons::application::DesktopIcons,
+    file_watcher::application::release_webview_path_watchers,
+    theme_variables::application::ThemeVariables,
     tray::application::ensure_tray_overflow_creation,
+    window_style::application::WidgetBackdrops,
 };
 use plugins::register_plugins;
 use seelen::{Seelen, SEELEN};
 use seelen_core::state::Settings;
-use tauri::webview_version;
+use seelen_weg::SeelenWeg;
+use tauri::{webview::PageLoadEvent, webview_version, WindowEvent};
 use tray::try_register_tray_icon;
 use utils::{
     integrity::{
@@ -83,6 +88,12 @@ fn register_panic_hook() {
             cause,
             string_location
         );
+
+        // the process is going down so don't leave the user without taskbar or desktop icons
+        if std::thread::current().name() == Some("main") {
+            SeelenWeg::restore_taskbar_on_panic();
+            DesktopIcons::restore_on_panic();
+        }
     }));
 }
 
@@ -220,6 +231,18 @@ fn main() -> Result<()> {
     app_builder = register_invoke_handler(app_builder);
 
     let app = app_builder
+        .on_page_load(|webview, payload| match payload.event() {
+            PageLoadEvent::Started => release_webview_path_watchers(webview.label()),
+            PageLoadEvent::Finished => {
+                WidgetBackdrops::on_page_load(webview);
+                ThemeVariables::on_page_load(webview);
+            }
+        })
+        .on_window_event(|window, event| {
+            if let WindowEvent::Destroyed = event {
+                release_webview_path_watchers(window.label());
+            }
+        })
         .setup(|app| {
             if let Err(err) = setup(app) {
                 log::error!("Error while setting up: {:?}", err);
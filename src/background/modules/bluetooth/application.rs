@@ -0,0 +1,171 @@
+use std::{collections::HashMap, sync::Arc};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use windows::{
+    core::HSTRING,
+    Devices::{
+        Bluetooth::{
+            BluetoothAdapter, BluetoothCacheMode, BluetoothConnectionStatus,
+            BluetoothDevice as WinBluetoothDevice, BluetoothError,
+        },
+        Enumeration::DeviceInformation,
+        Radios::Radio,
+    },
+    Foundation::{EventRegistrationToken, TypedEventHandler},
+};
+
+use crate::{error_handler::Result, event_manager, log_error, windows_api::WindowsApi};
+
+use super::domain::{BluetoothDevice, BluetoothRadioState, BluetoothState};
+
+lazy_static! {
+    pub static ref BLUETOOTH_MANAGER: Arc<Mutex<BluetoothManager>> =
+        Arc::new(Mutex::new(BluetoothManager::new()));
+}
+
+#[derive(Debug, Clone)]
+pub enum BluetoothEvent {
+    RadioStateChanged,
+    ConnectionStatusChanged(String),
+}
+
+pub struct BluetoothManager {
+    radio: Option<(Radio, EventRegistrationToken)>,
+    /// paired devices tracked to receive connection status changes
+    devices: HashMap<String, (WinBluetoothDevice, EventRegistrationToken)>,
+}
+
+unsafe impl Send for BluetoothManager {}
+
+event_manager!(BluetoothManager, BluetoothEvent);
+
+impl BluetoothManager {
+    fn new() -> Self {
+        Self {
+            radio: None,
+            devices: HashMap::new(),
+        }
+    }
+
+    fn default_radio() -> Result<Radio> {
+        let adapter = BluetoothAdapter::GetDefaultAsync()?.get()?;
+        Ok(adapter.GetRadioAsync()?.get()?)
+    }
+
+    /// adapters not present or turned off are reported as a state instead of an error
+    pub fn radio_state() -> BluetoothRadioState {
+        match Self::default_radio().and_then(|radio| Ok(radio.State()?)) {
+            Ok(state) => state.into(),
+            Err(_) => BluetoothRadioState::Unavailable,
+        }
+    }
+
+    pub fn register_events(&mut self) -> Result<()> {
+        if self.radio.is_none() {
+            if let Ok(radio) = Self::default_radio() {
+                let token = radio.StateChanged(&TypedEventHandler::new(|_, _| {
+                    log_error!(Self::event_tx().send(BluetoothEvent::RadioStateChanged));
+                    Ok(())
+                }))?;
+                self.radio = Some((radio, token));
+            }
+        }
+        for id in Self::paired_device_ids()? {
+            self.device(&id)?;
+        }
+        Ok(())
+    }
+
+    pub fn release(&mut self) {
+        if let Some((radio, token)) = self.radio.take() {
+            log_error!(radio.RemoveStateChanged(token));
+        }
+        for (_id, (device, token)) in self.devices.drain() {
+            log_error!(device.RemoveConnectionStatusChanged(token));
+            log_error!(device.Close());
+        }
+    }
+
+    fn paired_device_ids() -> Result<Vec<String>> {
+        let selector = WinBluetoothDevice::GetDeviceSelectorFromPairingState(true)?;
+        let collection = DeviceInformation::FindAllAsyncAqsFilter(&selector)?.get()?;
+        let mut ids = Vec::new();
+        for info in collection {
+            ids.push(info.Id()?.to_string_lossy());
+        }
+        Ok(ids)
+    }
+
+    /// get or create the tracked WinRT device object
+    fn device(&mut self, id: &str) -> Result<WinBluetoothDevice> {
+        if let Some((device, _)) = self.devices.get(id) {
+            return Ok(device.clone());
+        }
+
+        let device = WinBluetoothDevice::FromIdAsync(&HSTRING::from(id))?.get()?;
+        let device_id = id.to_owned();
+        let token = device.ConnectionStatusChanged(&TypedEventHandler::new(move |_, _| {
+            log_error!(
+                Self::event_tx().send(BluetoothEvent::ConnectionStatusChanged(device_id.clone()))
+            );
+            Ok(())
+        }))?;
+
+        self.devices.insert(id.to_owned(), (device.clone(), token));
+        Ok(device)
+    }
+
+    pub fn get_state(&mut self) -> Result<BluetoothState> {
+        let radio = Self::radio_state();
+        let mut devices = Vec::new();
+
+        if radio != BluetoothRadioState::Unavailable {
+            for id in Self::paired_device_ids()? {
+                let device = self.device(&id)?;
+                devices.push(BluetoothDevice {
+                    name: device.Name()?.to_string_lossy(),
+                    address: device.BluetoothAddress()?,
+                    connected: radio == BluetoothRadioState::On
+                        && device.ConnectionStatus()? == BluetoothConnectionStatus::Connected,
+                    id,
+                });
+            }
+        }
+
+        Ok(BluetoothState { radio, devices })
+    }
+
+    /// WinRT has no explicit connect api, requesting the services without cache
+    /// forces the system to open a connection with the device.
+    pub fn connect(&mut self, id: &str) -> Result<()> {
+        if Self::radio_state() != BluetoothRadioState::On {
+            return Err("Bluetooth adapter is not turned on".into());
+        }
+
+        let device = self.device(id)?;
+        let result = device
+            .GetRfcommServicesWithCacheModeAsync(BluetoothCacheMode::Uncached)?
+            .get()?;
+
+        let error = result.Error()?;
+        if error != BluetoothError::Success {
+            return Err(format!("Failed to connect to bluetooth device: {:?}", error).into());
+        }
+        Ok(())
+    }
+
+    /// WinRT has no disconnect api either, the radio driver is asked to drop the
+    /// connection by address, the device stays paired.
+    pub fn disconnect(&mut self, id: &str) -> Result<()> {
+        if Self::radio_state() != BluetoothRadioState::On {
+            return Err("Bluetooth adapter is not turned on".into());
+        }
+
+        let device = self.device(id)?;
+        if device.ConnectionStatus()? != BluetoothConnectionStatus::Connected {
+            return Ok(());
+        }
+        WindowsApi::disconnect_bluetooth_device(device.BluetoothAddress()?)
+    }
+}
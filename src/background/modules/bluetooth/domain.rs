@@ -0,0 +1,39 @@
+use serde::Serialize;
+use windows::Devices::Radios::RadioState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BluetoothRadioState {
+    On,
+    Off,
+    /// disabled by hardware switch or by the device manager
+    Disabled,
+    /// there is no bluetooth adapter on the system
+    Unavailable,
+}
+
+impl From<RadioState> for BluetoothRadioState {
+    fn from(state: RadioState) -> Self {
+        match state {
+            RadioState::On => Self::On,
+            RadioState::Off => Self::Off,
+            RadioState::Disabled => Self::Disabled,
+            _ => Self::Unavailable,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BluetoothDevice {
+    pub id: String,
+    pub name: String,
+    pub address: u64,
+    pub connected: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BluetoothState {
+    pub radio: BluetoothRadioState,
+    pub devices: Vec<BluetoothDevice>,
+}
@@ -0,0 +1,50 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::Emitter;
+
+use crate::{error_handler::Result, log_error, seelen::get_app_handle, trace_lock};
+
+use super::{
+    application::{BluetoothManager, BLUETOOTH_MANAGER},
+    domain::BluetoothState,
+};
+
+fn emit_bluetooth_state() -> Result<()> {
+    let state = trace_lock!(BLUETOOTH_MANAGER).get_state()?;
+    get_app_handle().emit("bluetooth-state-changed", state)?;
+    Ok(())
+}
+
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+pub fn register_bluetooth_events() {
+    let was_registered = REGISTERED.swap(true, Ordering::AcqRel);
+    std::thread::spawn(move || {
+        if !was_registered {
+            log::trace!("Registering bluetooth events");
+            log_error!(trace_lock!(BLUETOOTH_MANAGER).register_events());
+            BluetoothManager::subscribe(|_event| log_error!(emit_bluetooth_state()));
+        }
+        log_error!(emit_bluetooth_state());
+    });
+}
+
+pub fn release_bluetooth_events() {
+    if REGISTERED.load(Ordering::Acquire) {
+        trace_lock!(BLUETOOTH_MANAGER).release();
+    }
+}
+
+#[tauri::command(async)]
+pub fn bt_get_devices() -> Result<BluetoothState> {
+    trace_lock!(BLUETOOTH_MANAGER).get_state()
+}
+
+#[tauri::command(async)]
+pub fn bt_connect(id: String) -> Result<()> {
+    trace_lock!(BLUETOOTH_MANAGER).connect(&id)
+}
+
+#[tauri::command(async)]
+pub fn bt_disconnect(id: String) -> Result<()> {
+    trace_lock!(BLUETOOTH_MANAGER).disconnect(&id)
+}
@@ -0,0 +1,74 @@
+use windows::Win32::{
+    Foundation::HWND,
+    Graphics::Gdi::{GetDC, GetPixel, ReleaseDC, CLR_INVALID},
+    UI::WindowsAndMessaging::{
+        GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
+        SM_YVIRTUALSCREEN,
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    modules::{
+        capture::domain::PickedColor,
+        input::{domain::Point, Mouse},
+    },
+    windows_api::WindowsApi,
+};
+
+pub struct ColorPicker;
+impl ColorPicker {
+    /// coordinates of the virtual screen can be negative for monitors at the left/top of the primary
+    fn is_on_virtual_screen(x: i32, y: i32) -> bool {
+        let (left, top, width, height) = unsafe {
+            (
+                GetSystemMetrics(SM_XVIRTUALSCREEN),
+                GetSystemMetrics(SM_YVIRTUALSCREEN),
+                GetSystemMetrics(SM_CXVIRTUALSCREEN),
+                GetSystemMetrics(SM_CYVIRTUALSCREEN),
+            )
+        };
+        x >= left && x < left + width && y >= top && y < top + height
+    }
+
+    fn read_pixel(x: i32, y: i32) -> Result<PickedColor> {
+        if !Self::is_on_virtual_screen(x, y) {
+            return Err(format!("({}, {}) is outside of the screen", x, y).into());
+        }
+
+        let color = unsafe {
+            // screen DC spans the whole virtual screen
+            let hdc = GetDC(HWND::default());
+            if hdc.is_invalid() {
+                return Err("Failed to get the screen device context".into());
+            }
+            let color = GetPixel(hdc, x, y);
+            ReleaseDC(HWND::default(), hdc);
+            color
+        };
+
+        if color.0 == CLR_INVALID {
+            return Err(format!("Failed to read the pixel at ({}, {})", x, y).into());
+        }
+
+        // COLORREF is 0x00BBGGRR
+        let value = color.0;
+        Ok(PickedColor::new(
+            (value & 0xFF) as u8,
+            ((value >> 8) & 0xFF) as u8,
+            ((value >> 16) & 0xFF) as u8,
+        ))
+    }
+
+    /// `x` and `y` are physical screen coordinates
+    pub fn pick_at(x: i32, y: i32) -> Result<PickedColor> {
+        WindowsApi::with_physical_coordinates(|| Self::read_pixel(x, y))
+    }
+
+    pub fn pick_at_cursor() -> Result<PickedColor> {
+        WindowsApi::with_physical_coordinates(|| {
+            let pos: Point = Mouse::get_cursor_pos()?;
+            Self::read_pixel(pos.get_x(), pos.get_y())
+        })
+    }
+}
@@ -0,0 +1,192 @@
+pub mod color_picker;
+pub mod ocr;
+pub mod recorder;
+
+use windows::{
+    core::Interface,
+    Graphics::{
+        Capture::{Direct3D11CaptureFramePool, GraphicsCaptureItem, GraphicsCaptureSession},
+        DirectX::{Direct3D11::IDirect3DDevice, DirectXPixelFormat},
+        SizeInt32,
+    },
+    Win32::{
+        Foundation::HMODULE,
+        Graphics::{
+            Direct3D::D3D_DRIVER_TYPE_HARDWARE,
+            Direct3D11::{
+                D3D11CreateDevice, ID3D11Device, ID3D11DeviceContext, ID3D11Texture2D,
+                D3D11_CPU_ACCESS_READ, D3D11_CREATE_DEVICE_BGRA_SUPPORT, D3D11_MAPPED_SUBRESOURCE,
+                D3D11_MAP_READ, D3D11_SDK_VERSION, D3D11_TEXTURE2D_DESC, D3D11_USAGE_STAGING,
+            },
+            Dxgi::{
+                Common::{DXGI_FORMAT_B8G8R8A8_UNORM, DXGI_SAMPLE_DESC},
+                IDXGIDevice,
+            },
+            Gdi::HMONITOR,
+        },
+        System::WinRT::{
+            Direct3D11::{CreateDirect3D11DeviceFromDXGIDevice, IDirect3DDxgiInterfaceAccess},
+            Graphics::Capture::IGraphicsCaptureItemInterop,
+        },
+    },
+};
+
+use crate::{error_handler::Result, windows_api::monitor::Monitor};
+
+pub use recorder::ScreenRecorder;
+
+/// Top-down BGRA pixels without row padding
+pub struct CapturedFrame {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+/// Windows Graphics Capture session of a whole monitor, frames are read back to the CPU
+pub struct MonitorCapture {
+    item_size: SizeInt32,
+    d3d_context: ID3D11DeviceContext,
+    staging: ID3D11Texture2D,
+    frame_pool: Direct3D11CaptureFramePool,
+    session: GraphicsCaptureSession,
+}
+
+impl MonitorCapture {
+    fn create_d3d_device() -> Result<(ID3D11Device, ID3D11DeviceContext)> {
+        let mut device = None;
+        let mut context = None;
+        unsafe {
+            D3D11CreateDevice(
+                None,
+                D3D_DRIVER_TYPE_HARDWARE,
+                HMODULE::default(),
+                D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+                None,
+                D3D11_SDK_VERSION,
+                Some(&mut device),
+                None,
+                Some(&mut context),
+            )?;
+        }
+        match (device, context) {
+            (Some(device), Some(context)) => Ok((device, context)),
+            _ => Err("Failed to create D3D11 device".into()),
+        }
+    }
+
+    fn create_capture_item(monitor: HMONITOR) -> Result<GraphicsCaptureItem> {
+        let interop = windows::core::factory::<GraphicsCaptureItem, IGraphicsCaptureItemInterop>()?;
+        Ok(unsafe { interop.CreateForMonitor(monitor)? })
+    }
+
+    pub fn new(device_id: &str) -> Result<Self> {
+        let monitor = Monitor::by_id(device_id).ok_or("Monitor not found")?;
+
+        let (d3d_device, d3d_context) = Self::create_d3d_device()?;
+        let dxgi_device: IDXGIDevice = d3d_device.cast()?;
+        let device: IDirect3DDevice =
+            unsafe { CreateDirect3D11DeviceFromDXGIDevice(&dxgi_device)? }.cast()?;
+
+        let item = Self::create_capture_item(monitor.handle())?;
+        let item_size = item.Size()?;
+
+        let desc = D3D11_TEXTURE2D_DESC {
+            Width: item_size.Width as u32,
+            Height: item_size.Height as u32,
+            MipLevels: 1,
+            ArraySize: 1,
+            Format: DXGI_FORMAT_B8G8R8A8_UNORM,
+            SampleDesc: DXGI_SAMPLE_DESC {
+                Count: 1,
+                Quality: 0,
+            },
+            Usage: D3D11_USAGE_STAGING,
+            CPUAccessFlags: D3D11_CPU_ACCESS_READ.0 as u32,
+            ..Default::default()
+        };
+        let mut staging = None;
+        unsafe { d3d_device.CreateTexture2D(&desc, None, Some(&mut staging))? };
+        let staging = staging.ok_or("Failed to create staging texture")?;
+
+        let frame_pool = Direct3D11CaptureFramePool::CreateFreeThreaded(
+            &device,
+            DirectXPixelFormat::B8G8R8A8UIntNormalized,
+            2,
+            item_size,
+        )?;
+        let session = frame_pool.CreateCaptureSession(&item)?;
+        session.StartCapture()?;
+
+        Ok(Self {
+            item_size,
+            d3d_context,
+            staging,
+            frame_pool,
+            session,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.item_size.Width as u32
+    }
+
+    pub fn height(&self) -> u32 {
+        self.item_size.Height as u32
+    }
+
+    /// returns the newest frame of the pool, if the screen changed since the last call
+    pub fn latest_frame(&self) -> Result<Option<CapturedFrame>> {
+        let mut latest = None;
+        while let Ok(frame) = self.frame_pool.TryGetNextFrame() {
+            latest = Some(frame);
+        }
+
+        let frame = match latest {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+
+        // resolution changed while capturing, the staging texture no longer matches
+        if frame.ContentSize()? != self.item_size {
+            frame.Close()?;
+            return Ok(None);
+        }
+
+        let access: IDirect3DDxgiInterfaceAccess = frame.Surface()?.cast()?;
+        let texture: ID3D11Texture2D = unsafe { access.GetInterface()? };
+
+        let width = self.width();
+        let height = self.height();
+        let row_len = width as usize * 4;
+        let mut pixels = vec![0u8; row_len * height as usize];
+        unsafe {
+            self.d3d_context.CopyResource(&self.staging, &texture);
+            let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+            self.d3d_context
+                .Map(&self.staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))?;
+
+            let src = mapped.pData as *const u8;
+            for y in 0..height as usize {
+                std::ptr::copy_nonoverlapping(
+                    src.add(y * mapped.RowPitch as usize),
+                    pixels.as_mut_ptr().add(y * row_len),
+                    row_len,
+                );
+            }
+            self.d3d_context.Unmap(&self.staging, 0);
+        }
+
+        frame.Close()?;
+        Ok(Some(CapturedFrame {
+            width,
+            height,
+            pixels,
+        }))
+    }
+
+    pub fn close(self) -> Result<()> {
+        self.session.Close()?;
+        self.frame_pool.Close()?;
+        Ok(())
+    }
+}
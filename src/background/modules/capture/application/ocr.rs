@@ -0,0 +1,159 @@
+use seelen_core::rect::Rect;
+use windows::{
+    core::HSTRING,
+    Globalization::Language,
+    Graphics::Imaging::{BitmapPixelFormat, SoftwareBitmap},
+    Media::Ocr::OcrEngine,
+    Storage::Streams::DataWriter,
+    Win32::{
+        Foundation::HWND,
+        Graphics::Gdi::{
+            BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC,
+            GetDIBits, ReleaseDC, SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB,
+            DIB_RGB_COLORS, SRCCOPY,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    windows_api::{monitor::Monitor, WindowsApi},
+};
+
+use super::CapturedFrame;
+
+pub struct ScreenOcr;
+impl ScreenOcr {
+    fn engine(language: Option<String>) -> Result<OcrEngine> {
+        if OcrEngine::AvailableRecognizerLanguages()?.Size()? == 0 {
+            return Err("No OCR language pack is installed".into());
+        }
+
+        let engine = match language {
+            Some(tag) => {
+                let language = Language::CreateLanguage(&HSTRING::from(&tag))?;
+                if !OcrEngine::IsLanguageSupported(&language)? {
+                    return Err(format!("OCR language pack for {} is not installed", tag).into());
+                }
+                OcrEngine::TryCreateFromLanguage(&language)?
+            }
+            // system default, the first user profile language with an installed pack
+            None => OcrEngine::TryCreateFromUserProfileLanguages()?,
+        };
+        Ok(engine)
+    }
+
+    /// The region is copied from the screen DC as the color picker does, a Graphics Capture
+    /// session for a single frame is too heavy and shows the capture border on each call.
+    /// `rect` is relative to the monitor and in physical pixels.
+    fn capture_region(monitor_id: &str, rect: &Rect) -> Result<CapturedFrame> {
+        let monitor = Monitor::by_id(monitor_id).ok_or("Monitor not found")?;
+        let bounds = monitor.rect()?;
+        let monitor_width = bounds.right - bounds.left;
+        let monitor_height = bounds.bottom - bounds.top;
+
+        let left = rect.left.clamp(0, monitor_width);
+        let top = rect.top.clamp(0, monitor_height);
+        let right = rect.right.clamp(0, monitor_width);
+        let bottom = rect.bottom.clamp(0, monitor_height);
+        if right <= left || bottom <= top {
+            return Err("The region is empty or outside of the monitor".into());
+        }
+
+        let width = right - left;
+        let height = bottom - top;
+        let mut pixels = vec![0u8; width as usize * height as usize * 4];
+        unsafe {
+            let hdc_screen = GetDC(HWND::default());
+            if hdc_screen.is_invalid() {
+                return Err("Failed to get the screen device context".into());
+            }
+            let hdc_mem = CreateCompatibleDC(hdc_screen);
+            let bitmap = CreateCompatibleBitmap(hdc_screen, width, height);
+            let previous = SelectObject(hdc_mem, bitmap);
+
+            let copied = BitBlt(
+                hdc_mem,
+                0,
+                0,
+                width,
+                height,
+                hdc_screen,
+                bounds.left + left,
+                bounds.top + top,
+                SRCCOPY,
+            );
+
+            // negative height for top-down rows
+            let mut info = BITMAPINFO {
+                bmiHeader: BITMAPINFOHEADER {
+                    biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                    biWidth: width,
+                    biHeight: -height,
+                    biPlanes: 1,
+                    biBitCount: 32,
+                    biCompression: BI_RGB.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let lines = GetDIBits(
+                hdc_mem,
+                bitmap,
+                0,
+                height as u32,
+                Some(pixels.as_mut_ptr() as *mut _),
+                &mut info,
+                DIB_RGB_COLORS,
+            );
+
+            SelectObject(hdc_mem, previous);
+            let _ = DeleteObject(bitmap);
+            let _ = DeleteDC(hdc_mem);
+            ReleaseDC(HWND::default(), hdc_screen);
+
+            copied?;
+            if lines == 0 {
+                return Err("Failed to read the captured region".into());
+            }
+        }
+
+        Ok(CapturedFrame {
+            width: width as u32,
+            height: height as u32,
+            pixels,
+        })
+    }
+
+    /// lines are separated by `\n`
+    pub fn recognize_region(
+        monitor_id: &str,
+        rect: &Rect,
+        language: Option<String>,
+    ) -> Result<String> {
+        let engine = Self::engine(language)?;
+
+        let frame =
+            WindowsApi::with_physical_coordinates(|| Self::capture_region(monitor_id, rect))?;
+        let max_dimension = OcrEngine::MaxImageDimension()?;
+        if frame.width > max_dimension || frame.height > max_dimension {
+            return Err(format!("The region exceeds the OCR limit of {}px", max_dimension).into());
+        }
+
+        let writer = DataWriter::new()?;
+        writer.WriteBytes(&frame.pixels)?;
+        let bitmap = SoftwareBitmap::CreateCopyFromBuffer(
+            &writer.DetachBuffer()?,
+            BitmapPixelFormat::Bgra8,
+            frame.width as i32,
+            frame.height as i32,
+        )?;
+
+        let result = engine.RecognizeAsync(&bitmap)?.get()?;
+        let mut lines = Vec::new();
+        for line in result.Lines()? {
+            lines.push(line.Text()?.to_string_lossy());
+        }
+        Ok(lines.join("\n"))
+    }
+}
@@ -0,0 +1,261 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use tauri::Emitter;
+use windows::{
+    core::HSTRING,
+    Win32::Media::MediaFoundation::{
+        IMFSinkWriter, MFCreateMediaType, MFCreateMemoryBuffer, MFCreateSample,
+        MFCreateSinkWriterFromURL, MFMediaType_Video, MFShutdown, MFStartup, MFVideoFormat_H264,
+        MFVideoFormat_RGB32, MFVideoInterlace_Progressive, MFSTARTUP_FULL, MF_MT_AVG_BITRATE,
+        MF_MT_FRAME_RATE, MF_MT_FRAME_SIZE, MF_MT_INTERLACE_MODE, MF_MT_MAJOR_TYPE,
+        MF_MT_PIXEL_ASPECT_RATIO, MF_MT_SUBTYPE, MF_VERSION,
+    },
+};
+
+use crate::{
+    error_handler::Result, log_error, modules::capture::domain::RecordingProgress,
+    seelen::get_app_handle, trace_lock, utils::spawn_named_thread,
+};
+
+use super::{CapturedFrame, MonitorCapture};
+
+lazy_static! {
+    static ref ACTIVE_RECORDING: Mutex<Option<ActiveRecording>> = Mutex::new(None);
+}
+
+struct ActiveRecording {
+    path: PathBuf,
+    stop_signal: Arc<AtomicBool>,
+    thread: JoinHandle<Result<()>>,
+}
+
+/// Frames are captured with Windows Graphics Capture and encoded to H264 by a Media Foundation sink writer.
+struct Recorder {
+    device_id: String,
+    path: PathBuf,
+    fps: u32,
+    width: u32,
+    height: u32,
+    capture: MonitorCapture,
+    writer: IMFSinkWriter,
+    stream_index: u32,
+    /// last captured frame as bottom-up BGRA rows, ready to be written as RGB32
+    pixels: Vec<u8>,
+}
+
+fn pack_u32(high: u32, low: u32) -> u64 {
+    ((high as u64) << 32) | low as u64
+}
+
+impl Recorder {
+    fn create_writer(
+        path: &Path,
+        width: u32,
+        height: u32,
+        fps: u32,
+    ) -> Result<(IMFSinkWriter, u32)> {
+        unsafe {
+            let writer = MFCreateSinkWriterFromURL(&HSTRING::from(path.as_os_str()), None, None)?;
+
+            let output_type = MFCreateMediaType()?;
+            output_type.SetGUID(&MF_MT_MAJOR_TYPE, &MFMediaType_Video)?;
+            output_type.SetGUID(&MF_MT_SUBTYPE, &MFVideoFormat_H264)?;
+            // ~0.1 bits per pixel per frame is enough for desktop content
+            output_type.SetUINT32(&MF_MT_AVG_BITRATE, (width * height * fps) / 10)?;
+            output_type.SetUINT32(&MF_MT_INTERLACE_MODE, MFVideoInterlace_Progressive.0 as u32)?;
+            output_type.SetUINT64(&MF_MT_FRAME_SIZE, pack_u32(width, height))?;
+            output_type.SetUINT64(&MF_MT_FRAME_RATE, pack_u32(fps, 1))?;
+            output_type.SetUINT64(&MF_MT_PIXEL_ASPECT_RATIO, pack_u32(1, 1))?;
+            let stream_index = writer.AddStream(&output_type)?;
+
+            let input_type = MFCreateMediaType()?;
+            input_type.SetGUID(&MF_MT_MAJOR_TYPE, &MFMediaType_Video)?;
+            input_type.SetGUID(&MF_MT_SUBTYPE, &MFVideoFormat_RGB32)?;
+            input_type.SetUINT32(&MF_MT_INTERLACE_MODE, MFVideoInterlace_Progressive.0 as u32)?;
+            input_type.SetUINT64(&MF_MT_FRAME_SIZE, pack_u32(width, height))?;
+            input_type.SetUINT64(&MF_MT_FRAME_RATE, pack_u32(fps, 1))?;
+            input_type.SetUINT64(&MF_MT_PIXEL_ASPECT_RATIO, pack_u32(1, 1))?;
+            writer.SetInputMediaType(stream_index, &input_type, None)?;
+
+            writer.BeginWriting()?;
+            Ok((writer, stream_index))
+        }
+    }
+
+    fn new(device_id: &str, path: &Path, fps: u32) -> Result<Self> {
+        let capture = MonitorCapture::new(device_id)?;
+        // H264 encoder requires even dimensions
+        let width = capture.width() & !1;
+        let height = capture.height() & !1;
+        let (writer, stream_index) = Self::create_writer(path, width, height, fps)?;
+
+        Ok(Self {
+            device_id: device_id.to_owned(),
+            path: path.to_path_buf(),
+            fps,
+            width,
+            height,
+            capture,
+            writer,
+            stream_index,
+            pixels: Vec::new(),
+        })
+    }
+
+    /// crops the frame to the encoder size and flips it, RGB32 media type expects bottom-up rows
+    fn store_frame(&mut self, frame: CapturedFrame) {
+        let src_row_len = frame.width as usize * 4;
+        let row_len = self.width as usize * 4;
+        let mut pixels = vec![0u8; row_len * self.height as usize];
+        for y in 0..self.height as usize {
+            let dst_y = self.height as usize - 1 - y;
+            pixels[dst_y * row_len..(dst_y + 1) * row_len]
+                .copy_from_slice(&frame.pixels[y * src_row_len..y * src_row_len + row_len]);
+        }
+        self.pixels = pixels;
+    }
+
+    /// time unit is 100 nanoseconds
+    fn write_frame(&self, index: u64) -> Result<()> {
+        let frame_duration = 10_000_000 / self.fps as i64;
+        let len = self.pixels.len() as u32;
+        unsafe {
+            let buffer = MFCreateMemoryBuffer(len)?;
+            let mut data = std::ptr::null_mut();
+            buffer.Lock(&mut data, None, None)?;
+            std::ptr::copy_nonoverlapping(self.pixels.as_ptr(), data, self.pixels.len());
+            buffer.Unlock()?;
+            buffer.SetCurrentLength(len)?;
+
+            let sample = MFCreateSample()?;
+            sample.AddBuffer(&buffer)?;
+            sample.SetSampleTime(index as i64 * frame_duration)?;
+            sample.SetSampleDuration(frame_duration)?;
+            self.writer.WriteSample(self.stream_index, &sample)?;
+        }
+        Ok(())
+    }
+
+    fn emit_progress(&self, started_at: Instant, frames: u64) {
+        let progress = RecordingProgress {
+            device_id: self.device_id.clone(),
+            path: self.path.clone(),
+            duration_ms: started_at.elapsed().as_millis() as u64,
+            frames,
+        };
+        log_error!(get_app_handle().emit("screen-recording-progress", progress));
+    }
+
+    /// frames are written at a constant rate, repeating the last one if the screen didn't change
+    fn run(&mut self, stop_signal: &AtomicBool) -> Result<()> {
+        let frame_interval = Duration::from_secs_f64(1.0 / self.fps as f64);
+        let started_at = Instant::now();
+        let mut last_progress = Instant::now();
+        let mut ticks = 0;
+        let mut frames = 0;
+
+        while !stop_signal.load(Ordering::Acquire) {
+            ticks += 1;
+            if let Some(frame) = self.capture.latest_frame()? {
+                self.store_frame(frame);
+            }
+            if !self.pixels.is_empty() {
+                self.write_frame(frames)?;
+                frames += 1;
+            }
+
+            if last_progress.elapsed() >= Duration::from_secs(1) {
+                last_progress = Instant::now();
+                self.emit_progress(started_at, frames);
+            }
+
+            let next_frame_at = started_at + frame_interval * ticks;
+            if let Some(wait) = next_frame_at.checked_duration_since(Instant::now()) {
+                std::thread::sleep(wait);
+            }
+        }
+
+        self.emit_progress(started_at, frames);
+        Ok(())
+    }
+
+    /// needed to leave a playable mp4 file
+    fn finalize(self) -> Result<()> {
+        self.capture.close()?;
+        unsafe { self.writer.Finalize()? };
+        Ok(())
+    }
+}
+
+pub struct ScreenRecorder;
+impl ScreenRecorder {
+    pub fn start(device_id: String, path: PathBuf, fps: u32) -> Result<()> {
+        let mut active = trace_lock!(ACTIVE_RECORDING);
+        if active.as_ref().is_some_and(|r| !r.thread.is_finished()) {
+            return Err("There is already a recording in progress".into());
+        }
+
+        if fps == 0 || fps > 120 {
+            return Err("fps should be between 1 and 120".into());
+        }
+
+        let stop_signal = Arc::new(AtomicBool::new(false));
+        let (init_tx, init_rx) = mpsc::channel::<Result<()>>();
+
+        let thread_stop_signal = stop_signal.clone();
+        let thread_path = path.clone();
+        let thread = spawn_named_thread("Screen Recorder", move || -> Result<()> {
+            unsafe { MFStartup(MF_VERSION, MFSTARTUP_FULL)? };
+            let result = match Recorder::new(&device_id, &thread_path, fps) {
+                Ok(mut recorder) => {
+                    let _ = init_tx.send(Ok(()));
+                    let result = recorder.run(&thread_stop_signal);
+                    // always try to finalize even if the loop failed
+                    recorder.finalize().and(result)
+                }
+                Err(err) => {
+                    let _ = init_tx.send(Err(format!("{:?}", err).into()));
+                    Err(err)
+                }
+            };
+            unsafe { MFShutdown()? };
+            result
+        })?;
+
+        init_rx
+            .recv()
+            .map_err(|_| "Screen recorder thread exited unexpectedly")??;
+        *active = Some(ActiveRecording {
+            path,
+            stop_signal,
+            thread,
+        });
+        Ok(())
+    }
+
+    /// returns the path of the finished recording, if there was one
+    pub fn stop() -> Result<Option<PathBuf>> {
+        let active = trace_lock!(ACTIVE_RECORDING).take();
+        match active {
+            Some(recording) => {
+                recording.stop_signal.store(true, Ordering::Release);
+                recording
+                    .thread
+                    .join()
+                    .map_err(|_| "Screen recorder thread panicked")??;
+                Ok(Some(recording.path))
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingProgress {
+    pub device_id: String,
+    pub path: PathBuf,
+    pub duration_ms: u64,
+    pub frames: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PickedColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub hex: String,
+}
+
+impl PickedColor {
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            r,
+            g,
+            b,
+            hex: format!("#{:02x}{:02x}{:02x}", r, g, b),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+use seelen_core::rect::Rect;
+
+use crate::error_handler::Result;
+
+use super::{
+    application::{color_picker::ColorPicker, ocr::ScreenOcr, ScreenRecorder},
+    domain::PickedColor,
+};
+
+#[tauri::command(async)]
+pub fn start_recording(device_id: String, out_path: PathBuf, fps: u32) -> Result<()> {
+    ScreenRecorder::start(device_id, out_path, fps)
+}
+
+#[tauri::command(async)]
+pub fn stop_recording() -> Result<Option<PathBuf>> {
+    ScreenRecorder::stop()
+}
+
+#[tauri::command(async)]
+pub fn ocr_region(monitor_id: String, rect: Rect, language: Option<String>) -> Result<String> {
+    ScreenOcr::recognize_region(&monitor_id, &rect, language)
+}
+
+#[tauri::command(async)]
+pub fn pick_color_at(x: i32, y: i32) -> Result<PickedColor> {
+    ColorPicker::pick_at(x, y)
+}
+
+#[tauri::command(async)]
+pub fn pick_color_at_cursor() -> Result<PickedColor> {
+    ColorPicker::pick_at_cursor()
+}
@@ -3,7 +3,10 @@ use std::sync::atomic::Ordering;
 use clap::Command;
 
 use crate::{
-    error_handler::Result, get_subcommands, hook::LOG_WIN_EVENTS, utils::TRACE_LOCK_ENABLED,
+    error_handler::Result,
+    get_subcommands,
+    hook::{LOG_WIN_EVENTS, WIN_EVENT_COALESCE_OVERRIDE},
+    utils::TRACE_LOCK_ENABLED,
 };
 
 get_subcommands![
@@ -11,6 +14,8 @@ get_subcommands![
     ToggleWinEvents,
     /** Toggles the tracing of mutex lock */
     ToggleTraceLock,
+    /** Overrides the interval used to coalesce repeated window events until restart (0 disables it) */
+    SetWinEventCoalescing(ms: u64 => "Interval in milliseconds."),
 ];
 
 pub struct CliDebugger;
@@ -36,6 +41,9 @@ impl CliDebugger {
                     Ordering::Release,
                 );
             }
+            SubCommand::SetWinEventCoalescing(ms) => {
+                WIN_EVENT_COALESCE_OVERRIDE.store(ms, Ordering::Release);
+            }
         };
         Ok(())
     }
@@ -0,0 +1,86 @@
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use windows::Win32::{
+    Foundation::HWND,
+    UI::WindowsAndMessaging::{FindWindowA, FindWindowExA, SW_HIDE, SW_SHOW},
+};
+
+use crate::{
+    error_handler::Result,
+    pcstr, trace_lock,
+    windows_api::{WindowEnumerator, WindowsApi},
+};
+
+lazy_static! {
+    /// visibility of the icons before the first change done by us
+    static ref ORIGINAL_VISIBILITY: Mutex<Option<bool>> = Mutex::new(None);
+}
+
+pub struct DesktopIcons;
+impl DesktopIcons {
+    /// The icons list lives under `Progman > SHELLDLL_DefView`, but when the desktop
+    /// wallpaper is animated (slideshow, wallpaper engines or our wall on some builds)
+    /// the view is moved to a top level `WorkerW`.
+    fn list_view() -> Result<HWND> {
+        unsafe {
+            let progman = FindWindowA(pcstr!("Progman"), None)?;
+            let mut def_view =
+                FindWindowExA(progman, HWND::default(), pcstr!("SHELLDLL_DefView"), None).ok();
+
+            if def_view.is_none() {
+                WindowEnumerator::new().for_each(|current| {
+                    if def_view.is_none() {
+                        def_view = FindWindowExA(
+                            current,
+                            HWND::default(),
+                            pcstr!("SHELLDLL_DefView"),
+                            None,
+                        )
+                        .ok();
+                    }
+                })?;
+            }
+
+            let def_view = def_view.ok_or("Failed to find the desktop view")?;
+            Ok(FindWindowExA(
+                def_view,
+                HWND::default(),
+                pcstr!("SysListView32"),
+                None,
+            )?)
+        }
+    }
+
+    pub fn is_visible() -> Result<bool> {
+        Ok(WindowsApi::is_window_visible(Self::list_view()?))
+    }
+
+    pub fn set_visible(visible: bool) -> Result<()> {
+        let list_view = Self::list_view()?;
+        let current = WindowsApi::is_window_visible(list_view);
+        if current == visible {
+            return Ok(());
+        }
+        trace_lock!(ORIGINAL_VISIBILITY).get_or_insert(current);
+        WindowsApi::show_window(list_view, if visible { SW_SHOW } else { SW_HIDE })
+    }
+
+    /// Called on exit so the user doesn't end with the icons hidden
+    pub fn restore() -> Result<()> {
+        if let Some(original) = trace_lock!(ORIGINAL_VISIBILITY).take() {
+            let list_view = Self::list_view()?;
+            WindowsApi::show_window(list_view, if original { SW_SHOW } else { SW_HIDE })?;
+        }
+        Ok(())
+    }
+
+    /// Lock-free variant of `restore` for the panic hook
+    pub fn restore_on_panic() {
+        let Some(mut original) = ORIGINAL_VISIBILITY.try_lock() else {
+            return;
+        };
+        if let (Some(original), Ok(list_view)) = (original.take(), Self::list_view()) {
+            let _ = WindowsApi::show_window(list_view, if original { SW_SHOW } else { SW_HIDE });
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use crate::error_handler::Result;
+
+use super::application::DesktopIcons;
+
+#[tauri::command(async)]
+pub fn get_desktop_icons_visible() -> Result<bool> {
+    DesktopIcons::is_visible()
+}
+
+#[tauri::command(async)]
+pub fn set_desktop_icons_visible(visible: bool) -> Result<()> {
+    DesktopIcons::set_visible(visible)
+}
@@ -0,0 +1,175 @@
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use lazy_static::lazy_static;
+use tauri::Emitter;
+use windows::{
+    core::{GUID, PCWSTR},
+    Win32::{
+        Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+        UI::WindowsAndMessaging::{
+            CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+            RegisterDeviceNotificationW, TranslateMessage, DBT_DEVICEARRIVAL,
+            DBT_DEVICEREMOVECOMPLETE, DBT_DEVTYP_DEVICEINTERFACE,
+            DEVICE_NOTIFY_ALL_INTERFACE_CLASSES, DEVICE_NOTIFY_WINDOW_HANDLE,
+            DEV_BROADCAST_DEVICEINTERFACE_W, DEV_BROADCAST_HDR, MSG, WINDOW_EX_STYLE, WINDOW_STYLE,
+            WM_DEVICECHANGE, WM_FONTCHANGE, WNDCLASSW,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result, log_error, modules::fonts::application::Fonts, seelen::get_app_handle,
+    utils::spawn_named_thread, windows_api::WindowsApi,
+};
+
+use super::domain::{DeviceAction, DeviceChange, DeviceKind};
+
+/// a hub or dock emits a burst of notifications when connected
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+lazy_static! {
+    static ref CHANNEL: (Sender<DeviceChange>, Receiver<DeviceChange>) =
+        crossbeam_channel::unbounded();
+}
+
+static LISTENING: AtomicBool = AtomicBool::new(false);
+
+pub struct DeviceWatcher;
+impl DeviceWatcher {
+    unsafe fn read_change(wparam: WPARAM, lparam: LPARAM) -> Option<DeviceChange> {
+        let action = match wparam.0 as u32 {
+            DBT_DEVICEARRIVAL => DeviceAction::Arrival,
+            DBT_DEVICEREMOVECOMPLETE => DeviceAction::Removal,
+            _ => return None,
+        };
+
+        let header = (lparam.0 as *const DEV_BROADCAST_HDR).as_ref()?;
+        if header.dbch_devicetype != DBT_DEVTYP_DEVICEINTERFACE.0 {
+            return None;
+        }
+
+        let interface = &*(lparam.0 as *const DEV_BROADCAST_DEVICEINTERFACE_W);
+        // dbcc_name is a null terminated string of variable length
+        let device_id = PCWSTR(interface.dbcc_name.as_ptr()).to_string().ok()?;
+        Some(DeviceChange {
+            action,
+            device_id,
+            kind: DeviceKind::from_interface_class(&interface.dbcc_classguid),
+        })
+    }
+
+    unsafe extern "system" fn window_proc(
+        window: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        if message == WM_DEVICECHANGE {
+            if let Some(change) = Self::read_change(wparam, lparam) {
+                log_error!(CHANNEL.0.send(change));
+            }
+            return LRESULT(1);
+        }
+        // broadcasted to top-level windows when a font is installed or removed
+        if message == WM_FONTCHANGE {
+            Fonts::on_fonts_changed();
+            return LRESULT(0);
+        }
+        DefWindowProcW(window, message, wparam, lparam)
+    }
+
+    unsafe fn create_background_window() -> Result<()> {
+        let wide_name: Vec<u16> = "Seelen Device Watcher"
+            .encode_utf16()
+            .chain(Some(0))
+            .collect();
+        let wide_class: Vec<u16> = "SeelenDeviceWatcher"
+            .encode_utf16()
+            .chain(Some(0))
+            .collect();
+
+        let h_module = WindowsApi::module_handle_w()?;
+
+        let wnd_class = WNDCLASSW {
+            lpfnWndProc: Some(Self::window_proc),
+            hInstance: h_module.into(),
+            lpszClassName: PCWSTR(wide_class.as_ptr()),
+            ..Default::default()
+        };
+
+        RegisterClassW(&wnd_class);
+
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            PCWSTR(wide_class.as_ptr()),
+            PCWSTR(wide_name.as_ptr()),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            h_module,
+            None,
+        )?;
+
+        // the class guid is ignored when listening all interface classes
+        let mut notification_filter = DEV_BROADCAST_DEVICEINTERFACE_W {
+            dbcc_size: std::mem::size_of::<DEV_BROADCAST_DEVICEINTERFACE_W>() as u32,
+            dbcc_devicetype: DBT_DEVTYP_DEVICEINTERFACE.0,
+            dbcc_reserved: 0,
+            dbcc_classguid: GUID::zeroed(),
+            dbcc_name: [0; 1],
+        };
+
+        RegisterDeviceNotificationW(
+            hwnd,
+            &mut notification_filter as *mut _ as *mut _,
+            DEVICE_NOTIFY_WINDOW_HANDLE | DEVICE_NOTIFY_ALL_INTERFACE_CLASSES,
+        )?;
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, hwnd, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        Ok(())
+    }
+
+    /// waits until the burst ends and emits each distinct change once
+    fn dispatch_changes() {
+        let receiver = &CHANNEL.1;
+        while let Ok(first) = receiver.recv() {
+            let mut changes = vec![first];
+            while let Ok(change) = receiver.recv_timeout(DEBOUNCE) {
+                changes.push(change);
+            }
+
+            let mut seen = HashSet::new();
+            for change in changes {
+                if seen.insert(change.clone()) {
+                    log_error!(get_app_handle().emit("device-changed", &change));
+                }
+            }
+        }
+    }
+
+    /// Emits `device-changed` on device arrival/removal, the payload includes the kind
+    /// so each widget can react only to the devices it cares about.
+    pub fn start() -> Result<()> {
+        if LISTENING.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        spawn_named_thread("Device Watcher", || unsafe {
+            log_error!(Self::create_background_window());
+        })?;
+        spawn_named_thread("Device Events Dispatcher", Self::dispatch_changes)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use serde::Serialize;
+use windows::core::GUID;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum DeviceAction {
+    Arrival,
+    Removal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum DeviceKind {
+    Usb,
+    Hid,
+    Keyboard,
+    Mouse,
+    Monitor,
+    Disk,
+    Volume,
+    Audio,
+    Network,
+    Other,
+}
+
+impl DeviceKind {
+    /// https://learn.microsoft.com/en-us/windows-hardware/drivers/install/system-defined-device-interface-classes
+    pub fn from_interface_class(class: &GUID) -> Self {
+        const CLASSES: [(u128, DeviceKind); 9] = [
+            (0xA5DCBF10_6530_11D2_901F_00C04FB951ED, DeviceKind::Usb),
+            (0x4D1E55B2_F16F_11CF_88CB_001111000030, DeviceKind::Hid),
+            (0x884B96C3_56EF_11D1_BC8C_00A0C91405DD, DeviceKind::Keyboard),
+            (0x378DE44C_56EF_11D1_BC8C_00A0C913BE3E, DeviceKind::Mouse),
+            (0xE6F07B5F_EE97_4A90_B076_33F57BF4EAA7, DeviceKind::Monitor),
+            (0x53F56307_B6BF_11D0_94F2_00A0C91EFB8B, DeviceKind::Disk),
+            (0x53F5630D_B6BF_11D0_94F2_00A0C91EFB8B, DeviceKind::Volume),
+            (0x6994AD04_93EF_11D0_A3CC_00A0C9223196, DeviceKind::Audio),
+            (0xCAC88484_7515_4C03_82E6_71A87ABAC361, DeviceKind::Network),
+        ];
+        let class = class.to_u128();
+        CLASSES
+            .iter()
+            .find(|(guid, _)| *guid == class)
+            .map(|(_, kind)| *kind)
+            .unwrap_or(DeviceKind::Other)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceChange {
+    pub action: DeviceAction,
+    /// device interface path
+    pub device_id: String,
+    pub kind: DeviceKind,
+}
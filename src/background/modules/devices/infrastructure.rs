@@ -0,0 +1,7 @@
+use crate::log_error;
+
+use super::application::DeviceWatcher;
+
+pub fn register_device_events() {
+    log_error!(DeviceWatcher::start());
+}
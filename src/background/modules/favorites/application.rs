@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+
+use tauri::Emitter;
+
+use crate::{
+    error_handler::Result, seelen::get_app_handle,
+    seelen_weg::icon_extractor::extract_and_save_icon_from_file, utils::constants::SEELEN_COMMON,
+};
+
+use super::domain::Favorite;
+
+pub struct Favorites;
+impl Favorites {
+    fn read() -> Result<Vec<Favorite>> {
+        let path = SEELEN_COMMON.favorites_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    fn write(favorites: &[Favorite]) -> Result<()> {
+        std::fs::write(
+            SEELEN_COMMON.favorites_path(),
+            serde_json::to_string_pretty(favorites)?,
+        )?;
+        get_app_handle().emit(
+            "favorites-changed",
+            Self::with_availability(favorites.to_vec()),
+        )?;
+        Ok(())
+    }
+
+    fn with_availability(favorites: Vec<Favorite>) -> Vec<Favorite> {
+        favorites
+            .into_iter()
+            .map(|favorite| Favorite {
+                available: favorite.path.exists(),
+                ..favorite
+            })
+            .collect()
+    }
+
+    /// paths are case insensitive on Windows
+    fn is_same_path(a: &Path, b: &Path) -> bool {
+        a.to_string_lossy().to_lowercase() == b.to_string_lossy().to_lowercase()
+    }
+
+    fn display_name(path: &Path) -> String {
+        let is_shortcut = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk") || ext.eq_ignore_ascii_case("url"));
+        let name = if is_shortcut {
+            path.file_stem()
+        } else {
+            path.file_name()
+        };
+        // drive roots have no file name
+        name.map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string())
+    }
+
+    /// Entries whose target is missing are kept and flagged as unavailable
+    pub fn list() -> Result<Vec<Favorite>> {
+        Ok(Self::with_availability(Self::read()?))
+    }
+
+    pub fn add(path: String) -> Result<Favorite> {
+        let path = PathBuf::from(path);
+        if !path.exists() {
+            return Err(format!("{} does not exist", path.display()).into());
+        }
+
+        let mut favorites = Self::read()?;
+        if let Some(existing) = favorites
+            .iter()
+            .find(|f| Self::is_same_path(&f.path, &path))
+        {
+            return Ok(Favorite {
+                available: true,
+                ..existing.clone()
+            });
+        }
+
+        let icon = if path.is_dir() {
+            None
+        } else {
+            extract_and_save_icon_from_file(&path)
+                .inspect_err(|err| log::warn!("Failed to extract favorite icon: {err}"))
+                .ok()
+        };
+        let favorite = Favorite {
+            name: Self::display_name(&path),
+            path,
+            icon,
+            available: true,
+        };
+        favorites.push(favorite.clone());
+        Self::write(&favorites)?;
+        Ok(favorite)
+    }
+
+    pub fn remove(path: String) -> Result<()> {
+        let path = PathBuf::from(path);
+        let mut favorites = Self::read()?;
+        let len = favorites.len();
+        favorites.retain(|f| !Self::is_same_path(&f.path, &path));
+        if favorites.len() != len {
+            Self::write(&favorites)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Favorite {
+    pub path: PathBuf,
+    pub name: String,
+    /// extracted icon, None for folders or if extraction failed
+    pub icon: Option<PathBuf>,
+    /// false when the target was moved or deleted after being added, computed on read
+    #[serde(default, skip_deserializing)]
+    pub available: bool,
+}
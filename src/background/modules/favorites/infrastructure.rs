@@ -0,0 +1,18 @@
+use crate::error_handler::Result;
+
+use super::{application::Favorites, domain::Favorite};
+
+#[tauri::command(async)]
+pub fn favorites_list() -> Result<Vec<Favorite>> {
+    Favorites::list()
+}
+
+#[tauri::command(async)]
+pub fn favorites_add(path: String) -> Result<Favorite> {
+    Favorites::add(path)
+}
+
+#[tauri::command(async)]
+pub fn favorites_remove(path: String) -> Result<()> {
+    Favorites::remove(path)
+}
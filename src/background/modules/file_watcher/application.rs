@@ -0,0 +1,235 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{ReadDirectoryChangesWatcher, RecursiveMode, Watcher},
+    DebounceEventResult, Debouncer, FileIdMap,
+};
+use parking_lot::Mutex;
+use tauri::Emitter;
+
+use crate::{error_handler::Result, log_error, seelen::get_app_handle, trace_lock};
+
+use super::domain::{PathChange, PathChangeKind, PathWatcherEvent, WatcherId};
+
+/// changes deeper than this (relative to the watched path) are not received
+pub const MAX_WATCH_DEPTH: usize = 8;
+/// directories a recursive watcher can hold, a bigger tree should be watched by parts
+const MAX_WATCHED_DIRS: usize = 512;
+/// watchers alive at the same time by webview
+const MAX_WATCHERS_BY_WEBVIEW: usize = 16;
+
+lazy_static! {
+    pub static ref PATH_WATCHER: Mutex<PathWatcher> = Mutex::new(PathWatcher::default());
+}
+
+static NEXT_WATCHER_ID: AtomicU32 = AtomicU32::new(1);
+
+struct OwnedWatcher {
+    /// label of the webview that created the watcher
+    owner: String,
+    recursive: bool,
+    debouncer: Debouncer<ReadDirectoryChangesWatcher, FileIdMap>,
+}
+
+#[derive(Default)]
+pub struct PathWatcher {
+    watchers: HashMap<WatcherId, OwnedWatcher>,
+}
+
+unsafe impl Send for PathWatcher {}
+
+impl PathWatcher {
+    fn depth(root: &Path, path: &Path) -> usize {
+        path.strip_prefix(root)
+            .map(|relative| relative.components().count())
+            .unwrap_or(0)
+    }
+
+    /// `dir` and its subdirectories up to `levels` below it, symlinks and junctions are
+    /// not followed to avoid cycles
+    fn collect_dirs(dir: &Path, levels: usize) -> Result<Vec<PathBuf>> {
+        let mut dirs = vec![dir.to_path_buf()];
+        let mut level = vec![dir.to_path_buf()];
+        for _ in 0..levels {
+            let mut next = Vec::new();
+            for dir in &level {
+                let Ok(entries) = std::fs::read_dir(dir) else {
+                    continue;
+                };
+                for entry in entries.flatten() {
+                    if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                        next.push(entry.path());
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            dirs.extend(next.iter().cloned());
+            if dirs.len() > MAX_WATCHED_DIRS {
+                return Err(format!(
+                    "{} has more than {} directories, watch a narrower path",
+                    dir.display(),
+                    MAX_WATCHED_DIRS
+                )
+                .into());
+            }
+            level = next;
+        }
+        Ok(dirs)
+    }
+
+    /// Directories created or removed inside a recursive watcher are added or removed from
+    /// it, each directory is watched on its own so the depth cap is kept.
+    fn sync_dirs(id: WatcherId, root: &Path, changes: &[PathChange]) {
+        let mut guard = trace_lock!(PATH_WATCHER);
+        let watcher = match guard.watchers.get_mut(&id) {
+            Some(watcher) if watcher.recursive => watcher,
+            _ => return,
+        };
+
+        for change in changes {
+            let depth = Self::depth(root, &change.path);
+            match change.kind {
+                PathChangeKind::Created if depth < MAX_WATCH_DEPTH && change.path.is_dir() => {
+                    let dirs = match Self::collect_dirs(&change.path, MAX_WATCH_DEPTH - 1 - depth) {
+                        Ok(dirs) => dirs,
+                        Err(err) => {
+                            log::warn!("Path Watcher {}: {:?}", id, err);
+                            continue;
+                        }
+                    };
+                    for dir in dirs {
+                        log_error!(watcher
+                            .debouncer
+                            .watcher()
+                            .watch(&dir, RecursiveMode::NonRecursive));
+                    }
+                }
+                PathChangeKind::Removed => {
+                    // not watched paths fail, that's fine as files are removed too
+                    let _ = watcher.debouncer.watcher().unwatch(&change.path);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// all the events of a debounce window are sent together, deduplicated by path
+    fn on_events(id: WatcherId, root: &Path, result: DebounceEventResult) {
+        let events = match result {
+            Ok(events) => events,
+            Err(errors) => {
+                errors
+                    .iter()
+                    .for_each(|e| log::error!("Path Watcher {} Error: {:?}", id, e));
+                return;
+            }
+        };
+
+        let mut changes: Vec<PathChange> = Vec::new();
+        for event in events {
+            let kind = match PathChangeKind::from_event_kind(&event.kind) {
+                Some(kind) => kind,
+                None => continue,
+            };
+            for path in &event.paths {
+                match changes.iter_mut().find(|c| &c.path == path) {
+                    Some(change) => change.kind = kind,
+                    None => changes.push(PathChange {
+                        path: path.clone(),
+                        kind,
+                    }),
+                }
+            }
+        }
+
+        if changes.is_empty() {
+            return;
+        }
+        Self::sync_dirs(id, root, &changes);
+        log_error!(get_app_handle().emit("path-watcher-event", PathWatcherEvent { id, changes }));
+    }
+
+    pub fn watch(&mut self, owner: &str, path: PathBuf, recursive: bool) -> Result<WatcherId> {
+        if !path.exists() {
+            return Err(format!("Path {} does not exist", path.display()).into());
+        }
+        // a whole drive would flood the watcher with changes
+        if recursive && path.parent().is_none() {
+            return Err("Recursive watching of a drive root is not allowed".into());
+        }
+        let owned = self.watchers.values().filter(|w| w.owner == owner).count();
+        if owned >= MAX_WATCHERS_BY_WEBVIEW {
+            return Err(format!(
+                "A webview can not have more than {} path watchers",
+                MAX_WATCHERS_BY_WEBVIEW
+            )
+            .into());
+        }
+
+        let dirs = if recursive && path.is_dir() {
+            Self::collect_dirs(&path, MAX_WATCH_DEPTH - 1)?
+        } else {
+            vec![path.clone()]
+        };
+
+        let id = NEXT_WATCHER_ID.fetch_add(1, Ordering::Relaxed);
+        let root = path.clone();
+        let mut debouncer = new_debouncer(
+            Duration::from_millis(200),
+            None,
+            move |result: DebounceEventResult| Self::on_events(id, &root, result),
+        )?;
+        for dir in &dirs {
+            debouncer
+                .watcher()
+                .watch(dir, RecursiveMode::NonRecursive)?;
+        }
+
+        self.watchers.insert(
+            id,
+            OwnedWatcher {
+                owner: owner.to_owned(),
+                recursive,
+                debouncer,
+            },
+        );
+        Ok(id)
+    }
+
+    /// dropping the debouncer stops its threads
+    pub fn unwatch(&mut self, owner: &str, id: WatcherId) -> Result<()> {
+        match self.watchers.get(&id) {
+            Some(watcher) if watcher.owner == owner => {
+                self.watchers.remove(&id);
+                Ok(())
+            }
+            _ => Err(format!("Watcher {} not found", id).into()),
+        }
+    }
+
+    /// watchers of a webview are useless after it is reloaded or destroyed
+    pub fn release_owned_by(&mut self, owner: &str) {
+        self.watchers.retain(|_, watcher| watcher.owner != owner);
+    }
+
+    pub fn release_all(&mut self) {
+        self.watchers.clear();
+    }
+}
+
+pub fn release_path_watchers() {
+    trace_lock!(PATH_WATCHER).release_all();
+}
+
+pub fn release_webview_path_watchers(label: &str) {
+    trace_lock!(PATH_WATCHER).release_owned_by(label);
+}
@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use notify_debouncer_full::notify::EventKind;
+use serde::Serialize;
+
+pub type WatcherId = u32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PathChangeKind {
+    Created,
+    Modified,
+    Removed,
+    Other,
+}
+
+impl PathChangeKind {
+    /// returns None for access events as these are not changes
+    pub fn from_event_kind(kind: &EventKind) -> Option<Self> {
+        match kind {
+            EventKind::Access(_) => None,
+            EventKind::Create(_) => Some(Self::Created),
+            EventKind::Modify(_) => Some(Self::Modified),
+            EventKind::Remove(_) => Some(Self::Removed),
+            EventKind::Any | EventKind::Other => Some(Self::Other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PathChange {
+    pub path: PathBuf,
+    pub kind: PathChangeKind,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PathWatcherEvent {
+    pub id: WatcherId,
+    pub changes: Vec<PathChange>,
+}
@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+use tauri::WebviewWindow;
+
+use crate::{error_handler::Result, trace_lock};
+
+use super::{application::PATH_WATCHER, domain::WatcherId};
+
+/// the watcher is owned by the calling webview, it is released when the webview is reloaded
+#[tauri::command(async)]
+pub fn watch_path(
+    webview: WebviewWindow<tauri::Wry>,
+    path: PathBuf,
+    recursive: bool,
+) -> Result<WatcherId> {
+    trace_lock!(PATH_WATCHER).watch(webview.label(), path, recursive)
+}
+
+#[tauri::command(async)]
+pub fn unwatch_path(webview: WebviewWindow<tauri::Wry>, id: WatcherId) -> Result<()> {
+    trace_lock!(PATH_WATCHER).unwatch(webview.label(), id)
+}
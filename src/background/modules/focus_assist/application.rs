@@ -0,0 +1,136 @@
+use std::{
+    ffi::c_void,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use tauri::Emitter;
+use windows::{
+    core::{IUnknown, IUnknown_Vtbl, GUID, HRESULT, PCWSTR, PWSTR},
+    Win32::Foundation::NTSTATUS,
+};
+
+use crate::{error_handler::Result, log_error, seelen::get_app_handle, windows_api::Com};
+
+use super::domain::FocusAssistMode;
+
+/// WNF_SHEL_QUIETHOURS_ACTIVE_PROFILE_CHANGED, undocumented but stable since Windows 10 1803.
+/// It holds the active profile, including the ones applied by automatic rules (fullscreen, games, etc).
+const WNF_SHEL_QUIETHOURS_ACTIVE_PROFILE_CHANGED: u64 = 0x0D83063EA3BF1C75;
+
+/// QuietHoursSettings coclass, owner of the profile selected by the user on the action center
+const CLSID_QUIET_HOURS_SETTINGS: GUID = GUID::from_u128(0xf53321fa_34f8_4b7f_b9a3_361877cb94cf);
+
+/// Undocumented, only the first methods of the vtable are declared (the getter is there
+/// to keep the slot order).
+#[windows_core::interface("6bff4732-81ec-4ffb-ae67-b6c1bc29631f")]
+unsafe trait IQuietHoursSettings: IUnknown {
+    fn _get_user_selected_profile(&self, profile_id: *mut PWSTR) -> HRESULT;
+    fn put_user_selected_profile(&self, profile_id: PCWSTR) -> HRESULT;
+}
+
+type WnfUserCallback = unsafe extern "system" fn(
+    state_name: u64,
+    change_stamp: u32,
+    type_id: *const c_void,
+    context: *const c_void,
+    buffer: *const c_void,
+    length: u32,
+) -> NTSTATUS;
+
+#[link(name = "ntdll")]
+extern "system" {
+    fn NtQueryWnfStateData(
+        state_name: *const u64,
+        type_id: *const c_void,
+        explicit_scope: *const c_void,
+        change_stamp: *mut u32,
+        buffer: *mut c_void,
+        buffer_size: *mut u32,
+    ) -> NTSTATUS;
+
+    fn RtlSubscribeWnfStateChangeNotification(
+        subscription: *mut *mut c_void,
+        state_name: u64,
+        change_stamp: u32,
+        callback: WnfUserCallback,
+        context: *const c_void,
+        type_id: *const c_void,
+        serialization_group: u32,
+        unknown: u32,
+    ) -> NTSTATUS;
+}
+
+static WATCHING: AtomicBool = AtomicBool::new(false);
+
+pub struct FocusAssist;
+impl FocusAssist {
+    pub fn get() -> Result<FocusAssistMode> {
+        let mut raw = 0u32;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        let mut change_stamp = 0u32;
+        unsafe {
+            NtQueryWnfStateData(
+                &WNF_SHEL_QUIETHOURS_ACTIVE_PROFILE_CHANGED,
+                std::ptr::null(),
+                std::ptr::null(),
+                &mut change_stamp,
+                &mut raw as *mut u32 as *mut c_void,
+                &mut size,
+            )
+            .ok()?;
+        }
+        Ok(FocusAssistMode::from_raw(raw))
+    }
+
+    /// Changes the profile selected by the user, the same setting changed by the action center.
+    /// The active profile (WNF state) is updated by the system after it.
+    pub fn set(mode: FocusAssistMode) -> Result<()> {
+        Com::run_with_context(|| {
+            let settings: IQuietHoursSettings = Com::create_instance(&CLSID_QUIET_HOURS_SETTINGS)?;
+            let profile_id: Vec<u16> = mode.profile_id().encode_utf16().chain(Some(0)).collect();
+            unsafe { settings.put_user_selected_profile(PCWSTR(profile_id.as_ptr())) }.ok()?;
+            Ok(())
+        })
+    }
+
+    unsafe extern "system" fn on_state_changed(
+        _state_name: u64,
+        _change_stamp: u32,
+        _type_id: *const c_void,
+        _context: *const c_void,
+        buffer: *const c_void,
+        length: u32,
+    ) -> NTSTATUS {
+        if !buffer.is_null() && length as usize >= std::mem::size_of::<u32>() {
+            let mode = FocusAssistMode::from_raw(*(buffer as *const u32));
+            log_error!(get_app_handle().emit("focus-assist-changed", mode));
+        }
+        NTSTATUS(0)
+    }
+
+    /// Emits `focus-assist-changed` on every mode change, including the ones done by automatic rules.
+    pub fn watch() -> Result<()> {
+        if WATCHING.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        // the subscription lives until the process exits
+        let mut subscription = std::ptr::null_mut();
+        let status = unsafe {
+            RtlSubscribeWnfStateChangeNotification(
+                &mut subscription,
+                WNF_SHEL_QUIETHOURS_ACTIVE_PROFILE_CHANGED,
+                0,
+                Self::on_state_changed,
+                std::ptr::null(),
+                std::ptr::null(),
+                0,
+                0,
+            )
+        };
+        if let Err(err) = status.ok() {
+            WATCHING.store(false, Ordering::Release);
+            return Err(err.into());
+        }
+        Ok(())
+    }
+}
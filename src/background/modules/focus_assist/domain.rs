@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FocusAssistMode {
+    Off,
+    PriorityOnly,
+    AlarmsOnly,
+}
+
+impl FocusAssistMode {
+    pub fn from_raw(raw: u32) -> Self {
+        match raw {
+            1 => FocusAssistMode::PriorityOnly,
+            2 => FocusAssistMode::AlarmsOnly,
+            _ => FocusAssistMode::Off,
+        }
+    }
+
+    /// id of the quiet hours profile of the mode
+    pub fn profile_id(self) -> &'static str {
+        match self {
+            FocusAssistMode::Off => "Microsoft.QuietHoursProfile.Unrestricted",
+            FocusAssistMode::PriorityOnly => "Microsoft.QuietHoursProfile.PriorityOnly",
+            FocusAssistMode::AlarmsOnly => "Microsoft.QuietHoursProfile.AlarmsOnly",
+        }
+    }
+}
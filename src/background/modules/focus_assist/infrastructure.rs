@@ -0,0 +1,22 @@
+use tauri::Emitter;
+
+use crate::{error_handler::Result, log_error, seelen::get_app_handle};
+
+use super::{application::FocusAssist, domain::FocusAssistMode};
+
+pub fn register_focus_assist_events() {
+    log_error!(FocusAssist::watch());
+    if let Ok(mode) = FocusAssist::get() {
+        log_error!(get_app_handle().emit("focus-assist-changed", mode));
+    }
+}
+
+#[tauri::command(async)]
+pub fn get_focus_assist() -> Result<FocusAssistMode> {
+    FocusAssist::get()
+}
+
+#[tauri::command(async)]
+pub fn set_focus_assist(mode: FocusAssistMode) -> Result<()> {
+    FocusAssist::set(mode)
+}
@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use tauri::Emitter;
+use windows::Win32::{
+    Foundation::LPARAM,
+    Graphics::Gdi::{
+        EnumFontFamiliesExW, GetDC, ReleaseDC, DEFAULT_CHARSET, FIXED_PITCH, LOGFONTW, TEXTMETRICW,
+    },
+};
+
+use crate::{error_handler::Result, log_error, seelen::get_app_handle, trace_lock};
+
+use super::domain::FontFamily;
+
+lazy_static! {
+    /// enumeration is slow so it is only done once, until the installed fonts change
+    static ref CACHE: Mutex<Option<Vec<FontFamily>>> = Mutex::new(None);
+}
+
+pub struct Fonts;
+impl Fonts {
+    unsafe extern "system" fn enum_proc(
+        logfont: *const LOGFONTW,
+        _metric: *const TEXTMETRICW,
+        _font_type: u32,
+        lparam: LPARAM,
+    ) -> i32 {
+        let families = &mut *(lparam.0 as *mut BTreeMap<String, bool>);
+        if let Some(logfont) = logfont.as_ref() {
+            let name = String::from_utf16_lossy(&logfont.lfFaceName)
+                .trim_end_matches('\0')
+                .to_string();
+            // fonts starting with @ are the vertical variants of the same family
+            if !name.is_empty() && !name.starts_with('@') {
+                let monospace = logfont.lfPitchAndFamily & 0b11 == FIXED_PITCH.0 as u8;
+                families.entry(name).or_insert(monospace);
+            }
+        }
+        1
+    }
+
+    fn enumerate() -> Vec<FontFamily> {
+        // sorted and deduplicated by name, the same family is reported once per charset
+        let mut families: BTreeMap<String, bool> = BTreeMap::new();
+        let logfont = LOGFONTW {
+            lfCharSet: DEFAULT_CHARSET,
+            ..Default::default()
+        };
+        unsafe {
+            let hdc = GetDC(None);
+            EnumFontFamiliesExW(
+                hdc,
+                &logfont,
+                Some(Self::enum_proc),
+                LPARAM(&mut families as *mut _ as isize),
+                0,
+            );
+            ReleaseDC(None, hdc);
+        }
+        families
+            .into_iter()
+            .map(|(name, monospace)| FontFamily { name, monospace })
+            .collect()
+    }
+
+    pub fn installed() -> Result<Vec<FontFamily>> {
+        let mut cache = trace_lock!(CACHE);
+        Ok(cache.get_or_insert_with(Self::enumerate).clone())
+    }
+
+    /// should be called on WM_FONTCHANGE
+    pub fn on_fonts_changed() {
+        trace_lock!(CACHE).take();
+        log_error!(get_app_handle().emit("fonts-changed", ()));
+    }
+}
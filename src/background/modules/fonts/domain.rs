@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FontFamily {
+    pub name: String,
+    /// based on the pitch reported by GDI
+    pub monospace: bool,
+}
@@ -0,0 +1,8 @@
+use crate::error_handler::Result;
+
+use super::{application::Fonts, domain::FontFamily};
+
+#[tauri::command(async)]
+pub fn get_installed_fonts() -> Result<Vec<FontFamily>> {
+    Fonts::installed()
+}
@@ -1 +1,212 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 
+use windows::Win32::{
+    Foundation::{HINSTANCE, HMODULE, HWND, LPARAM, LRESULT, WPARAM},
+    UI::{
+        Input::KeyboardAndMouse::{
+            GetKeyState, INPUT, INPUT_0, INPUT_MOUSE, MOUSEEVENTF_ABSOLUTE, MOUSEEVENTF_MOVE,
+            MOUSEEVENTF_VIRTUALDESK, MOUSEEVENTF_WHEEL, MOUSEINPUT, MOUSE_EVENT_FLAGS, VIRTUAL_KEY,
+        },
+        WindowsAndMessaging::{
+            CallNextHookEx, DispatchMessageW, GetMessageW, GetSystemMetrics, SetWindowsHookExW,
+            TranslateMessage, HHOOK, KBDLLHOOKSTRUCT, MSG, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN,
+            SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, WH_KEYBOARD_LL, WM_KEYUP, WM_SYSKEYUP,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    event_manager, log_error,
+    utils::spawn_named_thread,
+    windows_api::{window::Window, WindowsApi},
+};
+
+use super::{
+    domain::{LockKey, LockKeysState, MouseButton, Point, WindowInfo},
+    send_input, Keyboard, Mouse,
+};
+
+#[derive(Debug, Clone)]
+pub enum KeyboardEvent {
+    LockKeyToggled(LockKey),
+}
+
+pub struct KeyboardHook;
+
+event_manager!(KeyboardHook, KeyboardEvent);
+
+static HOOK_STARTED: AtomicBool = AtomicBool::new(false);
+
+impl KeyboardHook {
+    /// low level hooks should return as fast as possible so we only dispatch the event here
+    unsafe extern "system" fn hook_proc(code: i32, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
+        let message = w_param.0 as u32;
+        if code >= 0 && (message == WM_KEYUP || message == WM_SYSKEYUP) {
+            let data = &*(l_param.0 as *const KBDLLHOOKSTRUCT);
+            // the toggle state is already updated on key up
+            if let Some(key) = LockKey::from_virtual_key(VIRTUAL_KEY(data.vkCode as u16)) {
+                log_error!(Self::event_tx().send(KeyboardEvent::LockKeyToggled(key)));
+            }
+        }
+        CallNextHookEx(HHOOK::default(), code, w_param, l_param)
+    }
+
+    pub fn start() -> Result<()> {
+        if HOOK_STARTED.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        log::trace!("Registering keyboard hook");
+        let h_module = WindowsApi::module_handle_w()?;
+        let addr = h_module.0 as isize;
+        spawn_named_thread("Keyboard Hook", move || unsafe {
+            let h_module = windows::Win32::Foundation::HMODULE(addr as _);
+            if let Err(err) = SetWindowsHookExW(WH_KEYBOARD_LL, Some(Self::hook_proc), h_module, 0)
+            {
+                log::error!("Failed to register keyboard hook: {:?}", err);
+                HOOK_STARTED.store(false, Ordering::Release);
+                return;
+            }
+            let mut msg = MSG::default();
+            while GetMessageW(&mut msg, HWND::default(), 0, 0).into() {
+                let _ = TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        })?;
+        Ok(())
+    }
+}
+
+pub struct LockKeys;
+impl LockKeys {
+    pub fn is_on(key: LockKey) -> bool {
+        // the low-order bit is the toggle state
+        unsafe { GetKeyState(key.virtual_key().0 as i32) & 1 == 1 }
+    }
+
+    pub fn state() -> LockKeysState {
+        LockKeysState {
+            caps_lock: Self::is_on(LockKey::CapsLock),
+            num_lock: Self::is_on(LockKey::NumLock),
+            scroll_lock: Self::is_on(LockKey::ScrollLock),
+        }
+    }
+
+    pub fn set(key: LockKey, on: bool) -> Result<()> {
+        if Self::is_on(key) == on {
+            return Ok(());
+        }
+        Keyboard::new().send_keys(key.as_keys())
+    }
+}
+
+/// Synthetic mouse input through `SendInput`.
+///
+/// Injected input is subject to UIPI, so events targeting windows of a process
+/// with a higher integrity level (e.g. elevated apps) are silently dropped by Windows.
+pub struct SyntheticMouse;
+impl SyntheticMouse {
+    fn mouse_input(dx: i32, dy: i32, data: i32, flags: MOUSE_EVENT_FLAGS) -> INPUT {
+        INPUT {
+            r#type: INPUT_MOUSE,
+            Anonymous: INPUT_0 {
+                mi: MOUSEINPUT {
+                    dx,
+                    dy,
+                    mouseData: data as u32,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        }
+    }
+
+    /// `x` and `y` are physical pixels on the virtual screen, so they can be negative
+    /// for monitors placed at the left/top of the primary one.
+    pub fn move_to(x: i32, y: i32) -> Result<()> {
+        WindowsApi::with_physical_coordinates(|| {
+            let (left, top, width, height) = unsafe {
+                (
+                    GetSystemMetrics(SM_XVIRTUALSCREEN),
+                    GetSystemMetrics(SM_YVIRTUALSCREEN),
+                    GetSystemMetrics(SM_CXVIRTUALSCREEN),
+                    GetSystemMetrics(SM_CYVIRTUALSCREEN),
+                )
+            };
+            if width <= 1 || height <= 1 {
+                return Err("Failed to get the virtual screen size".into());
+            }
+
+            let x = x.clamp(left, left + width - 1);
+            let y = y.clamp(top, top + height - 1);
+            // absolute coordinates are normalized to 0..=65535 over the whole virtual desktop
+            let dx = ((x - left) as i64 * 65535 / (width - 1) as i64) as i32;
+            let dy = ((y - top) as i64 * 65535 / (height - 1) as i64) as i32;
+
+            send_input(&[Self::mouse_input(
+                dx,
+                dy,
+                0,
+                MOUSEEVENTF_MOVE | MOUSEEVENTF_ABSOLUTE | MOUSEEVENTF_VIRTUALDESK,
+            )])
+        })
+    }
+
+    /// Clicks at the current cursor position
+    pub fn click(button: MouseButton, double: bool) -> Result<()> {
+        let (down, up) = button.event_flags();
+        let mut inputs = vec![
+            Self::mouse_input(0, 0, 0, down),
+            Self::mouse_input(0, 0, 0, up),
+        ];
+        if double {
+            // sent on the same batch to stay inside the system double click time
+            inputs.extend_from_within(..);
+        }
+        send_input(&inputs)
+    }
+
+    /// `delta` is in wheel units, 120 is one notch. Positive values scroll up.
+    pub fn scroll(delta: i32) -> Result<()> {
+        if delta == 0 {
+            return Ok(());
+        }
+        send_input(&[Self::mouse_input(0, 0, delta, MOUSEEVENTF_WHEEL)])
+    }
+}
+
+pub struct Cursor;
+impl Cursor {
+    /// physical pixels on the virtual screen
+    pub fn position() -> Result<Point> {
+        WindowsApi::with_physical_coordinates(Mouse::get_cursor_pos)
+    }
+
+    /// shell surfaces are not considered user windows
+    fn is_shell_surface(window: &Window) -> bool {
+        window.is_desktop()
+            || window.is_seelen_overlay()
+            || matches!(
+                window.class().as_str(),
+                "Shell_TrayWnd" | "Shell_SecondaryTrayWnd"
+            )
+    }
+
+    /// Top-level window under the point, `None` for the desktop, taskbars and Seelen overlays
+    pub fn window_from_point(x: i32, y: i32) -> Result<Option<WindowInfo>> {
+        let hwnd = WindowsApi::with_physical_coordinates(|| {
+            Ok(WindowsApi::window_from_point(&Point::new(x, y)))
+        })?;
+        if hwnd.is_invalid() {
+            return Ok(None);
+        }
+
+        let window = Window::from(WindowsApi::get_root_ancestor(hwnd));
+        if !window.is_window() || Self::is_shell_surface(&window) {
+            return Ok(None);
+        }
+
+        Ok(Some(WindowInfo::from(&window)))
+    }
+}
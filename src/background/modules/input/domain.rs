@@ -1,10 +1,22 @@
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::path::PathBuf;
 
-use windows::Win32::Foundation::POINT;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use windows::Win32::{
+    Foundation::POINT,
+    UI::Input::KeyboardAndMouse::{
+        MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP, MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_MIDDLEUP,
+        MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSE_EVENT_FLAGS, VIRTUAL_KEY, VK_CAPITAL,
+        VK_NUMLOCK, VK_SCROLL,
+    },
+};
 
 use seelen_core::rect::Rect;
 
+use crate::{error_handler::AppError, windows_api::window::Window};
+
 /// A Point type stores the x and y position.
 #[derive(Clone, Copy, PartialEq, Eq, Default)]
 pub struct Point(POINT);
@@ -71,3 +83,287 @@ impl AsMut<POINT> for Point {
         &mut self.0
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LockKey {
+    CapsLock,
+    NumLock,
+    ScrollLock,
+}
+
+impl LockKey {
+    pub fn virtual_key(&self) -> VIRTUAL_KEY {
+        match self {
+            LockKey::CapsLock => VK_CAPITAL,
+            LockKey::NumLock => VK_NUMLOCK,
+            LockKey::ScrollLock => VK_SCROLL,
+        }
+    }
+
+    pub fn from_virtual_key(key: VIRTUAL_KEY) -> Option<Self> {
+        match key {
+            VK_CAPITAL => Some(LockKey::CapsLock),
+            VK_NUMLOCK => Some(LockKey::NumLock),
+            VK_SCROLL => Some(LockKey::ScrollLock),
+            _ => None,
+        }
+    }
+
+    /// token to be used on `Keyboard::send_keys`
+    pub fn as_keys(&self) -> &'static str {
+        match self {
+            LockKey::CapsLock => "{CAPSLOCK}",
+            LockKey::NumLock => "{NUMLOCK}",
+            LockKey::ScrollLock => "{SCROLLLOCK}",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+impl MouseButton {
+    /// (down, up) flags for `SendInput`
+    pub fn event_flags(&self) -> (MOUSE_EVENT_FLAGS, MOUSE_EVENT_FLAGS) {
+        match self {
+            MouseButton::Left => (MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP),
+            MouseButton::Right => (MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP),
+            MouseButton::Middle => (MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_MIDDLEUP),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockKeysState {
+    pub caps_lock: bool,
+    pub num_lock: bool,
+    pub scroll_lock: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowInfo {
+    pub hwnd: isize,
+    pub title: String,
+    pub class: String,
+    pub exe: Option<PathBuf>,
+    pub umid: Option<String>,
+    pub minimized: bool,
+    /// input from a non elevated process to an elevated window is blocked by UIPI,
+    /// None if the elevation can't be read
+    pub elevated: Option<bool>,
+}
+
+/// Every field that is set must match (AND semantics), unset fields match any window.
+/// - `title` is a regex searched in the window title.
+/// - `class` and `umid` are compared exactly, ignoring case.
+/// - `exe` is compared ignoring case against the executable file name, or against
+///   the full path if it contains a path separator.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WindowFilter {
+    pub title: Option<String>,
+    pub class: Option<String>,
+    pub exe: Option<String>,
+    pub umid: Option<String>,
+    /// skip popups, tool windows, shell surfaces and cloaked windows
+    pub only_manageable: bool,
+}
+
+/// [`WindowFilter`] with the title pattern compiled, built with `try_from` so an invalid
+/// pattern fails once instead of on every window.
+#[derive(Debug, Clone)]
+pub struct CompiledWindowFilter {
+    pub title: Option<Regex>,
+    pub class: Option<String>,
+    pub exe: Option<String>,
+    pub umid: Option<String>,
+    pub only_manageable: bool,
+}
+
+impl TryFrom<WindowFilter> for CompiledWindowFilter {
+    type Error = AppError;
+
+    fn try_from(filter: WindowFilter) -> Result<Self, Self::Error> {
+        Ok(Self {
+            title: filter.title.as_deref().map(Regex::new).transpose()?,
+            class: filter.class,
+            exe: filter.exe,
+            umid: filter.umid,
+            only_manageable: filter.only_manageable,
+        })
+    }
+}
+
+impl CompiledWindowFilter {
+    pub fn matches(&self, window: &WindowInfo) -> bool {
+        if let Some(title) = &self.title {
+            if !title.is_match(&window.title) {
+                return false;
+            }
+        }
+
+        if let Some(class) = &self.class {
+            if !class.eq_ignore_ascii_case(&window.class) {
+                return false;
+            }
+        }
+
+        if let Some(exe) = &self.exe {
+            let Some(path) = &window.exe else {
+                return false;
+            };
+            let candidate = if exe.contains(['\\', '/']) {
+                Some(path.as_os_str())
+            } else {
+                path.file_name()
+            };
+            let matched = candidate
+                .map(|c| c.to_string_lossy().eq_ignore_ascii_case(exe))
+                .unwrap_or(false);
+            if !matched {
+                return false;
+            }
+        }
+
+        if let Some(umid) = &self.umid {
+            match &window.umid {
+                Some(window_umid) if window_umid.eq_ignore_ascii_case(umid) => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+impl From<&Window> for WindowInfo {
+    fn from(window: &Window) -> Self {
+        // UWP apps are hosted by ApplicationFrameHost, the real app is the frame creator
+        let app = match window.get_frame_creator() {
+            Ok(Some(creator)) => creator,
+            _ => *window,
+        };
+        WindowInfo {
+            hwnd: window.address(),
+            title: window.title(),
+            class: window.class(),
+            exe: app.exe().ok(),
+            umid: window
+                .app_user_model_id()
+                .or_else(|| app.process().package_app_user_model_id().ok()),
+            minimized: window.is_minimized(),
+            elevated: window.process().is_elevated(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window() -> WindowInfo {
+        WindowInfo {
+            hwnd: 1,
+            title: "README.md - Visual Studio Code".to_string(),
+            class: "Chrome_WidgetWin_1".to_string(),
+            exe: Some(PathBuf::from(
+                "C:\\Program Files\\Microsoft VS Code\\Code.exe",
+            )),
+            umid: Some("Microsoft.VisualStudioCode".to_string()),
+            minimized: false,
+            elevated: Some(false),
+        }
+    }
+
+    fn compile(filter: WindowFilter) -> CompiledWindowFilter {
+        CompiledWindowFilter::try_from(filter).unwrap()
+    }
+
+    #[test]
+    fn empty_filter_matches_any_window() {
+        assert!(compile(WindowFilter::default()).matches(&window()));
+    }
+
+    #[test]
+    fn invalid_title_pattern_fails_to_compile() {
+        let filter = WindowFilter {
+            title: Some("(unclosed".to_string()),
+            ..Default::default()
+        };
+        assert!(CompiledWindowFilter::try_from(filter).is_err());
+    }
+
+    #[test]
+    fn title_is_searched_as_regex() {
+        let matching = WindowFilter {
+            title: Some(r"\.md - Visual Studio Code$".to_string()),
+            ..Default::default()
+        };
+        let other = WindowFilter {
+            title: Some("^Notepad".to_string()),
+            ..Default::default()
+        };
+        assert!(compile(matching).matches(&window()));
+        assert!(!compile(other).matches(&window()));
+    }
+
+    #[test]
+    fn exe_matches_file_name_or_full_path_ignoring_case() {
+        let by_name = WindowFilter {
+            exe: Some("code.EXE".to_string()),
+            ..Default::default()
+        };
+        let by_path = WindowFilter {
+            exe: Some("c:\\program files\\microsoft vs code\\code.exe".to_string()),
+            ..Default::default()
+        };
+        let by_partial_path = WindowFilter {
+            exe: Some("Microsoft VS Code\\Code.exe".to_string()),
+            ..Default::default()
+        };
+        assert!(compile(by_name).matches(&window()));
+        assert!(compile(by_path).matches(&window()));
+        assert!(!compile(by_partial_path).matches(&window()));
+    }
+
+    #[test]
+    fn all_set_fields_should_match() {
+        let filter = WindowFilter {
+            class: Some("chrome_widgetwin_1".to_string()),
+            umid: Some("microsoft.visualstudiocode".to_string()),
+            ..Default::default()
+        };
+        assert!(compile(filter.clone()).matches(&window()));
+
+        let filter = WindowFilter {
+            exe: Some("notepad.exe".to_string()),
+            ..filter
+        };
+        assert!(!compile(filter).matches(&window()));
+    }
+
+    #[test]
+    fn windows_without_exe_or_umid_do_not_match_those_fields() {
+        let window = WindowInfo {
+            exe: None,
+            umid: None,
+            ..window()
+        };
+        let by_exe = WindowFilter {
+            exe: Some("Code.exe".to_string()),
+            ..Default::default()
+        };
+        let by_umid = WindowFilter {
+            umid: Some("Microsoft.VisualStudioCode".to_string()),
+            ..Default::default()
+        };
+        assert!(!compile(by_exe).matches(&window));
+        assert!(!compile(by_umid).matches(&window));
+    }
+}
@@ -1 +1,59 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 
+use tauri::Emitter;
+
+use crate::{error_handler::Result, log_error, seelen::get_app_handle};
+
+use super::{
+    application::{Cursor, KeyboardEvent, KeyboardHook, LockKeys, SyntheticMouse},
+    domain::{LockKey, LockKeysState, MouseButton, WindowInfo},
+};
+
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+pub fn register_keyboard_events() {
+    if !REGISTERED.swap(true, Ordering::AcqRel) {
+        KeyboardHook::subscribe(|event| match event {
+            KeyboardEvent::LockKeyToggled(_) => {
+                log_error!(get_app_handle().emit("lock-keys-state-changed", LockKeys::state()));
+            }
+        });
+        log_error!(KeyboardHook::start());
+    }
+    log_error!(get_app_handle().emit("lock-keys-state-changed", LockKeys::state()));
+}
+
+#[tauri::command(async)]
+pub fn get_lock_keys_state() -> LockKeysState {
+    LockKeys::state()
+}
+
+#[tauri::command(async)]
+pub fn set_lock_key(key: LockKey, on: bool) -> Result<()> {
+    LockKeys::set(key, on)
+}
+
+#[tauri::command(async)]
+pub fn mouse_move(x: i32, y: i32) -> Result<()> {
+    SyntheticMouse::move_to(x, y)
+}
+
+#[tauri::command(async)]
+pub fn mouse_click(button: MouseButton, double: bool) -> Result<()> {
+    SyntheticMouse::click(button, double)
+}
+
+#[tauri::command(async)]
+pub fn mouse_scroll(delta: i32) -> Result<()> {
+    SyntheticMouse::scroll(delta)
+}
+
+#[tauri::command(async)]
+pub fn get_cursor_pos() -> Result<(i32, i32)> {
+    let pos = Cursor::position()?;
+    Ok((pos.get_x(), pos.get_y()))
+}
+
+#[tauri::command(async)]
+pub fn window_from_point(x: i32, y: i32) -> Result<Option<WindowInfo>> {
+    Cursor::window_from_point(x, y)
+}
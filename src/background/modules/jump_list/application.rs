@@ -0,0 +1,422 @@
+use std::{
+    os::windows::ffi::OsStrExt,
+    path::{Path, PathBuf},
+};
+
+use tauri::Manager;
+use widestring::U16CStr;
+use windows::{
+    core::{Interface, BSTR, PCWSTR, PROPVARIANT},
+    Win32::{
+        Storage::{EnhancedStorage::PKEY_Title, FileSystem::WIN32_FIND_DATAW},
+        System::Com::{IPersistStream, STREAM_SEEK_CUR},
+        UI::Shell::{
+            ApplicationDocumentLists,
+            Common::{IObjectArray, IObjectCollection},
+            DestinationList, EnumerableObjectCollection, IApplicationDocumentLists,
+            ICustomDestinationList, IShellItem, IShellLinkW,
+            PropertiesSystem::IPropertyStore,
+            SHCreateMemStream, ShellLink, ADLT_FREQUENT, ADLT_RECENT, APPDOCLISTTYPE,
+            KNOWNDESTCATEGORY, SIGDN_FILESYSPATH, SIGDN_NORMALDISPLAY,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    seelen::get_app_handle,
+    utils::quote_arg,
+    windows_api::{Com, WindowsApi},
+};
+
+use super::domain::{JumpList, JumpListEntry};
+
+/// CLSID of ShellLink, each entry of a .customDestinations-ms file starts with it
+const SHELL_LINK_CLSID: [u8; 16] = [
+    0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+/// written after each category of a .customDestinations-ms file
+const CATEGORY_FOOTER: u32 = 0xBABFFBAB;
+
+/// category of the custom list of an app, as stored on its .customDestinations-ms file
+enum CustomCategory {
+    Custom(String, Vec<IShellLinkW>),
+    /// KDC_FREQUENT or KDC_RECENT
+    Known(i32),
+    Tasks(Vec<IShellLinkW>),
+}
+
+struct CustomDestinationsReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl CustomDestinationsReader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8]> {
+        let bytes = self
+            .bytes
+            .get(self.offset..self.offset + len)
+            .ok_or("Unexpected end of jump list file")?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self, chars: usize) -> Result<String> {
+        let units: Vec<u16> = self
+            .take(chars * 2)?
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect();
+        Ok(String::from_utf16(&units)?)
+    }
+
+    fn links(&mut self, count: u32) -> Result<Vec<IShellLinkW>> {
+        let mut links = Vec::new();
+        for _ in 0..count {
+            if self.take(SHELL_LINK_CLSID.len())? != SHELL_LINK_CLSID {
+                return Err("Unexpected jump list entry".into());
+            }
+            let stream = unsafe { SHCreateMemStream(Some(&self.bytes[self.offset..])) }
+                .ok_or("Failed to create stream")?;
+            let link: IShellLinkW = Com::create_instance(&ShellLink)?;
+            let mut read = 0u64;
+            unsafe {
+                link.cast::<IPersistStream>()?.Load(&stream)?;
+                stream.Seek(0, STREAM_SEEK_CUR, Some(&mut read))?;
+            }
+            self.take(read as usize)?;
+            links.push(link);
+        }
+        Ok(links)
+    }
+
+    /// header: version, categories count and a reserved value
+    fn categories(&mut self) -> Result<Vec<CustomCategory>> {
+        let _version = self.u32()?;
+        let count = self.u32()?;
+        let _reserved = self.u32()?;
+
+        let mut categories = Vec::new();
+        for _ in 0..count {
+            let category = match self.u32()? {
+                0 => {
+                    let name_len = self.u16()? as usize;
+                    let name = self.string(name_len)?;
+                    let entries = self.u32()?;
+                    CustomCategory::Custom(name, self.links(entries)?)
+                }
+                1 => CustomCategory::Known(self.u32()? as i32),
+                2 => {
+                    let entries = self.u32()?;
+                    CustomCategory::Tasks(self.links(entries)?)
+                }
+                kind => return Err(format!("Unknown jump list category: {kind}").into()),
+            };
+            if self.u32()? != CATEGORY_FOOTER {
+                return Err("Invalid jump list category footer".into());
+            }
+            categories.push(category);
+        }
+        Ok(categories)
+    }
+}
+
+pub struct AppJumpList;
+impl AppJumpList {
+    pub fn read(umid: &str) -> Result<JumpList> {
+        Com::run_with_context(|| {
+            Ok(JumpList {
+                recent: Self::read_automatic_destinations(umid, ADLT_RECENT)?,
+                frequent: Self::read_automatic_destinations(umid, ADLT_FREQUENT)?,
+                tasks: Self::read_custom_destinations(umid)?,
+            })
+        })
+    }
+
+    /// AutomaticDestinations are managed by the shell so they can be read using the public API
+    fn read_automatic_destinations(
+        umid: &str,
+        list_type: APPDOCLISTTYPE,
+    ) -> Result<Vec<JumpListEntry>> {
+        let lists: IApplicationDocumentLists = Com::create_instance(&ApplicationDocumentLists)?;
+        let umid: Vec<u16> = umid.encode_utf16().chain(Some(0)).collect();
+        unsafe { lists.SetAppID(PCWSTR(umid.as_ptr()))? };
+
+        // fails if the app has no jump list
+        let Ok(array) = (unsafe { lists.GetList::<IObjectArray>(list_type, 0) }) else {
+            return Ok(Vec::new());
+        };
+
+        let mut entries = Vec::new();
+        for index in 0..unsafe { array.GetCount()? } {
+            let entry = if let Ok(item) = unsafe { array.GetAt::<IShellItem>(index) } {
+                Self::entry_from_shell_item(&item)
+            } else if let Ok(link) = unsafe { array.GetAt::<IShellLinkW>(index) } {
+                Self::entry_from_shell_link(&link)
+            } else {
+                continue;
+            };
+            // a broken entry should not hide the rest of the list
+            match entry {
+                Ok(entry) => entries.push(entry),
+                Err(err) => log::warn!("Skipping jump list entry: {}", err.message()),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// The task is added to the "Tasks" category. The shell only allows replacing the whole
+    /// custom list, so the current categories and tasks of the app are written back as they are.
+    pub fn add_task(umid: &str, title: &str, program: &str, args: &[String]) -> Result<()> {
+        if !Path::new(program).exists() {
+            return Err(format!("Program not found: {program}").into());
+        }
+
+        Com::run_with_context(|| {
+            // if the current list can't be read we don't write, that would wipe it
+            let mut categories = Self::read_custom_categories(umid)?;
+            let new_task = Self::shell_link_from_entry(&JumpListEntry {
+                title: title.to_owned(),
+                path: Some(PathBuf::from(program)),
+                args: args
+                    .iter()
+                    .map(|arg| quote_arg(arg))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            })?;
+
+            match categories.iter_mut().find_map(|category| match category {
+                CustomCategory::Tasks(tasks) => Some(tasks),
+                _ => None,
+            }) {
+                Some(tasks) => {
+                    for task in tasks.iter() {
+                        if Self::entry_from_shell_link(task)?.title == title {
+                            return Err(format!("A task named {title} already exists").into());
+                        }
+                    }
+                    tasks.push(new_task);
+                }
+                None => categories.push(CustomCategory::Tasks(vec![new_task])),
+            }
+
+            let list: ICustomDestinationList = Com::create_instance(&DestinationList)?;
+            let umid: Vec<u16> = umid.encode_utf16().chain(Some(0)).collect();
+            unsafe {
+                list.SetAppID(PCWSTR(umid.as_ptr()))?;
+                let mut min_slots = 0;
+                let _removed: IObjectArray = list.BeginList(&mut min_slots)?;
+                for category in &categories {
+                    match category {
+                        CustomCategory::Custom(name, links) => {
+                            let name: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+                            // fails if the user removed one of its items from the jump list
+                            if let Err(err) = list
+                                .AppendCategory(PCWSTR(name.as_ptr()), &Self::object_array(links)?)
+                            {
+                                log::warn!("Failed to keep jump list category: {:?}", err);
+                            }
+                        }
+                        CustomCategory::Known(id) => {
+                            list.AppendKnownCategory(KNOWNDESTCATEGORY(*id))?;
+                        }
+                        CustomCategory::Tasks(links) => {
+                            list.AddUserTasks(&Self::object_array(links)?)?;
+                        }
+                    }
+                }
+                list.CommitList()?;
+            }
+            Ok(())
+        })
+    }
+
+    fn object_array(links: &[IShellLinkW]) -> Result<IObjectArray> {
+        let collection: IObjectCollection = Com::create_instance(&EnumerableObjectCollection)?;
+        for link in links {
+            unsafe { collection.AddObject(link)? };
+        }
+        Ok(collection.cast()?)
+    }
+
+    fn shell_link_from_entry(entry: &JumpListEntry) -> Result<IShellLinkW> {
+        let link: IShellLinkW = Com::create_instance(&ShellLink)?;
+        let path: Vec<u16> = entry
+            .path
+            .as_deref()
+            .ok_or("Jump list entry without path")?
+            .as_os_str()
+            .encode_wide()
+            .chain(Some(0))
+            .collect();
+        let args: Vec<u16> = entry.args.encode_utf16().chain(Some(0)).collect();
+        unsafe {
+            link.SetPath(PCWSTR(path.as_ptr()))?;
+            link.SetArguments(PCWSTR(args.as_ptr()))?;
+            let store: IPropertyStore = link.cast()?;
+            store.SetValue(&PKEY_Title, &PROPVARIANT::from(entry.title.as_str()))?;
+            store.Commit()?;
+        }
+        Ok(link)
+    }
+
+    fn custom_destinations_path(umid: &str) -> Result<PathBuf> {
+        Ok(get_app_handle()
+            .path()
+            .data_dir()?
+            .join("Microsoft\\Windows\\Recent\\CustomDestinations")
+            .join(format!(
+                "{:016x}.customDestinations-ms",
+                Self::app_id_hash(umid)
+            )))
+    }
+
+    /// CustomDestinations can't be read using the public API, so we parse the file.
+    fn read_custom_categories(umid: &str) -> Result<Vec<CustomCategory>> {
+        let path = Self::custom_destinations_path(umid)?;
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let bytes = std::fs::read(path)?;
+        CustomDestinationsReader {
+            bytes: &bytes,
+            offset: 0,
+        }
+        .categories()
+    }
+
+    /// Entries of the custom categories and tasks, if the file can't be parsed the serialized
+    /// shell links on it are searched instead.
+    fn read_custom_destinations(umid: &str) -> Result<Vec<JumpListEntry>> {
+        match Self::read_custom_categories(umid) {
+            Ok(categories) => {
+                let mut entries = Vec::new();
+                for category in categories {
+                    if let CustomCategory::Custom(_, links) | CustomCategory::Tasks(links) =
+                        category
+                    {
+                        for link in links {
+                            match Self::entry_from_shell_link(&link) {
+                                Ok(entry) => entries.push(entry),
+                                Err(err) => {
+                                    log::warn!("Skipping jump list entry: {}", err.message())
+                                }
+                            }
+                        }
+                    }
+                }
+                return Ok(entries);
+            }
+            Err(err) => log::warn!("Failed to parse jump list: {}", err.message()),
+        }
+
+        let path = Self::custom_destinations_path(umid)?;
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let bytes = std::fs::read(path)?;
+        let mut entries = Vec::new();
+        let mut offset = 0;
+        while let Some(position) = bytes[offset..]
+            .windows(SHELL_LINK_CLSID.len())
+            .position(|window| window == SHELL_LINK_CLSID)
+        {
+            offset += position + SHELL_LINK_CLSID.len();
+            if let Ok(entry) = Self::entry_from_serialized_link(&bytes[offset..]) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    fn entry_from_serialized_link(bytes: &[u8]) -> Result<JumpListEntry> {
+        let stream = unsafe { SHCreateMemStream(Some(bytes)) }.ok_or("Failed to create stream")?;
+        let link: IShellLinkW = Com::create_instance(&ShellLink)?;
+        unsafe { link.cast::<IPersistStream>()?.Load(&stream)? };
+        Self::entry_from_shell_link(&link)
+    }
+
+    fn entry_from_shell_item(item: &IShellItem) -> Result<JumpListEntry> {
+        unsafe {
+            Ok(JumpListEntry {
+                title: item.GetDisplayName(SIGDN_NORMALDISPLAY)?.to_string()?,
+                path: item
+                    .GetDisplayName(SIGDN_FILESYSPATH)
+                    .ok()
+                    .and_then(|path| path.to_string().ok())
+                    .map(PathBuf::from),
+                args: String::new(),
+            })
+        }
+    }
+
+    fn entry_from_shell_link(link: &IShellLinkW) -> Result<JumpListEntry> {
+        let mut path = [0u16; 1024];
+        let mut find_data = WIN32_FIND_DATAW::default();
+        unsafe { link.GetPath(&mut path, &mut find_data, 0)? };
+
+        let mut args = [0u16; 1024];
+        unsafe { link.GetArguments(&mut args)? };
+
+        let path = Some(Self::wide_to_string(&path))
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from);
+        // jump list links store their label as the title property instead of the description
+        let title = unsafe { link.cast::<IPropertyStore>()?.GetValue(&PKEY_Title) }
+            .ok()
+            .and_then(|value| BSTR::try_from(&value).ok())
+            .map(|title| title.to_string())
+            .filter(|title| !title.is_empty())
+            .map(|title| WindowsApi::resolve_indirect_string(&title).unwrap_or(title))
+            .unwrap_or_else(|| path.as_deref().map(Self::file_stem).unwrap_or_default());
+
+        Ok(JumpListEntry {
+            title,
+            path,
+            args: Self::wide_to_string(&args),
+        })
+    }
+
+    fn wide_to_string(buffer: &[u16]) -> String {
+        U16CStr::from_slice_truncate(buffer)
+            .map(|s| s.to_string_lossy())
+            .unwrap_or_default()
+    }
+
+    fn file_stem(path: &Path) -> String {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Name used by the shell for the jump list files of an app, undocumented:
+    /// CRC-64 of the upper case app id encoded as UTF-16LE.
+    fn app_id_hash(umid: &str) -> u64 {
+        const POLY: u64 = 0x92C64265D32139A7;
+        let mut crc = u64::MAX;
+        for byte in umid
+            .to_uppercase()
+            .encode_utf16()
+            .flat_map(|unit| unit.to_le_bytes())
+        {
+            crc ^= byte as u64;
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ POLY
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+        crc
+    }
+}
@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JumpListEntry {
+    pub title: String,
+    /// can be opened with `open_file` or executed with `run` using `args`, none for
+    /// entries without a filesystem path (ex: virtual folders or shell locations)
+    pub path: Option<PathBuf>,
+    pub args: String,
+}
+
+/// categories are empty if the app has no jump list
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JumpList {
+    pub recent: Vec<JumpListEntry>,
+    pub frequent: Vec<JumpListEntry>,
+    /// tasks and custom categories defined by the app itself
+    pub tasks: Vec<JumpListEntry>,
+}
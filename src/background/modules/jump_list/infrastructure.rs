@@ -0,0 +1,18 @@
+use crate::error_handler::Result;
+
+use super::{application::AppJumpList, domain::JumpList};
+
+#[tauri::command(async)]
+pub fn get_jump_list(umid: String) -> Result<JumpList> {
+    AppJumpList::read(&umid)
+}
+
+#[tauri::command(async)]
+pub fn add_jump_list_task(
+    umid: String,
+    title: String,
+    program: String,
+    args: Vec<String>,
+) -> Result<()> {
+    AppJumpList::add_task(&umid, &title, &program, &args)
+}
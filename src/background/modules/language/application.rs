@@ -1,11 +1,39 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use windows::{
+    core::PCWSTR,
+    Win32::{
+        Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+        UI::{
+            Input::KeyboardAndMouse::HKL,
+            WindowsAndMessaging::{
+                CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+                RegisterShellHookWindow, RegisterWindowMessageW, TranslateMessage, HSHELL_LANGUAGE,
+                HSHELL_RUDEAPPACTIVATED, HSHELL_WINDOWACTIVATED, MSG, WINDOW_EX_STYLE,
+                WINDOW_STYLE, WM_INPUTLANGCHANGE, WNDCLASSW,
+            },
+        },
+    },
+};
 use winreg::{
     enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE},
     RegKey,
 };
 
-use crate::{error_handler::Result, event_manager, windows_api::WindowsApi};
+use crate::{
+    error_handler::Result, event_manager, log_error, trace_lock, utils::spawn_named_thread,
+    windows_api::WindowsApi,
+};
+
+use super::domain::{ActiveKeyboardLayout, KeyboardLayout, Language};
+
+lazy_static! {
+    static ref LAST_LAYOUT_ID: Mutex<String> = Mutex::new(String::new());
+}
 
-use super::domain::{KeyboardLayout, Language};
+static SHELL_HOOK_MESSAGE: AtomicU32 = AtomicU32::new(0);
 
 pub struct LanguageManager {}
 
@@ -13,7 +41,7 @@ pub struct LanguageManager {}
 #[allow(dead_code)]
 pub enum LanguageEvent {
     LanguageChanged,
-    KeyboardLayoutChanged,
+    KeyboardLayoutChanged(String),
 }
 
 event_manager!(LanguageManager, LanguageEvent);
@@ -70,4 +98,159 @@ impl LanguageManager {
 
         Ok(languages)
     }
+
+    /// https://learn.microsoft.com/en-us/windows/win32/intl/language-identifiers
+    fn klid_from_hkl(hkl: HKL) -> Result<String> {
+        let device_id = ((hkl.0 as usize >> 16) & 0xFFFF) as u16;
+        // high nibble `F` means that the layout is not the default one for the language,
+        // in that case the layout is found by the `Layout Id` value on the registry
+        if device_id & 0xF000 != 0xF000 {
+            return Ok(format!("{:08X}", device_id));
+        }
+
+        let layout_id = device_id & 0x0FFF;
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let reg_layouts = hklm.open_subkey(r"SYSTEM\CurrentControlSet\Control\Keyboard Layouts")?;
+        for klid in reg_layouts.enum_keys().flatten() {
+            let layout = reg_layouts.open_subkey(&klid)?;
+            if let Ok(id) = layout.get_value::<String, _>("Layout Id") {
+                if u16::from_str_radix(&id, 16).is_ok_and(|id| id == layout_id) {
+                    return Ok(klid.to_uppercase());
+                }
+            }
+        }
+        Err(format!("Keyboard layout not found for {:?}", hkl).into())
+    }
+
+    /// keyboard layout id (KLID) used by the foreground window
+    fn current_layout_id() -> Result<String> {
+        let (_, thread_id) =
+            WindowsApi::window_thread_process_id(WindowsApi::get_foreground_window());
+        Self::klid_from_hkl(WindowsApi::get_keyboard_layout(thread_id))
+    }
+
+    pub fn get_current() -> Result<ActiveKeyboardLayout> {
+        let id = Self::current_layout_id()?;
+        for language in Self::enum_langs()? {
+            if let Some(layout) = language
+                .input_methods
+                .iter()
+                .find(|layout| layout.id.eq_ignore_ascii_case(&id))
+            {
+                return Ok(ActiveKeyboardLayout {
+                    language_code: language.code.clone(),
+                    layout: layout.clone(),
+                });
+            }
+        }
+        Err(format!("Active keyboard layout {} is not installed", id).into())
+    }
+
+    /// `tag` is the BCP-47 language tag of an installed language, ex: "en-US".
+    /// If `layout_id` is not specified the first keyboard layout of the language is used.
+    pub fn set_layout(tag: &str, layout_id: Option<&str>) -> Result<()> {
+        let languages = Self::enum_langs()?;
+        let language = languages
+            .iter()
+            .find(|language| language.code.eq_ignore_ascii_case(tag))
+            .ok_or_else(|| format!("Language {} is not installed", tag))?;
+
+        let layout = match layout_id {
+            Some(id) => language
+                .input_methods
+                .iter()
+                .find(|layout| layout.id.eq_ignore_ascii_case(id)),
+            None => language.input_methods.first(),
+        }
+        .ok_or_else(|| format!("Keyboard layout not installed for {}", tag))?;
+
+        WindowsApi::set_keyboard_layout(WindowsApi::load_keyboard_layout(&layout.id)?)
+    }
+
+    fn notify_if_layout_changed() {
+        let Ok(current) = Self::current_layout_id() else {
+            return;
+        };
+        let mut last = trace_lock!(LAST_LAYOUT_ID);
+        if *last != current {
+            *last = current.clone();
+            log_error!(Self::event_tx().send(LanguageEvent::KeyboardLayoutChanged(current)));
+        }
+    }
+
+    unsafe extern "system" fn window_proc(
+        window: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        // WM_INPUTLANGCHANGE is only received when our process changes the layout, changes made
+        // on other apps (ex: by the OS shortcut) are notified by the shell hook, also the
+        // effective layout is per thread so it can change when the foreground window changes.
+        let is_shell_hook = message != 0 && message == SHELL_HOOK_MESSAGE.load(Ordering::Acquire);
+        if message == WM_INPUTLANGCHANGE
+            || (is_shell_hook
+                && matches!(
+                    wparam.0 as u32,
+                    HSHELL_LANGUAGE | HSHELL_WINDOWACTIVATED | HSHELL_RUDEAPPACTIVATED
+                ))
+        {
+            Self::notify_if_layout_changed();
+            return LRESULT(0);
+        }
+        DefWindowProcW(window, message, wparam, lparam)
+    }
+
+    unsafe fn create_background_window() -> Result<()> {
+        let wide_class: Vec<u16> = "SeelenLanguageManager"
+            .encode_utf16()
+            .chain(Some(0))
+            .collect();
+        let h_module = WindowsApi::module_handle_w()?;
+
+        let wnd_class = WNDCLASSW {
+            lpfnWndProc: Some(Self::window_proc),
+            hInstance: h_module.into(),
+            lpszClassName: PCWSTR(wide_class.as_ptr()),
+            ..Default::default()
+        };
+        RegisterClassW(&wnd_class);
+
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            PCWSTR(wide_class.as_ptr()),
+            PCWSTR::null(),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            h_module,
+            None,
+        )?;
+
+        let shell_hook: Vec<u16> = "SHELLHOOK".encode_utf16().chain(Some(0)).collect();
+        SHELL_HOOK_MESSAGE.store(
+            RegisterWindowMessageW(PCWSTR(shell_hook.as_ptr())),
+            Ordering::Release,
+        );
+        RegisterShellHookWindow(hwnd).ok()?;
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, hwnd, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        Ok(())
+    }
+
+    pub fn start_layout_watcher() -> Result<()> {
+        *trace_lock!(LAST_LAYOUT_ID) = Self::current_layout_id().unwrap_or_default();
+        spawn_named_thread("Keyboard Layout Watcher", || unsafe {
+            log_error!(Self::create_background_window());
+        })?;
+        Ok(())
+    }
 }
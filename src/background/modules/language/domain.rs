@@ -12,3 +12,9 @@ pub struct Language {
     pub name: String,
     pub input_methods: Vec<KeyboardLayout>,
 }
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveKeyboardLayout {
+    pub language_code: String,
+    pub layout: KeyboardLayout,
+}
@@ -1,8 +1,41 @@
-use crate::error_handler::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use super::{application::LanguageManager, domain::Language};
+use tauri::Emitter;
+
+use crate::{error_handler::Result, log_error, seelen::get_app_handle};
+
+use super::{
+    application::{LanguageEvent, LanguageManager},
+    domain::{ActiveKeyboardLayout, Language},
+};
+
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+pub fn register_language_events() {
+    if REGISTERED.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    log::trace!("Registering language events");
+    LanguageManager::subscribe(|event| {
+        if let LanguageEvent::KeyboardLayoutChanged(_) = event {
+            if let Ok(layout) = LanguageManager::get_current() {
+                log_error!(get_app_handle().emit("keyboard-layout-changed", layout));
+            }
+        }
+    });
+    log_error!(LanguageManager::start_layout_watcher());
+}
 
 #[tauri::command(async)]
 pub fn get_system_languages() -> Result<Vec<Language>> {
     LanguageManager::enum_langs()
 }
+
+#[tauri::command(async)]
+pub fn get_current_input_language() -> Result<ActiveKeyboardLayout> {
+    LanguageManager::get_current()
+}
+
+#[tauri::command(async)]
+pub fn set_input_language(tag: String, layout_id: Option<String>) -> Result<()> {
+    LanguageManager::set_layout(&tag, layout_id.as_deref())
+}
@@ -0,0 +1,141 @@
+use std::{collections::HashSet, path::Path, sync::atomic::Ordering};
+
+use tauri_plugin_shell::ShellExt;
+use windows::Win32::UI::WindowsAndMessaging::{SW_MINIMIZE, SW_RESTORE};
+
+use crate::{
+    error_handler::Result,
+    hook::LAST_ACTIVE_NOT_SEELEN,
+    seelen::get_app_handle,
+    seelen_weg::SeelenWeg,
+    state::{application::FULL_STATE, infrastructure::update_extended_settings},
+    utils::{sleep_millis, spawn_named_thread},
+    windows_api::WindowsApi,
+};
+
+use super::domain::LaunchStatus;
+
+pub struct Launcher;
+impl Launcher {
+    /// items are identified by their program path, so the flag is shared by dock and launcher
+    fn normalize(program: &str) -> String {
+        program.trim().trim_matches('"').to_lowercase()
+    }
+
+    pub fn elevated_items() -> HashSet<String> {
+        FULL_STATE.load().extended_settings().elevated_items.clone()
+    }
+
+    pub fn is_elevated_item(program: &str) -> bool {
+        FULL_STATE
+            .load()
+            .extended_settings()
+            .elevated_items
+            .contains(&Self::normalize(program))
+    }
+
+    pub fn set_elevated_item(program: &str, elevated: bool) -> Result<()> {
+        let program = Self::normalize(program);
+        update_extended_settings(|settings| {
+            if elevated {
+                settings.elevated_items.insert(program.clone());
+            } else {
+                settings.elevated_items.remove(&program);
+            }
+        })
+    }
+
+    pub async fn run(program: &str, args: &[String]) -> Result<()> {
+        // we create a link file to trick with explorer into a separated process
+        // and without elevation in case Seelen UI was running as admin
+        // this could take some delay like is creating a file but just are some milliseconds
+        // and this exposed funtion is intended to just run certain times
+        let lnk_file = WindowsApi::create_temp_shortcut(program, &args.join(" "))?;
+        get_app_handle()
+            .shell()
+            .command("explorer")
+            .arg(&lnk_file)
+            .status()
+            .await?;
+        std::fs::remove_file(&lnk_file)?;
+        Ok(())
+    }
+
+    /// shows the UAC prompt if Seelen UI is not already elevated
+    pub fn run_as_admin(program: &str, args: &[String]) -> Result<LaunchStatus> {
+        match WindowsApi::shell_execute_as_admin(program, &args.join(" "))? {
+            true => Ok(LaunchStatus::Launched),
+            false => Ok(LaunchStatus::Cancelled),
+        }
+    }
+
+    /// `elevated` overrides the stored flag of the item
+    pub async fn launch(
+        program: &str,
+        args: &[String],
+        elevated: Option<bool>,
+    ) -> Result<LaunchStatus> {
+        let elevated = elevated.unwrap_or_else(|| Self::is_elevated_item(program));
+        if elevated {
+            return Self::run_as_admin(program, args);
+        }
+        Self::run(program, args).await?;
+        Ok(LaunchStatus::Launched)
+    }
+
+    /// `identifier` can be an exe path or an app user model id.
+    /// Repeated calls on an app with several windows cycle the focus through them.
+    pub async fn launch_or_focus(identifier: &str) -> Result<()> {
+        let windows = SeelenWeg::find_app_windows(identifier)?;
+        if windows.is_empty() {
+            if Path::new(identifier).exists() {
+                Self::run(identifier, &[]).await?;
+            } else {
+                let target = format!("shell:AppsFolder\\{identifier}");
+                Self::run("explorer.exe", &[target]).await?;
+            }
+            Self::focus_when_shown(identifier.to_owned())?;
+            return Ok(());
+        }
+
+        let last_active = LAST_ACTIVE_NOT_SEELEN.load(Ordering::Acquire);
+        let is_active = windows.iter().any(|w| w.address() == last_active);
+        let window = match (is_active, windows.len()) {
+            (false, _) => &windows[0],
+            (true, 1) => {
+                WindowsApi::show_window_async(windows[0].hwnd(), SW_MINIMIZE)?;
+                return Ok(());
+            }
+            // windows are in z-order so the last one is the least recently used
+            (true, _) => &windows[windows.len() - 1],
+        };
+
+        if window.is_minimized() {
+            WindowsApi::show_window_async(window.hwnd(), SW_RESTORE)?;
+        }
+        WindowsApi::async_force_set_foreground(window.hwnd());
+        Ok(())
+    }
+
+    /// Single instance apps can refuse the new process and only notify the running one,
+    /// that could be hidden on the tray and will not take the focus by itself.
+    fn focus_when_shown(identifier: String) -> Result<()> {
+        spawn_named_thread("Launch Or Focus", move || {
+            for _ in 0..20 {
+                sleep_millis(250);
+                match SeelenWeg::find_app_window(&identifier) {
+                    Ok(Some(window)) => {
+                        WindowsApi::async_force_set_foreground(window.hwnd());
+                        break;
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        log::error!("{:?}", err);
+                        break;
+                    }
+                }
+            }
+        })?;
+        Ok(())
+    }
+}
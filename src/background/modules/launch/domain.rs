@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum LaunchStatus {
+    Launched,
+    /// the user dismissed the UAC prompt, this is not a failure
+    Cancelled,
+}
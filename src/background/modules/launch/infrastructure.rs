@@ -0,0 +1,29 @@
+use std::collections::HashSet;
+
+use crate::error_handler::Result;
+
+use super::{application::Launcher, domain::LaunchStatus};
+
+#[tauri::command(async)]
+pub async fn launch_item(
+    program: String,
+    args: Vec<String>,
+    elevated: Option<bool>,
+) -> Result<LaunchStatus> {
+    Launcher::launch(&program, &args, elevated).await
+}
+
+#[tauri::command(async)]
+pub async fn launch_or_focus(identifier: String) -> Result<()> {
+    Launcher::launch_or_focus(&identifier).await
+}
+
+#[tauri::command(async)]
+pub fn get_elevated_items() -> HashSet<String> {
+    Launcher::elevated_items()
+}
+
+#[tauri::command(async)]
+pub fn set_item_elevated(program: String, elevated: bool) -> Result<()> {
+    Launcher::set_elevated_item(&program, elevated)
+}
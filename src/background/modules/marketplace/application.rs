@@ -0,0 +1,146 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+use crate::{error_handler::Result, trace_lock, utils::constants::SEELEN_COMMON};
+
+use super::domain::{ResourceIndex, ResourceIndexConfig, ResourceIndexEntry};
+
+lazy_static! {
+    static ref INDEX_CACHE: Mutex<Option<ResourceIndex>> = Mutex::new(None);
+}
+
+pub struct Marketplace;
+impl Marketplace {
+    fn read_config() -> Result<ResourceIndexConfig> {
+        let path = SEELEN_COMMON.resource_index_config_path();
+        if !path.exists() {
+            return Ok(ResourceIndexConfig::default());
+        }
+        Ok(serde_yaml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn config() -> ResourceIndexConfig {
+        Self::read_config().unwrap_or_else(|err| {
+            log::error!("Failed to read resource index config: {:?}", err);
+            ResourceIndexConfig::default()
+        })
+    }
+
+    pub fn set_index_url(url: String) -> Result<()> {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err("Invalid resource index url".into());
+        }
+        let config = ResourceIndexConfig {
+            url,
+            ..Self::config()
+        };
+        std::fs::write(
+            SEELEN_COMMON.resource_index_config_path(),
+            serde_yaml::to_string(&config)?,
+        )?;
+        // the cached catalog belongs to the old url
+        trace_lock!(INDEX_CACHE).take();
+        let cache_path = SEELEN_COMMON.resource_index_cache_path();
+        if cache_path.exists() {
+            std::fs::remove_file(cache_path)?;
+        }
+        Ok(())
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_secs()
+    }
+
+    /// memory cache first, then the copy stored on disk by a previous session
+    fn cached() -> Option<ResourceIndex> {
+        let mut cache = trace_lock!(INDEX_CACHE);
+        if cache.is_none() {
+            let path = SEELEN_COMMON.resource_index_cache_path();
+            *cache = std::fs::read_to_string(path)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok());
+        }
+        cache.clone()
+    }
+
+    fn store(index: &ResourceIndex) -> Result<()> {
+        *trace_lock!(INDEX_CACHE) = Some(index.clone());
+        std::fs::write(
+            SEELEN_COMMON.resource_index_cache_path(),
+            serde_json::to_string(index)?,
+        )?;
+        Ok(())
+    }
+
+    fn parse_entry(value: serde_json::Value) -> Result<ResourceIndexEntry> {
+        let entry: ResourceIndexEntry = serde_json::from_value(value)?;
+        if !entry.download_url.starts_with("https://") {
+            return Err(format!("{} has a non https download url", entry.id).into());
+        }
+        Ok(entry)
+    }
+
+    async fn fetch(config: &ResourceIndexConfig) -> Result<ResourceIndex> {
+        if config.url.is_empty() {
+            return Err("No resource index url configured".into());
+        }
+        let response = tauri_plugin_http::reqwest::get(&config.url)
+            .await?
+            .error_for_status()?;
+        let raw: Vec<serde_json::Value> = serde_json::from_str(&response.text().await?)?;
+        // a single bad entry on a (maybe self-hosted) catalog should not hide the rest
+        let entries = raw
+            .into_iter()
+            .filter_map(|value| match Self::parse_entry(value) {
+                Ok(entry) => Some(entry),
+                Err(err) => {
+                    log::warn!("Skipping invalid resource index entry: {:?}", err);
+                    None
+                }
+            })
+            .collect();
+        Ok(ResourceIndex {
+            entries,
+            updated_at: Self::now(),
+            stale: false,
+        })
+    }
+
+    pub async fn get_index() -> Result<ResourceIndex> {
+        let config = Self::config();
+        let cached = Self::cached();
+
+        if let Some(index) = &cached {
+            if Self::now().saturating_sub(index.updated_at) < config.ttl {
+                return Ok(index.clone());
+            }
+        }
+
+        match Self::fetch(&config).await {
+            Ok(index) => {
+                if let Err(err) = Self::store(&index) {
+                    log::error!("Failed to cache resource index: {:?}", err);
+                }
+                Ok(index)
+            }
+            Err(err) => match cached {
+                Some(index) => {
+                    log::warn!(
+                        "Failed to fetch resource index, using cached copy: {:?}",
+                        err
+                    );
+                    Ok(ResourceIndex {
+                        stale: true,
+                        ..index
+                    })
+                }
+                None => Err(err),
+            },
+        }
+    }
+}
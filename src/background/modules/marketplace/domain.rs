@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use crate::state::domain::ResourceKind;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceIndexEntry {
+    pub id: String,
+    pub name: String,
+    pub author: String,
+    pub kind: ResourceKind,
+    pub version: String,
+    /// url accepted by `install_resource_from_url`
+    pub download_url: String,
+    /// url of a preview image
+    pub preview: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceIndex {
+    pub entries: Vec<ResourceIndexEntry>,
+    /// unix timestamp in seconds
+    pub updated_at: u64,
+    /// true when the network request failed and this is the last cached catalog
+    pub stale: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ResourceIndexConfig {
+    /// url of a json catalog (array of entries), can point to a self-hosted catalog,
+    /// empty means no catalog is configured
+    pub url: String,
+    /// time in seconds to reuse the cached catalog before fetching again
+    pub ttl: u64,
+}
+
+impl Default for ResourceIndexConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            ttl: 3600,
+        }
+    }
+}
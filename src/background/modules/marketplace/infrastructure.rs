@@ -0,0 +1,18 @@
+use crate::error_handler::Result;
+
+use super::{application::Marketplace, domain::ResourceIndex};
+
+#[tauri::command(async)]
+pub async fn get_resource_index() -> Result<ResourceIndex> {
+    Marketplace::get_index().await
+}
+
+#[tauri::command(async)]
+pub fn get_resource_index_url() -> String {
+    Marketplace::config().url
+}
+
+#[tauri::command(async)]
+pub fn set_resource_index_url(url: String) -> Result<()> {
+    Marketplace::set_index_url(url)
+}
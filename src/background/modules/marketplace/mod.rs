@@ -0,0 +1,3 @@
+mod application;
+mod domain;
+pub mod infrastructure;
@@ -1,4 +1,9 @@
-use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use itertools::Itertools;
 use lazy_static::lazy_static;
@@ -9,7 +14,7 @@ use windows::{
         GlobalSystemMediaTransportControlsSession,
         GlobalSystemMediaTransportControlsSessionManager,
         GlobalSystemMediaTransportControlsSessionPlaybackStatus, MediaPropertiesChangedEventArgs,
-        PlaybackInfoChangedEventArgs, SessionsChangedEventArgs,
+        PlaybackInfoChangedEventArgs, SessionsChangedEventArgs, TimelinePropertiesChangedEventArgs,
     },
     Win32::{
         Devices::FunctionDiscovery::PKEY_Device_FriendlyName,
@@ -30,21 +35,25 @@ use windows::{
         UI::Shell::{PropertiesSystem::PROPERTYKEY, SIGDN_NORMALDISPLAY},
     },
 };
-use windows_core::Interface;
+use windows_core::{Interface, GUID};
 
 use crate::{
     error_handler::Result,
     event_manager, log_error,
     seelen_weg::icon_extractor::{extract_and_save_icon_from_file, extract_and_save_icon_umid},
     trace_lock,
-    utils::pcwstr,
+    utils::{pcwstr, spawn_named_thread},
     windows_api::{Com, WindowEnumerator, WindowsApi},
 };
 
 use super::domain::{
-    Device, DeviceChannel, IPolicyConfig, MediaPlayer, MediaPlayerOwner, PolicyConfig,
+    Device, DeviceChannel, IPolicyConfig, MediaPlayer, MediaPlayerOwner, MediaTimeline,
+    PolicyConfig, VolumeBalance,
 };
 
+/// interval used to report the progress of playing sessions
+const TIMELINE_TICK: Duration = Duration::from_secs(1);
+
 lazy_static! {
     pub static ref MEDIA_MANAGER: Arc<Mutex<MediaManager>> = Arc::new(Mutex::new(
         MediaManager::new().expect("Failed to create media manager")
@@ -79,6 +88,8 @@ pub enum MediaEvent {
         id: String,
         playing: bool,
     },
+    MediaPlayerTimelineChanged(MediaTimeline),
+    DeviceBalanceChanged(VolumeBalance),
 }
 
 #[windows_core::implement(IMMNotificationClient)]
@@ -176,6 +187,22 @@ impl MediaManagerEvents {
         Ok(())
     }
 
+    fn on_media_player_timeline_changed(
+        session: &Option<GlobalSystemMediaTransportControlsSession>,
+        _args: &Option<TimelinePropertiesChangedEventArgs>,
+    ) -> windows_core::Result<()> {
+        if let Some(session) = session {
+            match MediaManager::session_timeline(session) {
+                Ok(timeline) => {
+                    let tx = MediaManager::event_tx();
+                    log_error!(tx.send(MediaEvent::MediaPlayerTimelineChanged(timeline)));
+                }
+                Err(err) => log::error!("Failed to read media timeline: {:?}", err),
+            }
+        }
+        Ok(())
+    }
+
     fn on_media_players_changed(
         session_manager: &Option<GlobalSystemMediaTransportControlsSessionManager>,
         _args: &Option<SessionsChangedEventArgs>,
@@ -224,6 +251,13 @@ impl IAudioEndpointVolumeCallback_Impl for MediaDeviceEventHandler_Impl {
                 muted: data.bMuted.as_bool(),
             });
             log_error!(result);
+
+            // the notification data has a variable length array with the volume of each channel
+            let channels = unsafe {
+                std::slice::from_raw_parts(data.afChannelVolumes.as_ptr(), data.nChannels as usize)
+            };
+            let balance = VolumeBalance::from_channels(self.device_id.clone(), channels);
+            log_error!(tx.send(MediaEvent::DeviceBalanceChanged(balance)));
         }
         Ok(())
     }
@@ -310,6 +344,8 @@ impl IAudioSessionEvents_Impl for MediaSessionEventHandler_Impl {
 
 type OnDevicesChange = Box<dyn Fn(&Vec<Device>, &Vec<Device>) + Send + Sync>;
 type OnPlayersChange = Box<dyn Fn(&Vec<MediaPlayer>) + Send + Sync>;
+type OnTimelineChange = Box<dyn Fn(&MediaTimeline) + Send + Sync>;
+type OnBalanceChange = Box<dyn Fn(&VolumeBalance) + Send + Sync>;
 pub struct MediaManager {
     inputs: Vec<Device>,
     outputs: Vec<Device>,
@@ -317,6 +353,10 @@ pub struct MediaManager {
 
     registered_devices_callbacks: Vec<OnDevicesChange>,
     registered_players_callbacks: Vec<OnPlayersChange>,
+    registered_timeline_callbacks: Vec<OnTimelineChange>,
+    registered_balance_callbacks: Vec<OnBalanceChange>,
+    /// device id -> last reported balance, used to notify only real balance changes
+    devices_balance: HashMap<String, VolumeBalance>,
 
     device_enumerator: IMMDeviceEnumerator,
     mm_notification_client: IMMNotificationClient,
@@ -335,8 +375,19 @@ pub struct MediaManager {
     >,
     media_player_playback_event_handler:
         TypedEventHandler<GlobalSystemMediaTransportControlsSession, PlaybackInfoChangedEventArgs>,
-    /// session id -> (media properties changed event, playback info changed event)
-    media_player_event_tokens: HashMap<String, (EventRegistrationToken, EventRegistrationToken)>,
+    media_player_timeline_event_handler: TypedEventHandler<
+        GlobalSystemMediaTransportControlsSession,
+        TimelinePropertiesChangedEventArgs,
+    >,
+    /// session id -> (media properties changed event, playback info changed event, timeline changed event)
+    media_player_event_tokens: HashMap<
+        String,
+        (
+            EventRegistrationToken,
+            EventRegistrationToken,
+            EventRegistrationToken,
+        ),
+    >,
 }
 
 unsafe impl Send for MediaManager {}
@@ -400,6 +451,9 @@ impl MediaManager {
             playing: Vec::new(),
             registered_devices_callbacks: Vec::new(),
             registered_players_callbacks: Vec::new(),
+            registered_timeline_callbacks: Vec::new(),
+            registered_balance_callbacks: Vec::new(),
+            devices_balance: HashMap::new(),
 
             // unsafe com objects
             devices_audio_endpoint: HashMap::new(),
@@ -418,6 +472,9 @@ impl MediaManager {
             media_player_playback_event_handler: TypedEventHandler::new(
                 MediaManagerEvents::on_media_player_playback_changed,
             ),
+            media_player_timeline_event_handler: TypedEventHandler::new(
+                MediaManagerEvents::on_media_player_timeline_changed,
+            ),
         };
 
         unsafe { manager.initialize()? };
@@ -431,6 +488,20 @@ impl MediaManager {
         self.registered_players_callbacks.push(Box::new(callback));
     }
 
+    pub fn on_change_timeline<F>(&mut self, callback: F)
+    where
+        F: Fn(&MediaTimeline) + Send + Sync + 'static,
+    {
+        self.registered_timeline_callbacks.push(Box::new(callback));
+    }
+
+    pub fn on_change_balance<F>(&mut self, callback: F)
+    where
+        F: Fn(&VolumeBalance) + Send + Sync + 'static,
+    {
+        self.registered_balance_callbacks.push(Box::new(callback));
+    }
+
     pub fn on_change_devices<F>(&mut self, callback: F)
     where
         F: Fn(&Vec<Device>, &Vec<Device>) + Send + Sync + 'static,
@@ -458,6 +529,7 @@ impl MediaManager {
         self.media_player_manager
             .SessionsChanged(&self.media_player_manager_event_handler)?;
         Self::start_event_loop();
+        Self::start_timeline_ticker()?;
         Ok(())
     }
 
@@ -580,23 +652,28 @@ impl MediaManager {
             false
         })?;
 
+        let owner = owner.map(|w| MediaPlayerOwner {
+            name: w
+                .app_display_name()
+                .unwrap_or_else(|_| "Unknown App".to_string()),
+            icon_path: w
+                .app_user_model_id()
+                .and_then(|umid| extract_and_save_icon_umid(&umid).ok())
+                .or_else(|| w.exe().and_then(extract_and_save_icon_from_file).ok()),
+        });
+
+        let thumbnail = properties
+            .Thumbnail()
+            .ok()
+            .and_then(|stream| WindowsApi::extract_thumbnail_from_ref(stream).ok())
+            .or_else(|| Self::fallback_thumbnail(&source_app_user_model_id, owner.as_ref()));
+
         self.playing.push(MediaPlayer {
             id: source_app_user_model_id.clone(),
             title: properties.Title().unwrap_or_default().to_string_lossy(),
             author: properties.Artist().unwrap_or_default().to_string_lossy(),
-            owner: owner.map(|w| MediaPlayerOwner {
-                name: w
-                    .app_display_name()
-                    .unwrap_or_else(|_| "Unknown App".to_string()),
-                icon_path: w
-                    .app_user_model_id()
-                    .and_then(|umid| extract_and_save_icon_umid(&umid).ok())
-                    .or_else(|| w.exe().and_then(extract_and_save_icon_from_file).ok()),
-            }),
-            thumbnail: properties
-                .Thumbnail()
-                .ok()
-                .and_then(|stream| WindowsApi::extract_thumbnail_from_ref(stream).ok()),
+            owner,
+            thumbnail,
             playing: status == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Playing,
             default: false,
         });
@@ -607,12 +684,20 @@ impl MediaManager {
             (
                 session.MediaPropertiesChanged(&self.media_player_properties_event_handler)?,
                 session.PlaybackInfoChanged(&self.media_player_playback_event_handler)?,
+                session.TimelinePropertiesChanged(&self.media_player_timeline_event_handler)?,
             ),
         );
         self.media_players.insert(source_app_user_model_id, session);
         Ok(())
     }
 
+    /// icon of the player app, used when the track has no art
+    fn fallback_thumbnail(umid: &str, owner: Option<&MediaPlayerOwner>) -> Option<PathBuf> {
+        extract_and_save_icon_umid(umid)
+            .ok()
+            .or_else(|| owner.and_then(|o| o.icon_path.clone()))
+    }
+
     fn update_recommended_player(&mut self) {
         if let Ok(recommended) = self.get_recommended_player_id() {
             for player in &mut self.playing {
@@ -623,11 +708,12 @@ impl MediaManager {
 
     fn release_media_transport_session(&mut self, player_id: &str) -> Result<()> {
         if let Some(session) = self.media_players.remove(player_id) {
-            if let Some((properties_token, playback_token)) =
+            if let Some((properties_token, playback_token, timeline_token)) =
                 self.media_player_event_tokens.remove(player_id)
             {
                 session.RemoveMediaPropertiesChanged(properties_token)?;
                 session.RemovePlaybackInfoChanged(playback_token)?;
+                session.RemoveTimelinePropertiesChanged(timeline_token)?;
             }
         }
         self.playing.retain(|player| player.id != player_id);
@@ -646,8 +732,108 @@ impl MediaManager {
         }
     }
 
+    /// Timeline properties are only updated by the players on relevant changes (seek, track change, etc)
+    /// so the position is extrapolated from the last update while playing.
+    pub fn session_timeline(
+        session: &GlobalSystemMediaTransportControlsSession,
+    ) -> Result<MediaTimeline> {
+        // windows time is in 100ns ticks since 1601-01-01
+        const UNIX_EPOCH_AS_WINDOWS_TICKS: i64 = 116_444_736_000_000_000;
+        const TICKS_PER_MS: i64 = 10_000;
+
+        let timeline = session.GetTimelineProperties()?;
+        let playback = session.GetPlaybackInfo()?;
+        let playing = playback.PlaybackStatus()?
+            == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Playing;
+        let rate = playback
+            .PlaybackRate()
+            .and_then(|rate| rate.Value())
+            .unwrap_or(1.0);
+
+        let start = timeline.StartTime()?.Duration;
+        let end = timeline.EndTime()?.Duration;
+        let mut position = timeline.Position()?.Duration;
+        if playing {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as i64
+                / 100
+                + UNIX_EPOCH_AS_WINDOWS_TICKS;
+            let elapsed = now - timeline.LastUpdatedTime()?.UniversalTime;
+            position += (elapsed.max(0) as f64 * rate) as i64;
+        }
+
+        Ok(MediaTimeline {
+            id: session.SourceAppUserModelId()?.to_string_lossy(),
+            position_ms: (position.clamp(start, end.max(start)) - start) / TICKS_PER_MS,
+            duration_ms: (end - start).max(0) / TICKS_PER_MS,
+            rate,
+        })
+    }
+
+    fn start_timeline_ticker() -> Result<()> {
+        spawn_named_thread("Media Timeline Ticker", || loop {
+            std::thread::sleep(TIMELINE_TICK);
+            let timelines = {
+                let manager = trace_lock!(MEDIA_MANAGER);
+                manager
+                    .playing
+                    .iter()
+                    .filter(|player| player.playing)
+                    .filter_map(|player| manager.media_players.get(&player.id))
+                    .filter_map(|session| Self::session_timeline(session).ok())
+                    .collect_vec()
+            };
+            let tx = Self::event_tx();
+            for timeline in timelines {
+                log_error!(tx.send(MediaEvent::MediaPlayerTimelineChanged(timeline)));
+            }
+        })?;
+        Ok(())
+    }
+
+    /// `position_ms` is relative to the start of the media
+    pub fn seek(&self, id: &str, position_ms: i64) -> Result<()> {
+        let session = self.session_by_id(id).ok_or("Media player not found")?;
+        let controls = session.GetPlaybackInfo()?.Controls()?;
+        if !controls.IsPlaybackPositionEnabled()? {
+            return Err("The media player does not support seeking".into());
+        }
+        let start = session.GetTimelineProperties()?.StartTime()?.Duration;
+        let success = session
+            .TryChangePlaybackPositionAsync(start + position_ms.max(0) * 10_000)?
+            .get()?;
+        if !success {
+            return Err("failed to seek".into());
+        }
+        Ok(())
+    }
+
     fn start_event_loop() {
         Self::subscribe(|event| {
+            if let MediaEvent::MediaPlayerTimelineChanged(timeline) = &event {
+                let media_manager = trace_lock!(MEDIA_MANAGER);
+                for callback in &media_manager.registered_timeline_callbacks {
+                    callback(timeline);
+                }
+                return;
+            }
+
+            if let MediaEvent::DeviceBalanceChanged(balance) = event {
+                let mut media_manager = trace_lock!(MEDIA_MANAGER);
+                if media_manager.devices_balance.get(&balance.device_id) == Some(&balance) {
+                    return;
+                }
+                for callback in &media_manager.registered_balance_callbacks {
+                    callback(&balance);
+                }
+                media_manager
+                    .devices_balance
+                    .insert(balance.device_id.clone(), balance);
+                return;
+            }
+
             let is_changing_players = matches!(
                 event,
                 MediaEvent::MediaPlayerAdded(_)
@@ -733,7 +919,8 @@ impl MediaManager {
                 if let Some(player) = self.player_mut(&id) {
                     player.title = title;
                     player.author = author;
-                    player.thumbnail = thumbnail;
+                    player.thumbnail = thumbnail
+                        .or_else(|| Self::fallback_thumbnail(&player.id, player.owner.as_ref()));
                 }
             }
             MediaEvent::MediaPlayerPlaybackStatusChanged { id, playing } => {
@@ -741,6 +928,7 @@ impl MediaManager {
                     player.playing = playing;
                 }
             }
+            MediaEvent::MediaPlayerTimelineChanged(_) | MediaEvent::DeviceBalanceChanged(_) => {}
         }
         Ok(())
     }
@@ -760,6 +948,45 @@ impl MediaManager {
         Ok(())
     }
 
+    fn default_output_endpoint(&self) -> Result<(String, &IAudioEndpointVolume)> {
+        let device_id = unsafe {
+            self.device_enumerator
+                .GetDefaultAudioEndpoint(eRender, eMultimedia)?
+                .GetId()?
+                .to_string()?
+        };
+        let (endpoint, _) = self
+            .devices_audio_endpoint
+            .get(&device_id)
+            .ok_or("Default output device not loaded")?;
+        Ok((device_id, endpoint))
+    }
+
+    pub fn get_volume_balance(&self) -> Result<VolumeBalance> {
+        let (device_id, endpoint) = self.default_output_endpoint()?;
+        let channels = unsafe {
+            (0..endpoint.GetChannelCount()?)
+                .map(|channel| endpoint.GetChannelVolumeLevelScalar(channel))
+                .collect::<windows_core::Result<Vec<f32>>>()?
+        };
+        Ok(VolumeBalance::from_channels(device_id, &channels))
+    }
+
+    /// Mono devices are not modified, the returned status will be flagged as not supported
+    pub fn set_volume_balance(&self, balance: f32) -> Result<VolumeBalance> {
+        let (device_id, endpoint) = self.default_output_endpoint()?;
+        unsafe {
+            if endpoint.GetChannelCount()? < 2 {
+                return Ok(VolumeBalance::from_channels(device_id, &[]));
+            }
+            let level = endpoint.GetMasterVolumeLevelScalar()?;
+            let (left, right) = VolumeBalance::channel_levels(balance, level);
+            endpoint.SetChannelVolumeLevelScalar(0, left, &GUID::zeroed())?;
+            endpoint.SetChannelVolumeLevelScalar(1, right, &GUID::zeroed())?;
+        }
+        self.get_volume_balance()
+    }
+
     /// Release all resources
     /// should be called on application exit
     pub fn release(&mut self) {
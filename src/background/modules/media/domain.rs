@@ -31,6 +31,56 @@ pub struct MediaPlayer {
     pub default: bool,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaTimeline {
+    /// media player id
+    pub id: String,
+    pub position_ms: i64,
+    pub duration_ms: i64,
+    pub rate: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeBalance {
+    pub device_id: String,
+    /// -1.0 is full left and 1.0 is full right
+    pub balance: f32,
+    /// false on mono devices, where the balance is ignored
+    pub supported: bool,
+}
+
+impl VolumeBalance {
+    /// only the front left and front right channels (first two) are taken into account
+    pub fn from_channels(device_id: String, channels: &[f32]) -> Self {
+        if channels.len() < 2 {
+            return Self {
+                device_id,
+                balance: 0.0,
+                supported: false,
+            };
+        }
+        let (left, right) = (channels[0], channels[1]);
+        let max = left.max(right);
+        Self {
+            device_id,
+            balance: if max > 0.0 { (right - left) / max } else { 0.0 },
+            supported: true,
+        }
+    }
+
+    /// returns the (left, right) channel levels for the given balance and master level
+    pub fn channel_levels(balance: f32, level: f32) -> (f32, f32) {
+        let balance = balance.clamp(-1.0, 1.0);
+        if balance < 0.0 {
+            (level, level * (1.0 + balance))
+        } else {
+            (level * (1.0 - balance), level)
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct DeviceChannel {
     pub id: String,
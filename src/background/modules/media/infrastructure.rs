@@ -4,11 +4,11 @@ use tauri::Emitter;
 use windows::core::GUID;
 
 use crate::{
-    error_handler::Result, modules::media::application::MEDIA_MANAGER, seelen::get_app_handle,
-    trace_lock,
+    error_handler::Result, log_error, modules::media::application::MEDIA_MANAGER,
+    seelen::get_app_handle, trace_lock,
 };
 
-use super::domain::{Device, MediaPlayer};
+use super::domain::{Device, MediaPlayer, MediaTimeline, VolumeBalance};
 
 fn emit_media_sessions(playing: &Vec<MediaPlayer>) {
     let app = get_app_handle();
@@ -16,6 +16,14 @@ fn emit_media_sessions(playing: &Vec<MediaPlayer>) {
         .expect("failed to emit");
 }
 
+fn emit_media_timeline(timeline: &MediaTimeline) {
+    log_error!(get_app_handle().emit("media-timeline-changed", timeline));
+}
+
+fn emit_volume_balance(balance: &VolumeBalance) {
+    log_error!(get_app_handle().emit("volume-balance-changed", balance));
+}
+
 fn emit_media_devices(inputs: &Vec<Device>, outputs: &Vec<Device>) {
     let app = get_app_handle();
     app.emit(SeelenEvent::MediaInputs, inputs)
@@ -36,6 +44,8 @@ pub fn register_media_events() {
             log::trace!("Registering media events");
             manager.on_change_devices(emit_media_devices);
             manager.on_change_players(emit_media_sessions);
+            manager.on_change_timeline(emit_media_timeline);
+            manager.on_change_balance(emit_volume_balance);
         }
         emit_media_devices(manager.inputs(), manager.outputs());
         emit_media_sessions(manager.playing());
@@ -87,6 +97,23 @@ pub fn media_toggle_play_pause(id: String) -> Result<()> {
     Ok(())
 }
 
+/// `position_ms` is relative to the start of the media
+#[tauri::command(async)]
+pub fn media_seek(id: String, position_ms: i64) -> Result<()> {
+    trace_lock!(MEDIA_MANAGER).seek(&id, position_ms)
+}
+
+#[tauri::command(async)]
+pub fn get_volume_balance() -> Result<VolumeBalance> {
+    trace_lock!(MEDIA_MANAGER).get_volume_balance()
+}
+
+/// `balance` goes from -1.0 (left) to 1.0 (right)
+#[tauri::command(async)]
+pub fn set_volume_balance(balance: f32) -> Result<VolumeBalance> {
+    trace_lock!(MEDIA_MANAGER).set_volume_balance(balance)
+}
+
 #[tauri::command(async)]
 pub fn media_toggle_mute(id: String, _session_id: Option<String>) -> Result<()> {
     let manager = trace_lock!(MEDIA_MANAGER);
@@ -1,16 +1,45 @@
+pub mod bluetooth;
+pub mod capture;
 pub mod cli;
+pub mod desktop_icons;
+pub mod devices;
+pub mod favorites;
+pub mod file_watcher;
+pub mod focus_assist;
+pub mod fonts;
 pub mod input;
+pub mod jump_list;
 pub mod language;
+pub mod launch;
+pub mod marketplace;
 pub mod media;
 pub mod monitors;
 pub mod network;
+pub mod night_light;
 pub mod notifications;
+pub mod peripherals;
+pub mod pip;
 pub mod power;
+pub mod presentation;
+pub mod processes;
+pub mod radios;
+pub mod recycle_bin;
+pub mod registry;
+pub mod scheduled_tasks;
+pub mod shortcuts;
+pub mod show_desktop;
+pub mod spatial_audio;
 pub mod start;
+pub mod storage;
 pub mod system_settings;
+pub mod theme_variables;
 pub mod tray;
 pub mod uwp;
 pub mod virtual_desk;
+pub mod wallpaper;
+pub mod weather;
+pub mod window_snap;
+pub mod window_style;
 
 #[macro_export]
 macro_rules! event_manager {
@@ -4,9 +4,17 @@ use std::sync::Arc;
 use windows::{
     core::PCWSTR,
     Win32::{
-        Devices::Display::GUID_DEVINTERFACE_MONITOR,
+        Devices::Display::{
+            GetDisplayConfigBufferSizes, QueryDisplayConfig, SetDisplayConfig,
+            DISPLAYCONFIG_MODE_INFO, DISPLAYCONFIG_PATH_INFO, DISPLAYCONFIG_TOPOLOGY_ID,
+            GUID_DEVINTERFACE_MONITOR, QDC_ALL_PATHS, QDC_DATABASE_CURRENT,
+            QUERY_DISPLAY_CONFIG_FLAGS, SDC_APPLY,
+        },
         Foundation::{HWND, LPARAM, LRESULT, WPARAM},
-        Graphics::Gdi::HMONITOR,
+        Graphics::Gdi::{
+            CDS_NORESET, CDS_SET_PRIMARY, CDS_TEST, CDS_TYPE, CDS_UPDATEREGISTRY, DM_BITSPERPEL,
+            DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH, DM_POSITION, HMONITOR,
+        },
         UI::WindowsAndMessaging::{
             CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
             RegisterDeviceNotificationW, TranslateMessage, DBT_DEVTYP_DEVICEINTERFACE,
@@ -20,9 +28,11 @@ use crate::{
     error_handler::Result,
     event_manager, log_error, trace_lock,
     utils::spawn_named_thread,
-    windows_api::{MonitorEnumerator, WindowsApi},
+    windows_api::{monitor::Monitor, MonitorEnumerator, WindowsApi},
 };
 
+use super::domain::{DisplayMode, MonitorMode};
+
 lazy_static! {
     pub static ref MONITOR_MANAGER: Arc<Mutex<MonitorManager>> = Arc::new(Mutex::new(
         MonitorManager::new().expect("Failed to create monitor manager")
@@ -56,6 +66,7 @@ impl MonitorManager {
             // Added based on this https://stackoverflow.com/a/33762334
             WM_DISPLAYCHANGE | WM_SETTINGCHANGE | WM_DEVICECHANGE => {
                 // log::debug!("Dispatching {}, {:?}, {:?}", message, wparam, lparam);
+                Monitor::invalidate_display_devices();
                 let mut old_list = { trace_lock!(MONITOR_MANAGER).monitors.clone() };
                 let new_list = match Self::get_monitors() {
                     Ok(monitors) => monitors,
@@ -162,6 +173,137 @@ impl MonitorManager {
         })
     }
 
+    /// Moves all the monitors so the target one ends up at the origin (0,0) and marks it as primary,
+    /// positions are relative to the primary monitor so they all have to be recalculated.
+    pub fn set_primary(target: Monitor) -> Result<()> {
+        if target.is_primary()? {
+            return Ok(());
+        }
+
+        let origin = target.rect()?;
+        for monitor in MonitorEnumerator::get_all_v2()? {
+            let info = WindowsApi::monitor_info(monitor.handle())?;
+            let mut devmode = WindowsApi::get_display_device_settings(monitor.handle())?;
+            devmode.dmFields |= DM_POSITION;
+            unsafe {
+                let position = &mut devmode.Anonymous1.Anonymous2.dmPosition;
+                position.x -= origin.left;
+                position.y -= origin.top;
+            }
+
+            let mut flags = CDS_UPDATEREGISTRY | CDS_NORESET;
+            if monitor == target {
+                flags |= CDS_SET_PRIMARY;
+            }
+            WindowsApi::change_display_settings(
+                Some(info.szDevice.as_slice()),
+                Some(&devmode),
+                flags,
+            )?;
+        }
+
+        // apply all the queued changes at once
+        WindowsApi::change_display_settings(None, None, CDS_TYPE(0))
+    }
+
+    /// Supported modes sorted from the biggest to the smallest, without repeated entries
+    pub fn supported_modes(monitor: Monitor) -> Result<Vec<MonitorMode>> {
+        let mut modes: Vec<MonitorMode> = WindowsApi::get_display_device_modes(monitor.handle())?
+            .iter()
+            .map(MonitorMode::from)
+            .collect();
+        modes.sort_by(|a, b| b.cmp(a));
+        modes.dedup();
+        Ok(modes)
+    }
+
+    /// Tests the mode before applying it and reverts to the previous mode if
+    /// the display doesn't end up using the requested one.
+    pub fn set_mode(monitor: Monitor, mode: MonitorMode) -> Result<()> {
+        let current = WindowsApi::get_display_device_settings(monitor.handle())?;
+        let candidates = WindowsApi::get_display_device_modes(monitor.handle())?;
+        let mut devmode = candidates
+            .iter()
+            .filter(|devmode| MonitorMode::from(*devmode) == mode)
+            // keep the current color depth if possible
+            .max_by_key(|devmode| devmode.dmBitsPerPel == current.dmBitsPerPel)
+            .copied()
+            .ok_or("Mode not supported by the monitor")?;
+        devmode.dmFields = DM_PELSWIDTH | DM_PELSHEIGHT | DM_DISPLAYFREQUENCY | DM_BITSPERPEL;
+
+        let info = WindowsApi::monitor_info(monitor.handle())?;
+        let device = Some(info.szDevice.as_slice());
+        WindowsApi::change_display_settings(device, Some(&devmode), CDS_TEST)?;
+        WindowsApi::change_display_settings(device, Some(&devmode), CDS_UPDATEREGISTRY)?;
+
+        let applied = WindowsApi::get_display_device_settings(monitor.handle())?;
+        if MonitorMode::from(&applied) != mode {
+            let mut previous = current;
+            previous.dmFields = DM_PELSWIDTH | DM_PELSHEIGHT | DM_DISPLAYFREQUENCY | DM_BITSPERPEL;
+            WindowsApi::change_display_settings(device, Some(&previous), CDS_UPDATEREGISTRY)?;
+            return Err("The monitor rejected the requested mode, previous mode restored".into());
+        }
+        Ok(())
+    }
+
+    fn query_display_config(
+        flags: QUERY_DISPLAY_CONFIG_FLAGS,
+        topology: Option<&mut DISPLAYCONFIG_TOPOLOGY_ID>,
+    ) -> Result<Vec<DISPLAYCONFIG_PATH_INFO>> {
+        let mut path_count = 0;
+        let mut mode_count = 0;
+        unsafe {
+            GetDisplayConfigBufferSizes(flags, &mut path_count, &mut mode_count).ok()?;
+            let mut paths = vec![DISPLAYCONFIG_PATH_INFO::default(); path_count as usize];
+            let mut modes = vec![DISPLAYCONFIG_MODE_INFO::default(); mode_count as usize];
+            QueryDisplayConfig(
+                flags,
+                &mut path_count,
+                paths.as_mut_ptr(),
+                &mut mode_count,
+                modes.as_mut_ptr(),
+                topology.map(|t| t as *mut _),
+            )
+            .ok()?;
+            paths.truncate(path_count as usize);
+            Ok(paths)
+        }
+    }
+
+    /// Number of displays plugged in, including the ones disabled by the current display mode
+    fn connected_displays_count() -> Result<usize> {
+        let mut targets = Self::query_display_config(QDC_ALL_PATHS, None)?
+            .into_iter()
+            .filter(|path| path.targetInfo.targetAvailable.as_bool())
+            .map(|path| {
+                let adapter = path.targetInfo.adapterId;
+                (adapter.HighPart, adapter.LowPart, path.targetInfo.id)
+            })
+            .collect::<Vec<_>>();
+        targets.sort();
+        targets.dedup();
+        Ok(targets.len())
+    }
+
+    pub fn display_mode() -> Result<DisplayMode> {
+        let mut topology = DISPLAYCONFIG_TOPOLOGY_ID::default();
+        Self::query_display_config(QDC_DATABASE_CURRENT, Some(&mut topology))?;
+        DisplayMode::try_from(topology)
+    }
+
+    /// Equivalent to the Win + P menu, does nothing if there is only one display connected.
+    /// Returns the resulting display mode.
+    pub fn set_display_mode(mode: DisplayMode) -> Result<DisplayMode> {
+        if Self::connected_displays_count()? < 2 {
+            return Self::display_mode();
+        }
+        let result = unsafe { SetDisplayConfig(None, None, SDC_APPLY | mode.topology_flag()) };
+        if result != 0 {
+            return Err(format!("Failed to set display mode: {}", result).into());
+        }
+        Self::display_mode()
+    }
+
     fn get_monitors() -> Result<Vec<(String, HMONITOR)>> {
         let mut monitors = Vec::new();
         for m in MonitorEnumerator::get_all_v2()? {
@@ -1,4 +1,14 @@
-use serde::Serialize;
+use seelen_core::rect::Rect;
+use serde::{Deserialize, Serialize};
+use windows::Win32::{
+    Devices::Display::{
+        DISPLAYCONFIG_TOPOLOGY_CLONE, DISPLAYCONFIG_TOPOLOGY_EXTEND,
+        DISPLAYCONFIG_TOPOLOGY_EXTERNAL, DISPLAYCONFIG_TOPOLOGY_ID,
+        DISPLAYCONFIG_TOPOLOGY_INTERNAL, SDC_TOPOLOGY_CLONE, SDC_TOPOLOGY_EXTEND,
+        SDC_TOPOLOGY_EXTERNAL, SDC_TOPOLOGY_INTERNAL, SET_DISPLAY_CONFIG_FLAGS,
+    },
+    Graphics::Gdi::DEVMODEW,
+};
 
 use crate::{
     error_handler::AppError,
@@ -12,6 +22,9 @@ pub struct PhysicalMonitor {
     pub width: u32,
     pub height: u32,
     pub dpi: f32,
+    pub is_primary: bool,
+    pub rect: Rect,
+    pub work_area: Rect,
 }
 
 impl TryFrom<Monitor> for PhysicalMonitor {
@@ -26,6 +39,61 @@ impl TryFrom<Monitor> for PhysicalMonitor {
             width: (rect.right - rect.left) as u32,
             height: (rect.bottom - rect.top) as u32,
             dpi,
+            is_primary: m.is_primary()?,
+            work_area: m.work_area()?,
+            rect,
         })
     }
 }
+
+/// Same options as the Win + P menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    /// PC screen only
+    Internal,
+    Duplicate,
+    Extend,
+    /// Second screen only
+    External,
+}
+
+impl DisplayMode {
+    pub fn topology_flag(self) -> SET_DISPLAY_CONFIG_FLAGS {
+        match self {
+            DisplayMode::Internal => SDC_TOPOLOGY_INTERNAL,
+            DisplayMode::Duplicate => SDC_TOPOLOGY_CLONE,
+            DisplayMode::Extend => SDC_TOPOLOGY_EXTEND,
+            DisplayMode::External => SDC_TOPOLOGY_EXTERNAL,
+        }
+    }
+}
+
+impl TryFrom<DISPLAYCONFIG_TOPOLOGY_ID> for DisplayMode {
+    type Error = AppError;
+    fn try_from(topology: DISPLAYCONFIG_TOPOLOGY_ID) -> Result<Self, Self::Error> {
+        match topology {
+            DISPLAYCONFIG_TOPOLOGY_INTERNAL => Ok(DisplayMode::Internal),
+            DISPLAYCONFIG_TOPOLOGY_CLONE => Ok(DisplayMode::Duplicate),
+            DISPLAYCONFIG_TOPOLOGY_EXTEND => Ok(DisplayMode::Extend),
+            DISPLAYCONFIG_TOPOLOGY_EXTERNAL => Ok(DisplayMode::External),
+            _ => Err(format!("Unknown display topology: {:?}", topology).into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct MonitorMode {
+    pub width: u32,
+    pub height: u32,
+    pub refresh_hz: u32,
+}
+
+impl From<&DEVMODEW> for MonitorMode {
+    fn from(devmode: &DEVMODEW) -> Self {
+        Self {
+            width: devmode.dmPelsWidth,
+            height: devmode.dmPelsHeight,
+            refresh_hz: devmode.dmDisplayFrequency,
+        }
+    }
+}
@@ -1,11 +1,17 @@
-use seelen_core::handlers::SeelenEvent;
+use seelen_core::{handlers::SeelenEvent, rect::Rect};
 use tauri::Emitter;
 
 use crate::{
-    error_handler::Result, log_error, seelen::get_app_handle, windows_api::MonitorEnumerator,
+    error_handler::Result,
+    log_error,
+    seelen::get_app_handle,
+    windows_api::{monitor::Monitor, MonitorEnumerator},
 };
 
-use super::{domain::PhysicalMonitor, MonitorManager};
+use super::{
+    domain::{DisplayMode, MonitorMode, PhysicalMonitor},
+    MonitorManager,
+};
 
 fn _get_connected_monitors() -> Result<Vec<PhysicalMonitor>> {
     let mut monitors = Vec::new();
@@ -15,15 +21,74 @@ fn _get_connected_monitors() -> Result<Vec<PhysicalMonitor>> {
     Ok(monitors)
 }
 
+fn emit_connected_monitors() {
+    if let Ok(monitors) = _get_connected_monitors() {
+        log_error!(get_app_handle().emit(SeelenEvent::SystemMonitorsChanged, monitors));
+    }
+}
+
+/// Monitor updates include work area changes (`WM_SETTINGCHANGE` with `SPI_SETWORKAREA`),
+/// that are broadcasted each time an app bar is registered, moved or removed.
 pub fn register_monitor_webview_events() {
-    MonitorManager::subscribe(|_event| {
-        if let Ok(monitors) = _get_connected_monitors() {
-            log_error!(get_app_handle().emit(SeelenEvent::SystemMonitorsChanged, monitors));
-        }
-    });
+    MonitorManager::subscribe(|_event| emit_connected_monitors());
 }
 
 #[tauri::command(async)]
 pub fn get_connected_monitors() -> Result<Vec<PhysicalMonitor>> {
     _get_connected_monitors()
 }
+
+#[tauri::command(async)]
+pub fn get_monitor_work_area(device_id: String) -> Result<Rect> {
+    Monitor::by_id(&device_id)
+        .ok_or("Monitor not found")?
+        .work_area()
+}
+
+/// Returns the device id of the primary monitor
+#[tauri::command(async)]
+pub fn get_primary_monitor() -> Result<String> {
+    Monitor::primary().device_id()
+}
+
+#[tauri::command(async)]
+pub fn set_primary_monitor(device_id: String) -> Result<()> {
+    let monitor = Monitor::by_id(&device_id).ok_or("Monitor not found")?;
+    MonitorManager::set_primary(monitor)?;
+    emit_connected_monitors();
+    Ok(())
+}
+
+#[tauri::command(async)]
+pub fn get_display_mode() -> Result<DisplayMode> {
+    MonitorManager::display_mode()
+}
+
+/// Returns the resulting display mode
+#[tauri::command(async)]
+pub fn set_display_mode(mode: DisplayMode) -> Result<DisplayMode> {
+    let mode = MonitorManager::set_display_mode(mode)?;
+    emit_connected_monitors();
+    Ok(mode)
+}
+
+#[tauri::command(async)]
+pub fn get_monitor_supported_modes(device_id: String) -> Result<Vec<MonitorMode>> {
+    let monitor = Monitor::by_id(&device_id).ok_or("Monitor not found")?;
+    MonitorManager::supported_modes(monitor)
+}
+
+#[tauri::command(async)]
+pub fn set_monitor_mode(device_id: String, width: u32, height: u32, refresh_hz: u32) -> Result<()> {
+    let monitor = Monitor::by_id(&device_id).ok_or("Monitor not found")?;
+    MonitorManager::set_mode(
+        monitor,
+        MonitorMode {
+            width,
+            height,
+            refresh_hz,
+        },
+    )?;
+    emit_connected_monitors();
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tauri::Emitter;
+use windows::Win32::{
+    Foundation::HANDLE,
+    System::Registry::{RegNotifyChangeKeyValue, HKEY, REG_NOTIFY_CHANGE_LAST_SET},
+};
+use winreg::{
+    enums::{RegType, HKEY_CURRENT_USER, KEY_NOTIFY, KEY_READ, KEY_WRITE},
+    RegKey, RegValue,
+};
+
+use crate::{
+    error_handler::{AppError, Result},
+    log_error,
+    seelen::get_app_handle,
+    utils::spawn_named_thread,
+};
+
+use super::domain::NightLightBlob;
+
+const STATE_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\CloudStore\Store\DefaultAccount\Current\default$windows.data.bluelightreduction.bluelightreductionstate\windows.data.bluelightreduction.bluelightreductionstate";
+const STATE_VALUE: &str = "Data";
+
+static WATCHING: AtomicBool = AtomicBool::new(false);
+
+pub struct NightLight;
+impl NightLight {
+    fn open(flags: u32) -> Result<RegKey> {
+        RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey_with_flags(STATE_KEY, flags)
+            .map_err(|_| {
+                AppError::from(
+                    "Night light state not found, it is created when night light is used for first time",
+                )
+            })
+    }
+
+    fn read_blob(key: &RegKey) -> Result<NightLightBlob> {
+        NightLightBlob::parse(key.get_raw_value(STATE_VALUE)?.bytes)
+    }
+
+    pub fn is_enabled() -> Result<bool> {
+        Ok(Self::read_blob(&Self::open(KEY_READ)?)?.is_enabled())
+    }
+
+    /// The night light service watches this value so changes are applied right after writing it.
+    pub fn set_enabled(enabled: bool) -> Result<()> {
+        let key = Self::open(KEY_READ | KEY_WRITE)?;
+        let blob = Self::read_blob(&key)?;
+        if blob.is_enabled() == enabled {
+            return Ok(());
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        key.set_raw_value(
+            STATE_VALUE,
+            &RegValue {
+                vtype: RegType::REG_BINARY,
+                bytes: blob.with_enabled(enabled, timestamp)?,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Emits `night-light-changed` on every toggle, including the ones done by the OS scheduler.
+    pub fn watch() -> Result<()> {
+        if WATCHING.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+
+        let key = match Self::open(KEY_READ | KEY_NOTIFY) {
+            Ok(key) => key,
+            Err(err) => {
+                WATCHING.store(false, Ordering::Release);
+                return Err(err);
+            }
+        };
+
+        spawn_named_thread("Night Light Watcher", move || {
+            let mut last = Self::read_blob(&key).map(|b| b.is_enabled()).ok();
+            loop {
+                // blocks until the key changes
+                let result = unsafe {
+                    RegNotifyChangeKeyValue(
+                        HKEY(key.raw_handle() as _),
+                        false,
+                        REG_NOTIFY_CHANGE_LAST_SET,
+                        HANDLE::default(),
+                        false,
+                    )
+                };
+                if let Err(err) = result.ok() {
+                    log::error!("Night light watcher stopped: {:?}", err);
+                    WATCHING.store(false, Ordering::Release);
+                    break;
+                }
+
+                match Self::read_blob(&key) {
+                    Ok(blob) => {
+                        let enabled = blob.is_enabled();
+                        if last != Some(enabled) {
+                            last = Some(enabled);
+                            log_error!(get_app_handle().emit("night-light-changed", enabled));
+                        }
+                    }
+                    Err(err) => log::error!("Failed to read night light state: {:?}", err),
+                }
+            }
+        })?;
+        Ok(())
+    }
+}
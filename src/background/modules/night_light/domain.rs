@@ -0,0 +1,113 @@
+use std::ops::Range;
+
+use crate::error_handler::Result;
+
+const BLOB_HEADER: [u8; 4] = [0x43, 0x42, 0x01, 0x00];
+const BLOB_VERSION: [u8; 4] = [0x0A, 0x02, 0x01, 0x00];
+const TIMESTAMP_TAG: [u8; 2] = [0x2A, 0x06];
+const PAYLOAD_TAG: [u8; 3] = [0x2A, 0x2B, 0x0E];
+/// present at the start of the payload only while night light is on
+const ENABLED_FLAG: [u8; 2] = [0x10, 0x00];
+
+fn read_varint(bytes: &[u8], start: usize) -> Option<(u64, Range<usize>)> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().skip(start).take(10) {
+        value |= ((byte & 0x7F) as u64) << (7 * (i - start));
+        if byte & 0x80 == 0 {
+            return Some((value, start..i + 1));
+        }
+    }
+    None
+}
+
+fn write_varint(mut value: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return out;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// `Data` value of the night light state on the CloudStore:
+///
+/// `header, version, timestamp tag, varint timestamp, payload tag, varint payload size, header, [enabled flag], ...`
+#[derive(Debug, Clone)]
+pub struct NightLightBlob {
+    bytes: Vec<u8>,
+    timestamp: Range<usize>,
+    size: Range<usize>,
+    payload_start: usize,
+}
+
+impl NightLightBlob {
+    pub fn parse(bytes: Vec<u8>) -> Result<Self> {
+        let unsupported = || "Unsupported night light data format";
+
+        let expect = |at: usize, expected: &[u8]| -> Result<usize> {
+            match bytes.get(at..at + expected.len()) {
+                Some(slice) if slice == expected => Ok(at + expected.len()),
+                _ => Err(unsupported().into()),
+            }
+        };
+
+        let mut cursor = expect(0, &BLOB_HEADER)?;
+        cursor = expect(cursor, &BLOB_VERSION)?;
+        cursor = expect(cursor, &TIMESTAMP_TAG)?;
+        let (_, timestamp) = read_varint(&bytes, cursor).ok_or_else(unsupported)?;
+        cursor = expect(timestamp.end, &PAYLOAD_TAG)?;
+        let (_, size) = read_varint(&bytes, cursor).ok_or_else(unsupported)?;
+        let payload_start = expect(size.end, &BLOB_HEADER)?;
+
+        Ok(Self {
+            bytes,
+            timestamp,
+            size,
+            payload_start,
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.bytes
+            .get(self.payload_start..self.payload_start + ENABLED_FLAG.len())
+            .is_some_and(|flag| flag == ENABLED_FLAG)
+    }
+
+    /// Returns the patched blob, the timestamp is updated because the system ignores
+    /// writes that are not newer than the stored state.
+    pub fn with_enabled(&self, enabled: bool, timestamp: u64) -> Result<Vec<u8>> {
+        let (size, _) = read_varint(&self.bytes, self.size.start)
+            .ok_or("Unsupported night light data format")?;
+        let flag_len = ENABLED_FLAG.len() as u64;
+
+        let was_enabled = self.is_enabled();
+        let size = match (was_enabled, enabled) {
+            (false, true) => size + flag_len,
+            (true, false) => size
+                .checked_sub(flag_len)
+                .ok_or("Unsupported night light data format")?,
+            _ => size,
+        };
+        let rest_start = if was_enabled {
+            self.payload_start + ENABLED_FLAG.len()
+        } else {
+            self.payload_start
+        };
+
+        let mut out = Vec::with_capacity(self.bytes.len() + ENABLED_FLAG.len());
+        out.extend_from_slice(&self.bytes[..self.timestamp.start]);
+        out.extend(write_varint(timestamp));
+        out.extend_from_slice(&self.bytes[self.timestamp.end..self.size.start]);
+        out.extend(write_varint(size));
+        out.extend_from_slice(&self.bytes[self.size.end..self.payload_start]);
+        if enabled {
+            out.extend_from_slice(&ENABLED_FLAG);
+        }
+        out.extend_from_slice(&self.bytes[rest_start..]);
+        Ok(out)
+    }
+}
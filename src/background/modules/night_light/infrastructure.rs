@@ -0,0 +1,22 @@
+use tauri::Emitter;
+
+use crate::{error_handler::Result, log_error, seelen::get_app_handle};
+
+use super::application::NightLight;
+
+pub fn register_night_light_events() {
+    log_error!(NightLight::watch());
+    if let Ok(enabled) = NightLight::is_enabled() {
+        log_error!(get_app_handle().emit("night-light-changed", enabled));
+    }
+}
+
+#[tauri::command(async)]
+pub fn get_night_light() -> Result<bool> {
+    NightLight::is_enabled()
+}
+
+#[tauri::command(async)]
+pub fn set_night_light(enabled: bool) -> Result<()> {
+    NightLight::set_enabled(enabled)
+}
@@ -0,0 +1,27 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::modules::focus_assist::domain::FocusAssistMode;
+
+/// Focus Assist mode applied while do not disturb is enabled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum DndMode {
+    #[default]
+    PriorityOnly,
+    AlarmsOnly,
+}
+
+impl From<DndMode> for FocusAssistMode {
+    fn from(mode: DndMode) -> Self {
+        match mode {
+            DndMode::PriorityOnly => FocusAssistMode::PriorityOnly,
+            DndMode::AlarmsOnly => FocusAssistMode::AlarmsOnly,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NotificationsConfig {
+    pub dnd_mode: DndMode,
+}
@@ -3,9 +3,19 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use seelen_core::handlers::SeelenEvent;
 use tauri::Emitter;
 
-use crate::{error_handler::Result, log_error, seelen::get_app_handle, trace_lock};
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::focus_assist::{application::FocusAssist, domain::FocusAssistMode},
+    seelen::get_app_handle,
+    state::application::FULL_STATE,
+    trace_lock,
+};
 
-use super::application::{AppNotification, NOTIFICATION_MANAGER};
+use super::{
+    application::{AppNotification, NOTIFICATION_MANAGER},
+    domain::DndMode,
+};
 
 fn emit_notifications(notifications: &Vec<AppNotification>) {
     get_app_handle()
@@ -45,3 +55,21 @@ pub fn notifications_close(id: u32) -> Result<()> {
 pub fn notifications_close_all() -> Result<()> {
     trace_lock!(NOTIFICATION_MANAGER).clear_notifications()
 }
+
+/// Do not disturb is backed by the native Focus Assist so both are always in sync.
+#[tauri::command(async)]
+pub fn notifications_get_dnd() -> Result<bool> {
+    Ok(FocusAssist::get()? != FocusAssistMode::Off)
+}
+
+/// `mode` overrides the Focus Assist mode configured to be used while enabled.
+#[tauri::command(async)]
+pub fn notifications_set_dnd(enabled: bool, mode: Option<DndMode>) -> Result<()> {
+    let mode = match enabled {
+        true => mode
+            .unwrap_or_else(|| FULL_STATE.load().extended_settings().notifications.dnd_mode)
+            .into(),
+        false => FocusAssistMode::Off,
+    };
+    FocusAssist::set(mode)
+}
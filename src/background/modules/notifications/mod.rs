@@ -1,2 +1,3 @@
 mod application;
+pub mod domain;
 pub mod infrastructure;
@@ -0,0 +1,180 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use tauri::Emitter;
+use windows::{
+    core::{Interface, HSTRING},
+    Devices::{
+        Enumeration::{DeviceInformation, DeviceInformationKind},
+        HumanInterfaceDevice::{HidDevice, HidReportType},
+    },
+    Foundation::{Collections::IIterable, IReference},
+    Storage::FileAccessMode,
+};
+
+use crate::{error_handler::Result, log_error, seelen::get_app_handle, utils::spawn_named_thread};
+
+use super::domain::{PeripheralBattery, PeripheralKind};
+
+/// DEVPKEY_Bluetooth_Battery, set by the bluetooth stack for devices reporting battery (HFP/BLE)
+const BLUETOOTH_BATTERY_KEY: &str = "{104EA319-6EE2-4701-BD47-8DDBF425BBE5} 2";
+const PRESENT_DEVICES_FILTER: &str =
+    "System.Devices.Present:=System.StructuredQueryType.Boolean#True";
+
+/// HID Generic Device Controls page, Battery Strength usage
+const HID_USAGE_PAGE_GENERIC_DEVICE: u16 = 0x06;
+const HID_USAGE_BATTERY_STRENGTH: u16 = 0x20;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// smaller changes are not worth to notify
+const SIGNIFICANT_CHANGE: u8 = 5;
+
+static POLLING: AtomicBool = AtomicBool::new(false);
+
+pub struct PeripheralBatteries;
+impl PeripheralBatteries {
+    fn bluetooth() -> Result<Vec<PeripheralBattery>> {
+        let properties =
+            IIterable::<HSTRING>::try_from(vec![HSTRING::from(BLUETOOTH_BATTERY_KEY)])?;
+        let collection = DeviceInformation::FindAllAsyncWithKindAqsFilterAndAdditionalProperties(
+            &HSTRING::from(PRESENT_DEVICES_FILTER),
+            &properties,
+            DeviceInformationKind::Device,
+        )?
+        .get()?;
+
+        let key = HSTRING::from(BLUETOOTH_BATTERY_KEY);
+        let mut batteries = Vec::new();
+        for info in collection {
+            let props = info.Properties()?;
+            // devices without battery doesn't have the property
+            let Ok(level) = props
+                .Lookup(&key)
+                .and_then(|value| value.cast::<IReference<u8>>()?.Value())
+            else {
+                continue;
+            };
+            batteries.push(PeripheralBattery {
+                id: info.Id()?.to_string_lossy(),
+                name: info.Name()?.to_string_lossy(),
+                percentage: level.min(100),
+                kind: PeripheralKind::Bluetooth,
+            });
+        }
+        Ok(batteries)
+    }
+
+    /// reads the battery strength control of a HID collection, scaled to its logical range
+    fn read_hid_level(device: &HidDevice) -> Result<Option<u8>> {
+        for report_type in [HidReportType::Feature, HidReportType::Input] {
+            let descriptions = device.GetNumericControlDescriptions(
+                report_type,
+                HID_USAGE_PAGE_GENERIC_DEVICE,
+                HID_USAGE_BATTERY_STRENGTH,
+            )?;
+            for description in descriptions {
+                let report_id = description.ReportId()?;
+                let report = match report_type {
+                    HidReportType::Feature => device.GetFeatureReportByIdAsync(report_id)?.get()?,
+                    _ => device.GetInputReportByIdAsync(report_id)?.get()?,
+                };
+                let value = report
+                    .GetNumericControlByDescription(&description)?
+                    .Value()?;
+
+                let min = description.LogicalMinimum()? as i64;
+                let max = description.LogicalMaximum()? as i64;
+                if max <= min {
+                    continue;
+                }
+                let percentage = ((value - min) * 100 / (max - min)).clamp(0, 100);
+                return Ok(Some(percentage as u8));
+            }
+        }
+        Ok(None)
+    }
+
+    fn hid() -> Result<Vec<PeripheralBattery>> {
+        let selector = HidDevice::GetDeviceSelector(
+            HID_USAGE_PAGE_GENERIC_DEVICE,
+            HID_USAGE_BATTERY_STRENGTH,
+        )?;
+        let collection = DeviceInformation::FindAllAsyncAqsFilter(&selector)?.get()?;
+
+        let mut batteries = Vec::new();
+        for info in collection {
+            let id = info.Id()?;
+            // access can be denied or the device can be in use, those are skipped
+            let Ok(device) =
+                HidDevice::FromIdAsync(&id, FileAccessMode::Read).and_then(|op| op.get())
+            else {
+                continue;
+            };
+            let level = Self::read_hid_level(&device);
+            log_error!(device.Close());
+            if let Ok(Some(percentage)) = level {
+                batteries.push(PeripheralBattery {
+                    id: id.to_string_lossy(),
+                    name: info.Name()?.to_string_lossy(),
+                    percentage,
+                    kind: PeripheralKind::Hid,
+                });
+            }
+        }
+        Ok(batteries)
+    }
+
+    pub fn get_all() -> Result<Vec<PeripheralBattery>> {
+        let mut batteries = Self::bluetooth()?;
+        match Self::hid() {
+            Ok(hid) => batteries.extend(hid),
+            Err(err) => log::warn!("Failed to read HID batteries: {:?}", err),
+        }
+        Ok(batteries)
+    }
+
+    fn changed_significantly(old: &HashMap<String, u8>, new: &[PeripheralBattery]) -> bool {
+        old.len() != new.len()
+            || new.iter().any(|battery| match old.get(&battery.id) {
+                Some(level) => level.abs_diff(battery.percentage) >= SIGNIFICANT_CHANGE,
+                None => true,
+            })
+    }
+
+    /// Polls the levels and emits `peripheral-batteries-changed` when a device is added/removed
+    /// or its level changes significantly. There is no system event for battery changes.
+    pub fn start_polling() -> Result<()> {
+        if POLLING.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        spawn_named_thread("Peripheral Batteries", || {
+            let mut last: Option<HashMap<String, u8>> = None;
+            loop {
+                match Self::get_all() {
+                    Ok(batteries) => {
+                        if last
+                            .as_ref()
+                            .map_or(true, |old| Self::changed_significantly(old, &batteries))
+                        {
+                            log_error!(
+                                get_app_handle().emit("peripheral-batteries-changed", &batteries)
+                            );
+                            last = Some(
+                                batteries
+                                    .iter()
+                                    .map(|b| (b.id.clone(), b.percentage))
+                                    .collect(),
+                            );
+                        }
+                    }
+                    Err(err) => log::error!("Failed to read peripheral batteries: {:?}", err),
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        })?;
+        Ok(())
+    }
+}
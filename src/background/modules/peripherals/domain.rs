@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PeripheralKind {
+    Bluetooth,
+    Hid,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeripheralBattery {
+    pub id: String,
+    pub name: String,
+    /// 0-100
+    pub percentage: u8,
+    pub kind: PeripheralKind,
+}
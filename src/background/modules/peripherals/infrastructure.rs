@@ -0,0 +1,12 @@
+use crate::{error_handler::Result, log_error};
+
+use super::{application::PeripheralBatteries, domain::PeripheralBattery};
+
+pub fn register_peripheral_battery_events() {
+    log_error!(PeripheralBatteries::start_polling());
+}
+
+#[tauri::command(async)]
+pub fn get_peripheral_batteries() -> Result<Vec<PeripheralBattery>> {
+    PeripheralBatteries::get_all()
+}
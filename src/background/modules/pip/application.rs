@@ -0,0 +1,228 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        mpsc,
+    },
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::rect::Rect;
+use windows::{
+    core::PCWSTR,
+    Win32::{
+        Foundation::{BOOL, HWND, LPARAM, LRESULT, RECT, WPARAM},
+        Graphics::{
+            Dwm::{
+                DwmRegisterThumbnail, DwmUnregisterThumbnail, DwmUpdateThumbnailProperties,
+                DWM_THUMBNAIL_PROPERTIES, DWM_TNP_OPACITY, DWM_TNP_RECTDESTINATION,
+                DWM_TNP_RECTSOURCE, DWM_TNP_VISIBLE,
+            },
+            Gdi::{GetStockObject, BLACK_BRUSH, HBRUSH},
+        },
+        UI::WindowsAndMessaging::{
+            CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, PostMessageW,
+            PostQuitMessage, RegisterClassW, TranslateMessage, HWND_TOPMOST, MSG, SWP_NOACTIVATE,
+            WM_CLOSE, WM_DESTROY, WNDCLASSW, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TOPMOST,
+            WS_POPUP, WS_VISIBLE,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error, trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{window::Window, WindowsApi},
+    winevent::WinEvent,
+};
+
+struct PipClone {
+    source: isize,
+    overlay: isize,
+    thumbnail: isize,
+    /// region of the source window to be shown, relative to the source window
+    region: Option<RECT>,
+}
+
+lazy_static! {
+    static ref CLONES: Mutex<HashMap<u32, PipClone>> = Mutex::new(HashMap::new());
+}
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+fn to_win_rect(rect: &Rect) -> RECT {
+    RECT {
+        left: rect.left,
+        top: rect.top,
+        right: rect.right,
+        bottom: rect.bottom,
+    }
+}
+
+pub struct PictureInPicture;
+impl PictureInPicture {
+    pub const TITLE: &'static str = "Seelen PiP";
+    const CLASS: &'static str = "SeelenPiP";
+
+    unsafe extern "system" fn window_proc(
+        window: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        if message == WM_DESTROY {
+            PostQuitMessage(0);
+            return LRESULT(0);
+        }
+        DefWindowProcW(window, message, wparam, lparam)
+    }
+
+    /// Creates the overlay and runs its message loop until the overlay is closed
+    unsafe fn run_overlay(rect: RECT, on_created: mpsc::Sender<Result<isize>>) -> Result<()> {
+        let wide_name: Vec<u16> = Self::TITLE.encode_utf16().chain(Some(0)).collect();
+        let wide_class: Vec<u16> = Self::CLASS.encode_utf16().chain(Some(0)).collect();
+
+        let h_module = WindowsApi::module_handle_w()?;
+        let wnd_class = WNDCLASSW {
+            lpfnWndProc: Some(Self::window_proc),
+            hInstance: h_module.into(),
+            lpszClassName: PCWSTR(wide_class.as_ptr()),
+            hbrBackground: HBRUSH(GetStockObject(BLACK_BRUSH).0),
+            ..Default::default()
+        };
+        // fails if already registered by a previous clone
+        RegisterClassW(&wnd_class);
+
+        let hwnd = CreateWindowExW(
+            WS_EX_TOPMOST | WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE,
+            PCWSTR(wide_class.as_ptr()),
+            PCWSTR(wide_name.as_ptr()),
+            WS_POPUP | WS_VISIBLE,
+            rect.left,
+            rect.top,
+            rect.right - rect.left,
+            rect.bottom - rect.top,
+            None,
+            None,
+            h_module,
+            None,
+        );
+
+        let hwnd = match hwnd {
+            Ok(hwnd) => hwnd,
+            Err(err) => {
+                let _ = on_created.send(Err(err.into()));
+                return Ok(());
+            }
+        };
+        let _ = on_created.send(Ok(hwnd.0 as isize));
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        Ok(())
+    }
+
+    fn update_thumbnail(clone: &PipClone, width: i32, height: i32) -> Result<()> {
+        let mut properties = DWM_THUMBNAIL_PROPERTIES {
+            dwFlags: DWM_TNP_RECTDESTINATION | DWM_TNP_VISIBLE | DWM_TNP_OPACITY,
+            rcDestination: RECT {
+                left: 0,
+                top: 0,
+                right: width,
+                bottom: height,
+            },
+            opacity: 255,
+            fVisible: BOOL::from(true),
+            ..Default::default()
+        };
+        if let Some(region) = clone.region {
+            properties.dwFlags |= DWM_TNP_RECTSOURCE;
+            properties.rcSource = region;
+        }
+        unsafe { DwmUpdateThumbnailProperties(clone.thumbnail, &properties)? };
+        Ok(())
+    }
+
+    /// Shows a live clone of `hwnd` in a topmost overlay placed at `rect`.\
+    /// `region` is relative to the source window, if not set the whole window is cloned.
+    pub fn clone_window(hwnd: HWND, rect: Rect, region: Option<Rect>) -> Result<u32> {
+        if !Window::from(hwnd).is_window() {
+            return Err("Invalid source window".into());
+        }
+
+        let rect = to_win_rect(&rect);
+        let (tx, rx) = mpsc::channel();
+        spawn_named_thread("PiP Overlay", move || unsafe {
+            log_error!(Self::run_overlay(rect, tx));
+        })?;
+        let overlay = rx
+            .recv_timeout(Duration::from_secs(2))
+            .map_err(|_| "PiP overlay was not created in time")??;
+
+        let thumbnail = match unsafe { DwmRegisterThumbnail(HWND(overlay as _), hwnd) } {
+            Ok(thumbnail) => thumbnail,
+            Err(err) => {
+                unsafe { log_error!(PostMessageW(HWND(overlay as _), WM_CLOSE, None, None)) };
+                return Err(err.into());
+            }
+        };
+
+        let clone = PipClone {
+            source: hwnd.0 as isize,
+            overlay,
+            thumbnail,
+            region: region.as_ref().map(to_win_rect),
+        };
+        Self::update_thumbnail(&clone, rect.right - rect.left, rect.bottom - rect.top)?;
+
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        trace_lock!(CLONES).insert(id, clone);
+        Ok(id)
+    }
+
+    pub fn move_clone(id: u32, rect: Rect) -> Result<()> {
+        let clones = trace_lock!(CLONES);
+        let clone = clones.get(&id).ok_or("PiP clone not found")?;
+        let rect = to_win_rect(&rect);
+        WindowsApi::set_position(
+            HWND(clone.overlay as _),
+            Some(HWND_TOPMOST),
+            &rect,
+            SWP_NOACTIVATE,
+        )?;
+        Self::update_thumbnail(clone, rect.right - rect.left, rect.bottom - rect.top)
+    }
+
+    pub fn stop(id: u32) -> Result<()> {
+        let clone = trace_lock!(CLONES)
+            .remove(&id)
+            .ok_or("PiP clone not found")?;
+        unsafe {
+            log_error!(DwmUnregisterThumbnail(clone.thumbnail));
+            PostMessageW(HWND(clone.overlay as _), WM_CLOSE, None, None)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the clones of a closed window
+    pub fn on_win_event(event: WinEvent, window: &Window) {
+        if event != WinEvent::ObjectDestroy {
+            return;
+        }
+        let source = window.hwnd().0 as isize;
+        let ids: Vec<u32> = trace_lock!(CLONES)
+            .iter()
+            .filter(|(_, clone)| clone.source == source)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in ids {
+            log_error!(Self::stop(id));
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use seelen_core::rect::Rect;
+use windows::Win32::Foundation::HWND;
+
+use crate::error_handler::Result;
+
+use super::application::PictureInPicture;
+
+/// Returns the id of the clone
+#[tauri::command(async)]
+pub fn pip_clone_window(hwnd: isize, rect: Rect, region: Option<Rect>) -> Result<u32> {
+    PictureInPicture::clone_window(HWND(hwnd as _), rect, region)
+}
+
+#[tauri::command(async)]
+pub fn pip_move(clone_id: u32, rect: Rect) -> Result<()> {
+    PictureInPicture::move_clone(clone_id, rect)
+}
+
+#[tauri::command(async)]
+pub fn pip_stop(clone_id: u32) -> Result<()> {
+    PictureInPicture::stop(clone_id)
+}
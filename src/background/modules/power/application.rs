@@ -0,0 +1,151 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use tauri::Emitter;
+use windows::Win32::System::SystemInformation::GetTickCount64;
+
+use crate::{
+    error_handler::Result, log_error, seelen::get_app_handle, trace_lock,
+    utils::constants::SEELEN_COMMON, utils::spawn_named_thread,
+};
+
+use super::{
+    domain::{PowerAction, PowerActionCountdown, ScheduledPowerAction},
+    infrastructure::{hibernate, lock_workstation, log_out, restart, shutdown, suspend},
+};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+static THREAD_STARTED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref SCHEDULED: Mutex<Option<ScheduledPowerAction>> = Mutex::new(None);
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+pub struct PowerScheduler;
+impl PowerScheduler {
+    fn read_schedule() -> Result<Option<ScheduledPowerAction>> {
+        let path = SEELEN_COMMON.power_schedule_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(serde_yaml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    fn write_schedule(schedule: Option<&ScheduledPowerAction>) -> Result<()> {
+        let path = SEELEN_COMMON.power_schedule_path();
+        match schedule {
+            Some(schedule) => std::fs::write(path, serde_yaml::to_string(schedule)?)?,
+            None if path.exists() => std::fs::remove_file(path)?,
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Re-arms the persisted schedule with the remaining time. Schedules that expired while
+    /// Seelen UI was not running or created before the last boot (the user already turned
+    /// off the system) are discarded.
+    pub fn init() -> Result<()> {
+        let Some(schedule) = Self::read_schedule()? else {
+            return Ok(());
+        };
+        let now = now_secs();
+        let boot_time = now.saturating_sub(unsafe { GetTickCount64() } / 1000);
+        if schedule.execute_at <= now || schedule.created_at < boot_time {
+            log::info!("Discarding outdated power schedule: {:?}", schedule);
+            return Self::write_schedule(None);
+        }
+        *trace_lock!(SCHEDULED) = Some(schedule);
+        Self::ensure_thread()
+    }
+
+    pub fn schedule(action: PowerAction, delay_secs: u64) -> Result<()> {
+        let now = now_secs();
+        let schedule = ScheduledPowerAction {
+            action,
+            created_at: now,
+            execute_at: now.saturating_add(delay_secs),
+        };
+        Self::write_schedule(Some(&schedule))?;
+        *trace_lock!(SCHEDULED) = Some(schedule);
+        Self::ensure_thread()?;
+        Self::tick();
+        Ok(())
+    }
+
+    pub fn cancel() -> Result<()> {
+        if trace_lock!(SCHEDULED).take().is_some() {
+            get_app_handle().emit("power-action-cancelled", ())?;
+        }
+        Self::write_schedule(None)
+    }
+
+    /// should be called when an action is performed by other means than the scheduler
+    pub fn on_action_performed(action: PowerAction) {
+        let is_scheduled = trace_lock!(SCHEDULED)
+            .as_ref()
+            .is_some_and(|schedule| schedule.action == action);
+        if is_scheduled {
+            log_error!(Self::cancel());
+        }
+    }
+
+    fn tick() {
+        let mut guard = trace_lock!(SCHEDULED);
+        let Some(schedule) = guard.as_ref() else {
+            return;
+        };
+
+        let remaining_secs = schedule.execute_at.saturating_sub(now_secs());
+        log_error!(get_app_handle().emit(
+            "power-action-countdown",
+            PowerActionCountdown {
+                action: schedule.action,
+                remaining_secs,
+            },
+        ));
+
+        if remaining_secs == 0 {
+            let action = schedule.action;
+            guard.take();
+            drop(guard);
+            log_error!(Self::write_schedule(None));
+            log_error!(Self::perform(action));
+        }
+    }
+
+    fn perform(action: PowerAction) -> Result<()> {
+        log::info!("Performing scheduled power action: {:?}", action);
+        match action {
+            PowerAction::Shutdown => shutdown()?,
+            PowerAction::Restart => restart()?,
+            PowerAction::Suspend => suspend(),
+            PowerAction::Hibernate => hibernate()?,
+            PowerAction::LogOut => log_out(),
+            PowerAction::Lock => lock_workstation()?,
+        }
+        Ok(())
+    }
+
+    fn ensure_thread() -> Result<()> {
+        if THREAD_STARTED.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        spawn_named_thread("Power Action Scheduler", || loop {
+            Self::tick();
+            std::thread::sleep(TICK_INTERVAL);
+        })?;
+        Ok(())
+    }
+}
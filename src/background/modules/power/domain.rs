@@ -1,4 +1,5 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use windows::Win32::System::Power::SYSTEM_POWER_STATUS;
 
 use crate::error_handler::{AppError, Result};
@@ -28,6 +29,128 @@ impl From<SYSTEM_POWER_STATUS> for PowerStatus {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PowerAction {
+    Shutdown,
+    Restart,
+    Suspend,
+    Hibernate,
+    LogOut,
+    Lock,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledPowerAction {
+    pub action: PowerAction,
+    /// unix timestamp in seconds
+    pub created_at: u64,
+    /// unix timestamp in seconds
+    pub execute_at: u64,
+}
+
+/// Battery saver threshold of a power plan before it was forced to 100% to turn battery saver on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatterySaverOverride {
+    pub scheme: String,
+    pub threshold: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerActionCountdown {
+    pub action: PowerAction,
+    pub remaining_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BatteryAlertSettings {
+    /// percent at which the `low-battery` event is emitted while discharging
+    pub low_battery_threshold: u8,
+    /// enable battery saver when the threshold is crossed
+    pub auto_battery_saver: bool,
+}
+
+impl Default for BatteryAlertSettings {
+    fn default() -> Self {
+        Self {
+            low_battery_threshold: 20,
+            auto_battery_saver: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserSession {
+    pub id: u32,
+    /// empty for sessions without a logged user (services, listeners)
+    pub user_name: String,
+    pub station: String,
+    pub state: SessionState,
+    /// the session where Seelen UI is running
+    pub current: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SessionState {
+    Active,
+    Connected,
+    Disconnected,
+    Idle,
+    Other,
+}
+
+impl SessionState {
+    /// from WTS_CONNECTSTATE_CLASS
+    pub fn from_raw(raw: i32) -> Self {
+        match raw {
+            0 => SessionState::Active,
+            1 => SessionState::Connected,
+            4 => SessionState::Disconnected,
+            5 => SessionState::Idle,
+            _ => SessionState::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerPlan {
+    pub guid: String,
+    pub name: String,
+    pub active: bool,
+}
+
+/// state of the power mode slider, including the modes forced by the system (battery saver, game mode, etc)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PowerMode {
+    BatterySaver,
+    BetterBattery,
+    Balanced,
+    HighPerformance,
+    MaxPerformance,
+    GameMode,
+    MixedReality,
+}
+
+impl PowerMode {
+    pub fn from_raw(raw: i32) -> Option<Self> {
+        match raw {
+            0 => Some(PowerMode::BatterySaver),
+            1 => Some(PowerMode::BetterBattery),
+            2 => Some(PowerMode::Balanced),
+            3 => Some(PowerMode::HighPerformance),
+            4 => Some(PowerMode::MaxPerformance),
+            5 => Some(PowerMode::GameMode),
+            6 => Some(PowerMode::MixedReality),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Battery {
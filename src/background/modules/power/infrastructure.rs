@@ -1,28 +1,70 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::{
+    ffi::c_void,
+    sync::atomic::{AtomicBool, AtomicI32, AtomicU8, Ordering},
+};
 
 use seelen_core::handlers::SeelenEvent;
 use tauri::Emitter;
 use windows::{
-    core::PCWSTR,
+    core::{GUID, PCWSTR, PWSTR},
     Win32::{
-        Foundation::{HWND, LPARAM, LRESULT, WPARAM},
-        System::Shutdown::{EWX_LOGOFF, EWX_REBOOT, EWX_SHUTDOWN, SHTDN_REASON_NONE},
+        Foundation::{HANDLE, HWND, LPARAM, LRESULT, WPARAM},
+        System::{
+            Power::{
+                PowerRegisterForEffectivePowerModeNotifications, EFFECTIVE_POWER_MODE,
+                EFFECTIVE_POWER_MODE_V2,
+            },
+            RemoteDesktop::{
+                WTSEnumerateSessionsW, WTSFreeMemory, WTSLogoffSession,
+                WTSQuerySessionInformationW, WTSUserName, WTS_CURRENT_SERVER_HANDLE,
+                WTS_SESSION_INFOW,
+            },
+            Shutdown::{LockWorkStation, EWX_LOGOFF, EWX_REBOOT, EWX_SHUTDOWN, SHTDN_REASON_NONE},
+        },
         UI::WindowsAndMessaging::{
             CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, PostQuitMessage,
-            RegisterClassW, TranslateMessage, MSG, PBT_APMPOWERSTATUSCHANGE, WINDOW_EX_STYLE,
-            WINDOW_STYLE, WM_DESTROY, WM_POWERBROADCAST, WNDCLASSW,
+            RegisterClassW, RegisterPowerSettingNotification, TranslateMessage,
+            DEVICE_NOTIFY_WINDOW_HANDLE, MSG, PBT_APMPOWERSTATUSCHANGE, PBT_APMSUSPEND,
+            PBT_POWERSETTINGCHANGE, WINDOW_EX_STYLE, WINDOW_STYLE, WM_DESTROY, WM_ENDSESSION,
+            WM_POWERBROADCAST, WNDCLASSW,
         },
     },
 };
 
 use crate::{
-    error_handler::Result, log_error, modules::power::domain::Battery, seelen::get_app_handle,
-    utils::spawn_named_thread, windows_api::WindowsApi,
+    error_handler::Result,
+    log_error,
+    modules::{power::domain::Battery, theme_variables::application::ThemeVariables},
+    seelen::get_app_handle,
+    state::{application::FULL_STATE, infrastructure::update_extended_settings},
+    utils::spawn_named_thread,
+    windows_api::WindowsApi,
 };
 
-use super::domain::PowerStatus;
+use super::{
+    application::PowerScheduler,
+    domain::{
+        BatteryAlertSettings, BatterySaverOverride, PowerAction, PowerMode, PowerPlan, PowerStatus,
+        SessionState, UserSession,
+    },
+};
 
 static REGISTERED: AtomicBool = AtomicBool::new(false);
+/// last effective power mode reported by the system, -1 if unknown
+static POWER_MODE: AtomicI32 = AtomicI32::new(-1);
+
+/// GUID_ACTIVE_POWERSCHEME, notified when the active plan changes (by the user or by the system on AC/DC changes)
+const GUID_ACTIVE_POWERSCHEME: GUID = GUID::from_u128(0x31f9f286_5084_42fe_b720_2b0264993763);
+/// GUID_ENERGY_SAVER_SUBGROUP
+const GUID_ENERGY_SAVER_SUBGROUP: GUID = GUID::from_u128(0xde830923_a562_41af_a086_e3a2c6bad2da);
+/// GUID_ENERGY_SAVER_BATTERY_THRESHOLD, percent of battery at which battery saver is turned on
+const GUID_ENERGY_SAVER_BATTERY_THRESHOLD: GUID =
+    GUID::from_u128(0xe69653ca_cf7f_4f05_aa73_cb833fa90ad4);
+/// default threshold used by Windows
+const DEFAULT_BATTERY_SAVER_THRESHOLD: u32 = 20;
+
+/// last battery percent seen, 255 if unknown (same as SYSTEM_POWER_STATUS)
+static LAST_BATTERY_PERCENT: AtomicU8 = AtomicU8::new(255);
 
 pub struct PowerManager;
 impl PowerManager {
@@ -34,11 +76,25 @@ impl PowerManager {
     ) -> LRESULT {
         match msg {
             WM_POWERBROADCAST => {
-                if PBT_APMPOWERSTATUSCHANGE == w_param.0 as u32 {
-                    log_error!(PowerManager::emit_system_power_info());
+                match w_param.0 as u32 {
+                    PBT_APMPOWERSTATUSCHANGE => log_error!(PowerManager::emit_system_power_info()),
+                    PBT_POWERSETTINGCHANGE => log_error!(PowerManager::emit_power_plans()),
+                    // the system was suspended by other means than the scheduler
+                    PBT_APMSUSPEND => {
+                        PowerScheduler::on_action_performed(PowerAction::Suspend);
+                        PowerScheduler::on_action_performed(PowerAction::Hibernate);
+                    }
+                    _ => {}
                 }
                 LRESULT(1)
             }
+            // the session is ending (shutdown, restart or log out) so any scheduled action is done
+            WM_ENDSESSION => {
+                if w_param.0 != 0 {
+                    log_error!(PowerScheduler::cancel());
+                }
+                LRESULT(0)
+            }
             WM_DESTROY => {
                 PostQuitMessage(0);
                 LRESULT(0)
@@ -90,6 +146,22 @@ impl PowerManager {
             )?
         };
 
+        unsafe {
+            RegisterPowerSettingNotification(
+                HANDLE(hwnd.0),
+                &GUID_ACTIVE_POWERSCHEME,
+                DEVICE_NOTIFY_WINDOW_HANDLE,
+            )?;
+            // the callback is called once on registration with the current mode
+            let mut registration = std::ptr::null_mut();
+            PowerRegisterForEffectivePowerModeNotifications(
+                EFFECTIVE_POWER_MODE_V2,
+                Some(Self::on_power_mode_change),
+                None,
+                &mut registration,
+            )?;
+        }
+
         let addr = hwnd.0 as isize;
         spawn_named_thread("Power Manager Message Loop", move || unsafe {
             let hwnd = HWND(addr as _);
@@ -109,10 +181,224 @@ impl PowerManager {
         Ok(())
     }
 
+    unsafe extern "system" fn on_power_mode_change(mode: EFFECTIVE_POWER_MODE, _: *const c_void) {
+        POWER_MODE.store(mode.0, Ordering::Release);
+        if let Some(mode) = PowerMode::from_raw(mode.0) {
+            log_error!(get_app_handle().emit("power-mode-changed", mode));
+        }
+    }
+
+    pub fn power_mode() -> Option<PowerMode> {
+        PowerMode::from_raw(POWER_MODE.load(Ordering::Acquire))
+    }
+
+    pub fn power_plans() -> Result<Vec<PowerPlan>> {
+        let active = WindowsApi::power_active_scheme()?;
+        let mut plans = Vec::new();
+        for scheme in WindowsApi::power_enumerate_schemes()? {
+            plans.push(PowerPlan {
+                guid: uuid::Uuid::from_u128(scheme.to_u128()).to_string(),
+                name: WindowsApi::power_scheme_name(&scheme)?,
+                active: scheme == active,
+            });
+        }
+        Ok(plans)
+    }
+
+    pub fn set_active_power_plan(guid: &str) -> Result<()> {
+        let scheme =
+            uuid::Uuid::parse_str(guid).map_err(|_| format!("Invalid power plan: {guid}"))?;
+        WindowsApi::power_set_active_scheme(&GUID::from_u128(scheme.as_u128()))
+    }
+
+    pub fn emit_power_plans() -> Result<()> {
+        get_app_handle().emit("power-plans-changed", Self::power_plans()?)?;
+        Ok(())
+    }
+
+    pub fn battery_alert_settings() -> BatteryAlertSettings {
+        FULL_STATE.load().extended_settings().battery_alerts
+    }
+
+    pub fn set_battery_alert_settings(settings: BatteryAlertSettings) -> Result<()> {
+        if settings.low_battery_threshold > 100 {
+            return Err("Low battery threshold should be a percent".into());
+        }
+        update_extended_settings(|extended| extended.battery_alerts = settings)
+    }
+
+    pub fn is_battery_saver_on() -> Result<bool> {
+        Ok(WindowsApi::get_system_power_status()?.SystemStatusFlag == 1)
+    }
+
+    /// There is no public API to toggle battery saver, so the battery saver threshold of the
+    /// active plan is raised to 100% to force it on and restored to the previous value to turn it off.
+    /// Windows only applies battery saver while running on battery.
+    /// The original threshold is persisted before being overridden, so it can be restored
+    /// on startup if Seelen UI was closed or crashed while battery saver was forced on.
+    pub fn set_battery_saver(enabled: bool) -> Result<()> {
+        if !enabled && Self::restore_battery_saver_threshold()? {
+            return Ok(());
+        }
+        let scheme = WindowsApi::power_active_scheme()?;
+        let current = WindowsApi::power_read_dc_value(
+            &scheme,
+            &GUID_ENERGY_SAVER_SUBGROUP,
+            &GUID_ENERGY_SAVER_BATTERY_THRESHOLD,
+        )?;
+        if !enabled {
+            // nothing to restore, forced on by other app or by the user
+            if current >= 100 {
+                Self::write_battery_saver_threshold(&scheme, DEFAULT_BATTERY_SAVER_THRESHOLD)?;
+            }
+            return Ok(());
+        }
+        // keep the first saved value if battery saver was already forced on
+        if current < 100 && Self::read_battery_saver_override()?.is_none() {
+            Self::write_battery_saver_override(Some(&BatterySaverOverride {
+                scheme: uuid::Uuid::from_u128(scheme.to_u128()).to_string(),
+                threshold: current,
+            }))?;
+        }
+        Self::write_battery_saver_threshold(&scheme, 100)
+    }
+
+    /// Restores the threshold overridden by `set_battery_saver`, should be called on startup.
+    /// Returns false if there was nothing to restore.
+    pub fn restore_battery_saver_threshold() -> Result<bool> {
+        let Some(saved) = Self::read_battery_saver_override()? else {
+            return Ok(false);
+        };
+        let scheme = uuid::Uuid::parse_str(&saved.scheme)
+            .map_err(|_| format!("Invalid power plan: {}", saved.scheme))?;
+        Self::write_battery_saver_threshold(&GUID::from_u128(scheme.as_u128()), saved.threshold)?;
+        Self::write_battery_saver_override(None)?;
+        Ok(true)
+    }
+
+    fn write_battery_saver_threshold(scheme: &GUID, threshold: u32) -> Result<()> {
+        WindowsApi::power_write_dc_value(
+            scheme,
+            &GUID_ENERGY_SAVER_SUBGROUP,
+            &GUID_ENERGY_SAVER_BATTERY_THRESHOLD,
+            threshold,
+        )?;
+        // values written to a plan are only applied once the plan is (re)activated
+        if WindowsApi::power_active_scheme()? == *scheme {
+            WindowsApi::power_set_active_scheme(scheme)?;
+        }
+        Ok(())
+    }
+
+    fn read_battery_saver_override() -> Result<Option<BatterySaverOverride>> {
+        let path = SEELEN_COMMON.battery_saver_override_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(serde_yaml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    fn write_battery_saver_override(value: Option<&BatterySaverOverride>) -> Result<()> {
+        let path = SEELEN_COMMON.battery_saver_override_path();
+        match value {
+            Some(value) => std::fs::write(path, serde_yaml::to_string(value)?)?,
+            None if path.exists() => std::fs::remove_file(path)?,
+            None => {}
+        }
+        Ok(())
+    }
+
+    fn check_low_battery(status: &PowerStatus) -> Result<()> {
+        let percent = status.battery_life_percent;
+        let last = LAST_BATTERY_PERCENT.swap(percent, Ordering::AcqRel);
+        // 255 = unknown status, 0 = offline (discharging)
+        if percent == 255 || last == 255 || status.ac_line_status != 0 {
+            return Ok(());
+        }
+
+        let settings = Self::battery_alert_settings();
+        if last > settings.low_battery_threshold && percent <= settings.low_battery_threshold {
+            get_app_handle().emit("low-battery", percent)?;
+            if settings.auto_battery_saver && !Self::is_battery_saver_on()? {
+                Self::set_battery_saver(true)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn session_user_name(session_id: u32) -> Result<String> {
+        let mut buffer = PWSTR::null();
+        let mut size = 0;
+        unsafe {
+            WTSQuerySessionInformationW(
+                WTS_CURRENT_SERVER_HANDLE,
+                session_id,
+                WTSUserName,
+                &mut buffer,
+                &mut size,
+            )?;
+            let user_name = buffer.to_string();
+            WTSFreeMemory(buffer.0 as _);
+            Ok(user_name?)
+        }
+    }
+
+    pub fn sessions() -> Result<Vec<UserSession>> {
+        let current = WindowsApi::current_session_id()?;
+        let mut info: *mut WTS_SESSION_INFOW = std::ptr::null_mut();
+        let mut count = 0;
+        unsafe { WTSEnumerateSessionsW(WTS_CURRENT_SERVER_HANDLE, 0, 1, &mut info, &mut count)? };
+
+        let mut sessions = Vec::new();
+        for session in unsafe { std::slice::from_raw_parts(info, count as usize) } {
+            sessions.push(UserSession {
+                id: session.SessionId,
+                user_name: Self::session_user_name(session.SessionId).unwrap_or_default(),
+                station: unsafe { session.pWinStationName.to_string() }.unwrap_or_default(),
+                state: SessionState::from_raw(session.State.0),
+                current: session.SessionId == current,
+            });
+        }
+        unsafe { WTSFreeMemory(info as _) };
+        Ok(sessions)
+    }
+
+    /// signing out other users requires elevation
+    pub fn log_off_session(session_id: u32) -> Result<()> {
+        if session_id != WindowsApi::current_session_id()? && !WindowsApi::is_elevated()? {
+            return Err("Administrator privileges are required to sign out other sessions".into());
+        }
+        unsafe { WTSLogoffSession(WTS_CURRENT_SERVER_HANDLE, session_id, false)? };
+        Ok(())
+    }
+
+    /// hibernation requires the S4 state and the hibernation file (`powercfg /hibernate on`)
+    pub fn can_hibernate() -> Result<bool> {
+        let capabilities = WindowsApi::get_power_capabilities()?;
+        Ok(capabilities.SystemS4.as_bool()
+            && capabilities.HiberFilePresent.as_bool()
+            && WindowsApi::is_hibernate_allowed())
+    }
+
+    pub fn hibernate() -> Result<()> {
+        PowerScheduler::on_action_performed(PowerAction::Hibernate);
+        let capabilities = WindowsApi::get_power_capabilities()?;
+        if !capabilities.SystemS4.as_bool() || !capabilities.HiberFilePresent.as_bool() {
+            return Err("Hibernation is not enabled on this system".into());
+        }
+        // supported and enabled but not allowed means that it was disabled by a policy
+        if !WindowsApi::is_hibernate_allowed() {
+            return Err("Hibernation is disabled by policy".into());
+        }
+        WindowsApi::set_suspend_state(true)
+    }
+
     pub fn emit_system_power_info() -> Result<()> {
         let handle = get_app_handle();
 
         let power_status: PowerStatus = WindowsApi::get_system_power_status()?.into();
+        log_error!(Self::check_low_battery(&power_status));
+        ThemeVariables::on_battery_change(power_status.battery_life_percent);
         handle.emit(SeelenEvent::PowerStatus, power_status)?;
 
         let mut batteries: Vec<Battery> = Vec::new();
@@ -127,24 +413,101 @@ impl PowerManager {
     }
 }
 
+#[tauri::command(async)]
+pub fn get_power_plans() -> Result<Vec<PowerPlan>> {
+    PowerManager::power_plans()
+}
+
+#[tauri::command(async)]
+pub fn set_active_power_plan(guid: String) -> Result<()> {
+    PowerManager::set_active_power_plan(&guid)
+}
+
+/// `None` if the system has not reported the power mode yet or it is not supported
+#[tauri::command(async)]
+pub fn get_power_mode() -> Option<PowerMode> {
+    PowerManager::power_mode()
+}
+
+#[tauri::command(async)]
+pub fn get_battery_saver() -> Result<bool> {
+    PowerManager::is_battery_saver_on()
+}
+
+#[tauri::command(async)]
+pub fn set_battery_saver(enabled: bool) -> Result<()> {
+    PowerManager::set_battery_saver(enabled)
+}
+
+#[tauri::command(async)]
+pub fn get_battery_alert_settings() -> BatteryAlertSettings {
+    PowerManager::battery_alert_settings()
+}
+
+#[tauri::command(async)]
+pub fn set_battery_alert_settings(settings: BatteryAlertSettings) -> Result<()> {
+    PowerManager::set_battery_alert_settings(settings)
+}
+
+#[tauri::command(async)]
+pub fn lock_workstation() -> Result<()> {
+    PowerScheduler::on_action_performed(PowerAction::Lock);
+    unsafe { LockWorkStation()? };
+    Ok(())
+}
+
+#[tauri::command(async)]
+pub fn get_sessions() -> Result<Vec<UserSession>> {
+    PowerManager::sessions()
+}
+
+#[tauri::command(async)]
+pub fn log_off_session(id: u32) -> Result<()> {
+    PowerManager::log_off_session(id)
+}
+
 #[tauri::command(async)]
 pub fn log_out() {
+    PowerScheduler::on_action_performed(PowerAction::LogOut);
     log_error!(WindowsApi::exit_windows(EWX_LOGOFF, SHTDN_REASON_NONE));
 }
 
 #[tauri::command(async)]
 pub fn suspend() {
-    log_error!(WindowsApi::set_suspend_state());
+    PowerScheduler::on_action_performed(PowerAction::Suspend);
+    log_error!(WindowsApi::set_suspend_state(false));
+}
+
+#[tauri::command(async)]
+pub fn hibernate() -> Result<()> {
+    PowerManager::hibernate()
+}
+
+#[tauri::command(async)]
+pub fn can_hibernate() -> Result<bool> {
+    PowerManager::can_hibernate()
 }
 
 #[tauri::command(async)]
 pub fn restart() -> Result<()> {
+    PowerScheduler::on_action_performed(PowerAction::Restart);
     WindowsApi::exit_windows(EWX_REBOOT, SHTDN_REASON_NONE)?;
     Ok(())
 }
 
 #[tauri::command(async)]
 pub fn shutdown() -> Result<()> {
+    PowerScheduler::on_action_performed(PowerAction::Shutdown);
     WindowsApi::exit_windows(EWX_SHUTDOWN, SHTDN_REASON_NONE)?;
     Ok(())
 }
+
+#[tauri::command(async)]
+pub fn schedule_power_action(action: PowerAction, delay_secs: u64) -> Result<()> {
+    PowerScheduler::schedule(action, delay_secs)
+}
+
+#[tauri::command(async)]
+pub fn cancel_scheduled_power_action() -> Result<()> {
+    PowerScheduler::cancel()
+}
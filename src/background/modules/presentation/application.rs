@@ -0,0 +1,72 @@
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use tauri::Emitter;
+use windows::Win32::{
+    Graphics::Gdi::HMONITOR,
+    UI::Shell::{QUNS_PRESENTATION_MODE, QUNS_RUNNING_D3D_FULL_SCREEN},
+};
+
+use crate::{
+    log_error,
+    seelen::get_app_handle,
+    trace_lock,
+    windows_api::{window::Window, WindowsApi},
+    winevent::WinEvent,
+};
+
+use super::domain::PresentationState;
+
+lazy_static! {
+    static ref LAST_STATE: Mutex<PresentationState> = Mutex::new(PresentationState::default());
+}
+
+pub struct PresentationMode;
+impl PresentationMode {
+    pub fn state() -> PresentationState {
+        let notification_state = WindowsApi::get_notification_state().ok();
+        let fullscreen = WinEvent::synthetic_fullscreened().is_some();
+        let presentation = notification_state == Some(QUNS_PRESENTATION_MODE);
+        let game = notification_state == Some(QUNS_RUNNING_D3D_FULL_SCREEN);
+        PresentationState {
+            fullscreen,
+            presentation,
+            game,
+            quiet: fullscreen || presentation || game,
+        }
+    }
+
+    /// Monitor of the app that turned quiet mode on, if any. Presentation and game
+    /// modes are not reported by monitor, so the foreground window one is used.
+    pub fn quiet_monitor() -> Option<HMONITOR> {
+        if let Some(data) = WinEvent::synthetic_fullscreened() {
+            return Some(data.monitor);
+        }
+        Self::state().quiet.then(|| {
+            Window::from(WindowsApi::get_foreground_window())
+                .monitor()
+                .handle()
+        })
+    }
+
+    /// Emits `quiet-mode` if the state changed
+    pub fn refresh() {
+        let state = Self::state();
+        let mut last = trace_lock!(LAST_STATE);
+        if *last != state {
+            *last = state;
+            log_error!(get_app_handle().emit("quiet-mode", state));
+        }
+    }
+
+    pub fn on_win_event(event: WinEvent) {
+        // there is no notification for presentation mode, so it is checked on focus changes
+        if matches!(
+            event,
+            WinEvent::SystemForeground
+                | WinEvent::SyntheticFullscreenStart(_)
+                | WinEvent::SyntheticFullscreenEnd(_)
+        ) {
+            Self::refresh();
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentationState {
+    /// the foreground window covers its whole monitor (synthetic fullscreen detection)
+    pub fullscreen: bool,
+    /// presentation settings are on, as reported by `SHQueryUserNotificationState`
+    pub presentation: bool,
+    /// a Direct3D exclusive fullscreen app is running, this is the same signal Windows uses
+    /// to enable Game Mode, there is no public API to query Game Bar itself.
+    pub game: bool,
+    /// any of the above, widgets and the window manager should quiet down
+    pub quiet: bool,
+}
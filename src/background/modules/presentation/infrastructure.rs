@@ -0,0 +1,6 @@
+use super::{application::PresentationMode, domain::PresentationState};
+
+#[tauri::command(async)]
+pub fn get_presentation_state() -> PresentationState {
+    PresentationMode::state()
+}
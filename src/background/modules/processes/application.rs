@@ -0,0 +1,105 @@
+use std::{sync::Arc, time::Duration};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use sysinfo::{ProcessRefreshKind, System, UpdateKind, MINIMUM_CPU_UPDATE_INTERVAL};
+use tauri_plugin_shell::ShellExt;
+
+use crate::{
+    error_handler::Result, log_error, seelen::get_app_handle, trace_lock,
+    utils::spawn_named_thread, windows_api::WindowsApi,
+};
+
+use super::domain::{PriorityClass, ProcessInfo};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+lazy_static! {
+    pub static ref PROCESS_SAMPLER: Arc<Mutex<ProcessSampler>> =
+        Arc::new(Mutex::new(ProcessSampler::new()));
+}
+
+/// Cpu usage is calculated as the difference between two refreshes, so we keep
+/// refreshing in background to always have a meaningful value.
+pub struct ProcessSampler {
+    system: System,
+}
+
+impl ProcessSampler {
+    fn refresh_kind() -> ProcessRefreshKind {
+        ProcessRefreshKind::new()
+            .with_cpu()
+            .with_memory()
+            .with_exe(UpdateKind::OnlyIfNotSet)
+    }
+
+    fn new() -> Self {
+        let mut system = System::new();
+        system.refresh_processes_specifics(Self::refresh_kind());
+        // second refresh needed to have the first cpu usage sample
+        std::thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+        system.refresh_processes_specifics(Self::refresh_kind());
+
+        log_error!(spawn_named_thread("Process Sampler", || loop {
+            std::thread::sleep(SAMPLE_INTERVAL);
+            trace_lock!(PROCESS_SAMPLER).refresh();
+        }));
+
+        Self { system }
+    }
+
+    fn refresh(&mut self) {
+        self.system
+            .refresh_processes_specifics(Self::refresh_kind());
+    }
+
+    /// protected processes are included with the data we could get
+    pub fn processes(&self) -> Vec<ProcessInfo> {
+        let cpu_count = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1) as f32;
+
+        self.system
+            .processes()
+            .values()
+            .map(|p| ProcessInfo {
+                pid: p.pid().as_u32(),
+                name: p.name().to_string(),
+                exe: p.exe().map(|path| path.to_path_buf()),
+                memory: p.memory(),
+                cpu: p.cpu_usage() / cpu_count,
+            })
+            .collect()
+    }
+}
+
+pub async fn kill_process(pid: u32, force: bool) -> Result<()> {
+    let pid = pid.to_string();
+    let mut args = vec!["/PID", &pid];
+    if force {
+        args.push("/F");
+    }
+
+    let output = get_app_handle()
+        .shell()
+        .command("taskkill.exe")
+        .args(args)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to kill process {}: {}", pid, stderr.trim()).into());
+    }
+    Ok(())
+}
+
+/// returns the previous priority so it can be restored later
+pub fn set_process_priority(pid: u32, priority: PriorityClass) -> Result<PriorityClass> {
+    let previous = WindowsApi::set_priority_class(pid, priority.into())?;
+    Ok(PriorityClass::try_from(previous)?)
+}
+
+pub fn set_process_efficiency_mode(pid: u32, enabled: bool) -> Result<()> {
+    WindowsApi::set_power_throttling(pid, enabled)
+}
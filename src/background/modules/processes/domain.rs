@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use windows::Win32::System::Threading::{
+    ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS, HIGH_PRIORITY_CLASS,
+    IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, PROCESS_CREATION_FLAGS, REALTIME_PRIORITY_CLASS,
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    /// None if access to the process was denied
+    pub exe: Option<PathBuf>,
+    /// bytes
+    pub memory: u64,
+    /// percentage of the total cpu usage, 0-100
+    pub cpu: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PriorityClass {
+    Idle,
+    BelowNormal,
+    Normal,
+    AboveNormal,
+    High,
+    /// without elevation Windows silently downgrades it to `High`
+    Realtime,
+}
+
+impl From<PriorityClass> for PROCESS_CREATION_FLAGS {
+    fn from(value: PriorityClass) -> Self {
+        match value {
+            PriorityClass::Idle => IDLE_PRIORITY_CLASS,
+            PriorityClass::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+            PriorityClass::Normal => NORMAL_PRIORITY_CLASS,
+            PriorityClass::AboveNormal => ABOVE_NORMAL_PRIORITY_CLASS,
+            PriorityClass::High => HIGH_PRIORITY_CLASS,
+            PriorityClass::Realtime => REALTIME_PRIORITY_CLASS,
+        }
+    }
+}
+
+impl TryFrom<PROCESS_CREATION_FLAGS> for PriorityClass {
+    type Error = String;
+
+    fn try_from(value: PROCESS_CREATION_FLAGS) -> Result<Self, Self::Error> {
+        match value {
+            IDLE_PRIORITY_CLASS => Ok(PriorityClass::Idle),
+            BELOW_NORMAL_PRIORITY_CLASS => Ok(PriorityClass::BelowNormal),
+            NORMAL_PRIORITY_CLASS => Ok(PriorityClass::Normal),
+            ABOVE_NORMAL_PRIORITY_CLASS => Ok(PriorityClass::AboveNormal),
+            HIGH_PRIORITY_CLASS => Ok(PriorityClass::High),
+            REALTIME_PRIORITY_CLASS => Ok(PriorityClass::Realtime),
+            _ => Err(format!("Unknown priority class: {:#x}", value.0)),
+        }
+    }
+}
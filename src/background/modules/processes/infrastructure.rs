@@ -0,0 +1,27 @@
+use crate::{error_handler::Result, trace_lock};
+
+use super::{
+    application::PROCESS_SAMPLER,
+    domain::{PriorityClass, ProcessInfo},
+};
+
+#[tauri::command(async)]
+pub fn get_processes() -> Vec<ProcessInfo> {
+    trace_lock!(PROCESS_SAMPLER).processes()
+}
+
+#[tauri::command(async)]
+pub async fn kill_process(pid: u32, force: bool) -> Result<()> {
+    super::application::kill_process(pid, force).await
+}
+
+/// returns the previous priority
+#[tauri::command(async)]
+pub fn set_process_priority(pid: u32, priority: PriorityClass) -> Result<PriorityClass> {
+    super::application::set_process_priority(pid, priority)
+}
+
+#[tauri::command(async)]
+pub fn set_process_efficiency_mode(pid: u32, enabled: bool) -> Result<()> {
+    super::application::set_process_efficiency_mode(pid, enabled)
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use windows::{
+    Devices::Radios::{Radio, RadioAccessStatus, RadioState},
+    Foundation::{EventRegistrationToken, TypedEventHandler},
+};
+
+use crate::{error_handler::Result, event_manager, log_error};
+
+use super::domain::{AirplaneModeState, RadioDevice, RadioDeviceState, RadioToggleResult};
+
+lazy_static! {
+    pub static ref RADIO_MANAGER: Arc<Mutex<RadioManager>> =
+        Arc::new(Mutex::new(RadioManager::new()));
+}
+
+#[derive(Debug, Clone)]
+pub enum RadioEvent {
+    StateChanged,
+}
+
+pub struct RadioManager {
+    radios: Vec<(Radio, EventRegistrationToken)>,
+}
+
+unsafe impl Send for RadioManager {}
+
+event_manager!(RadioManager, RadioEvent);
+
+impl RadioManager {
+    fn new() -> Self {
+        Self { radios: Vec::new() }
+    }
+
+    fn get_radios() -> Result<Vec<Radio>> {
+        let access = Radio::RequestAccessAsync()?.get()?;
+        if access != RadioAccessStatus::Allowed {
+            return Err(format!("Access to radios was denied: {:?}", access).into());
+        }
+        Ok(Radio::GetRadiosAsync()?.get()?.into_iter().collect())
+    }
+
+    pub fn register_events(&mut self) -> Result<()> {
+        if !self.radios.is_empty() {
+            return Ok(());
+        }
+        for radio in Self::get_radios()? {
+            let token = radio.StateChanged(&TypedEventHandler::new(|_, _| {
+                log_error!(Self::event_tx().send(RadioEvent::StateChanged));
+                Ok(())
+            }))?;
+            self.radios.push((radio, token));
+        }
+        Ok(())
+    }
+
+    pub fn release(&mut self) {
+        for (radio, token) in self.radios.drain(..) {
+            log_error!(radio.RemoveStateChanged(token));
+        }
+    }
+
+    pub fn get_airplane_mode() -> Result<AirplaneModeState> {
+        let mut radios = Vec::new();
+        for radio in Self::get_radios()? {
+            radios.push(RadioDevice {
+                name: radio.Name()?.to_string_lossy(),
+                kind: radio.Kind()?.into(),
+                state: radio.State()?.into(),
+            });
+        }
+        Ok(AirplaneModeState {
+            enabled: !radios.iter().any(|r| r.state == RadioDeviceState::On),
+            radios,
+        })
+    }
+
+    fn set_radio_state(radio: &Radio, state: RadioState) -> Result<()> {
+        let status = radio.SetStateAsync(state)?.get()?;
+        if status != RadioAccessStatus::Allowed {
+            return Err(format!("{:?}", status).into());
+        }
+        Ok(())
+    }
+
+    /// Radios are toggled independently so one refusing doesn't stop the others,
+    /// the result of each one is returned to be shown to the user.
+    pub fn set_airplane_mode(enabled: bool) -> Result<Vec<RadioToggleResult>> {
+        let state = if enabled {
+            RadioState::Off
+        } else {
+            RadioState::On
+        };
+
+        let mut results = Vec::new();
+        for radio in Self::get_radios()? {
+            let result = Self::set_radio_state(&radio, state);
+            results.push(RadioToggleResult {
+                name: radio.Name()?.to_string_lossy(),
+                kind: radio.Kind()?.into(),
+                success: result.is_ok(),
+                error: result.err().map(|err| err.message().to_owned()),
+            });
+        }
+        Ok(results)
+    }
+}
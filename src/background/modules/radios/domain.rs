@@ -0,0 +1,67 @@
+use serde::Serialize;
+use windows::Devices::Radios::{RadioKind, RadioState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RadioDeviceKind {
+    Other,
+    WiFi,
+    MobileBroadband,
+    Bluetooth,
+    FM,
+}
+
+impl From<RadioKind> for RadioDeviceKind {
+    fn from(kind: RadioKind) -> Self {
+        match kind {
+            RadioKind::WiFi => Self::WiFi,
+            RadioKind::MobileBroadband => Self::MobileBroadband,
+            RadioKind::Bluetooth => Self::Bluetooth,
+            RadioKind::FM => Self::FM,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RadioDeviceState {
+    Unknown,
+    On,
+    Off,
+    Disabled,
+}
+
+impl From<RadioState> for RadioDeviceState {
+    fn from(state: RadioState) -> Self {
+        match state {
+            RadioState::On => Self::On,
+            RadioState::Off => Self::Off,
+            RadioState::Disabled => Self::Disabled,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RadioDevice {
+    pub name: String,
+    pub kind: RadioDeviceKind,
+    pub state: RadioDeviceState,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirplaneModeState {
+    /// true when there is no radio turned on
+    pub enabled: bool,
+    pub radios: Vec<RadioDevice>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RadioToggleResult {
+    pub name: String,
+    pub kind: RadioDeviceKind,
+    pub success: bool,
+    pub error: Option<String>,
+}
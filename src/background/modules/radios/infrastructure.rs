@@ -0,0 +1,44 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::Emitter;
+
+use crate::{error_handler::Result, log_error, seelen::get_app_handle, trace_lock};
+
+use super::{
+    application::{RadioManager, RADIO_MANAGER},
+    domain::{AirplaneModeState, RadioToggleResult},
+};
+
+fn emit_airplane_mode() -> Result<()> {
+    get_app_handle().emit("airplane-mode-changed", RadioManager::get_airplane_mode()?)?;
+    Ok(())
+}
+
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+pub fn register_radio_events() {
+    let was_registered = REGISTERED.swap(true, Ordering::AcqRel);
+    std::thread::spawn(move || {
+        if !was_registered {
+            log::trace!("Registering radio events");
+            log_error!(trace_lock!(RADIO_MANAGER).register_events());
+            RadioManager::subscribe(|_event| log_error!(emit_airplane_mode()));
+        }
+        log_error!(emit_airplane_mode());
+    });
+}
+
+pub fn release_radio_events() {
+    if REGISTERED.load(Ordering::Acquire) {
+        trace_lock!(RADIO_MANAGER).release();
+    }
+}
+
+#[tauri::command(async)]
+pub fn get_airplane_mode() -> Result<AirplaneModeState> {
+    RadioManager::get_airplane_mode()
+}
+
+#[tauri::command(async)]
+pub fn set_airplane_mode(enabled: bool) -> Result<Vec<RadioToggleResult>> {
+    RadioManager::set_airplane_mode(enabled)
+}
@@ -0,0 +1,241 @@
+use std::{
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use tauri::Emitter;
+use windows::{
+    core::{HSTRING, PCWSTR},
+    Win32::{
+        Foundation::{BOOL, HWND, LPARAM, LRESULT, WPARAM},
+        System::Com::CoTaskMemFree,
+        UI::{
+            Shell::{
+                FOLDERID_RecycleBinFolder, FileOperation, IFileOperation, IShellItem,
+                SHCNRF_InterruptLevel, SHCNRF_ShellLevel, SHChangeNotifyEntry,
+                SHChangeNotifyRegister, SHCreateItemFromParsingName, SHEmptyRecycleBinW,
+                SHGetKnownFolderIDList, SHQueryRecycleBinW, FOFX_RECYCLEONDELETE, FOF_ALLOWUNDO,
+                FOF_NOCONFIRMATION, FOF_NOERRORUI, FOF_SILENT, KF_FLAG_DEFAULT, SHCNE_ALLEVENTS,
+                SHERB_NOCONFIRMATION, SHERB_NOPROGRESSUI, SHERB_NOSOUND, SHQUERYRBINFO,
+            },
+            WindowsAndMessaging::{
+                CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+                TranslateMessage, MSG, WINDOW_EX_STYLE, WINDOW_STYLE, WM_APP, WNDCLASSW,
+            },
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    seelen::get_app_handle,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{Com, WindowsApi},
+};
+
+use super::domain::{RecycleBinInfo, RecycleResult};
+
+/// message sent by the shell to the watcher window on changes of the bin folder
+const WM_RECYCLE_BIN_CHANGED: u32 = WM_APP + 1;
+
+/// deleting a folder notifies each one of its items
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+lazy_static! {
+    static ref CHANNEL: (Sender<()>, Receiver<()>) = crossbeam_channel::unbounded();
+    static ref LAST_INFO: Mutex<Option<RecycleBinInfo>> = Mutex::new(None);
+}
+
+static LISTENING: AtomicBool = AtomicBool::new(false);
+
+pub struct RecycleBin;
+impl RecycleBin {
+    /// a null root path aggregates the bins of all the drives
+    pub fn query() -> Result<RecycleBinInfo> {
+        let mut info = SHQUERYRBINFO {
+            cbSize: std::mem::size_of::<SHQUERYRBINFO>() as u32,
+            ..Default::default()
+        };
+        unsafe { SHQueryRecycleBinW(PCWSTR::null(), &mut info)? };
+        Ok(RecycleBinInfo {
+            item_count: info.i64NumItems,
+            total_bytes: info.i64Size,
+        })
+    }
+
+    /// `confirm_ui` shows the shell confirmation dialog and progress, the call
+    /// fails if the user cancels it.
+    pub fn empty(confirm_ui: bool) -> Result<()> {
+        // the shell fails with E_UNEXPECTED when the bin is already empty
+        if Self::query()?.item_count == 0 {
+            return Ok(());
+        }
+        let flags = if confirm_ui {
+            0
+        } else {
+            SHERB_NOCONFIRMATION | SHERB_NOPROGRESSUI | SHERB_NOSOUND
+        };
+        unsafe { SHEmptyRecycleBinW(HWND::default(), PCWSTR::null(), flags)? };
+        Ok(())
+    }
+
+    fn recycle(path: &Path) -> Result<()> {
+        if !path.exists() {
+            return Err(format!("{} does not exist", path.display()).into());
+        }
+        unsafe {
+            let item: IShellItem = SHCreateItemFromParsingName(&HSTRING::from(path), None)?;
+            let operation: IFileOperation = Com::create_instance(&FileOperation)?;
+            // errors as files in use or access denied are returned instead of prompting the user
+            operation.SetOperationFlags(
+                FOF_ALLOWUNDO
+                    | FOF_NOCONFIRMATION
+                    | FOF_SILENT
+                    | FOF_NOERRORUI
+                    | FOFX_RECYCLEONDELETE,
+            )?;
+            operation.DeleteItem(&item, None)?;
+            operation.PerformOperations()?;
+            if operation.GetAnyOperationsAborted()?.as_bool() {
+                return Err(format!("Recycling of {} was aborted", path.display()).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Each path is sent in its own operation so a file in use or without access
+    /// doesn't stop the rest, works with files and directories.
+    pub fn move_to_recycle_bin(paths: Vec<String>) -> Result<Vec<RecycleResult>> {
+        Com::run_with_context(|| {
+            let results = paths
+                .into_iter()
+                .map(|path| {
+                    let result = Self::recycle(Path::new(&path));
+                    RecycleResult {
+                        path,
+                        success: result.is_ok(),
+                        error: result.err().map(|err| err.message().to_owned()),
+                    }
+                })
+                .collect();
+            Ok(results)
+        })
+    }
+
+    unsafe extern "system" fn window_proc(
+        window: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        if message == WM_RECYCLE_BIN_CHANGED {
+            log_error!(CHANNEL.0.send(()));
+            return LRESULT(0);
+        }
+        DefWindowProcW(window, message, wparam, lparam)
+    }
+
+    unsafe fn create_background_window() -> Result<()> {
+        let wide_name: Vec<u16> = "Seelen Recycle Bin Watcher"
+            .encode_utf16()
+            .chain(Some(0))
+            .collect();
+        let wide_class: Vec<u16> = "SeelenRecycleBinWatcher"
+            .encode_utf16()
+            .chain(Some(0))
+            .collect();
+
+        let h_module = WindowsApi::module_handle_w()?;
+
+        let wnd_class = WNDCLASSW {
+            lpfnWndProc: Some(Self::window_proc),
+            hInstance: h_module.into(),
+            lpszClassName: PCWSTR(wide_class.as_ptr()),
+            ..Default::default()
+        };
+
+        RegisterClassW(&wnd_class);
+
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            PCWSTR(wide_class.as_ptr()),
+            PCWSTR(wide_name.as_ptr()),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            h_module,
+            None,
+        )?;
+
+        let pidl =
+            SHGetKnownFolderIDList(&FOLDERID_RecycleBinFolder, KF_FLAG_DEFAULT.0 as _, None)?;
+        let entry = SHChangeNotifyEntry {
+            pidl,
+            fRecursive: BOOL::from(true),
+        };
+        let registered = SHChangeNotifyRegister(
+            hwnd,
+            SHCNRF_ShellLevel | SHCNRF_InterruptLevel,
+            SHCNE_ALLEVENTS.0 as i32,
+            WM_RECYCLE_BIN_CHANGED,
+            1,
+            &entry,
+        );
+        // the shell keeps its own copy of the registered pidl
+        CoTaskMemFree(Some(pidl as _));
+        if registered == 0 {
+            return Err("Failed to register recycle bin change notifications".into());
+        }
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, hwnd, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        Ok(())
+    }
+
+    /// emits once per burst and only if the contents changed
+    fn dispatch_changes() {
+        let receiver = &CHANNEL.1;
+        while receiver.recv().is_ok() {
+            while receiver.recv_timeout(DEBOUNCE).is_ok() {}
+
+            let info = match Self::query() {
+                Ok(info) => info,
+                Err(err) => {
+                    log::error!("Failed to query recycle bin: {err}");
+                    continue;
+                }
+            };
+            let mut last = trace_lock!(LAST_INFO);
+            if *last != Some(info) {
+                *last = Some(info);
+                log_error!(get_app_handle().emit("recycle-bin-changed", info));
+            }
+        }
+    }
+
+    /// Emits `recycle-bin-changed` with the new contents when items are added, restored or removed
+    pub fn start() -> Result<()> {
+        if LISTENING.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        *trace_lock!(LAST_INFO) = Self::query().ok();
+        spawn_named_thread("Recycle Bin Watcher", || unsafe {
+            log_error!(Self::create_background_window());
+        })?;
+        spawn_named_thread("Recycle Bin Events Dispatcher", Self::dispatch_changes)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+/// Contents of the recycle bins of all the drives
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecycleBinInfo {
+    pub item_count: i64,
+    pub total_bytes: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecycleResult {
+    pub path: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
@@ -0,0 +1,25 @@
+use crate::{error_handler::Result, log_error};
+
+use super::{
+    application::RecycleBin,
+    domain::{RecycleBinInfo, RecycleResult},
+};
+
+pub fn register_recycle_bin_events() {
+    log_error!(RecycleBin::start());
+}
+
+#[tauri::command(async)]
+pub fn recycle_bin_query() -> Result<RecycleBinInfo> {
+    RecycleBin::query()
+}
+
+#[tauri::command(async)]
+pub fn recycle_bin_empty(confirm_ui: bool) -> Result<()> {
+    RecycleBin::empty(confirm_ui)
+}
+
+#[tauri::command(async)]
+pub fn move_to_recycle_bin(paths: Vec<String>) -> Result<Vec<RecycleResult>> {
+    RecycleBin::move_to_recycle_bin(paths)
+}
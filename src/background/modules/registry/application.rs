@@ -0,0 +1,130 @@
+use winreg::{
+    enums::{
+        RegType, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_READ, KEY_WRITE, REG_BINARY,
+        REG_EXPAND_SZ,
+    },
+    types::{FromRegValue, ToRegValue},
+    RegKey, RegValue,
+};
+
+use crate::{error_handler::Result, windows_api::WindowsApi};
+
+use super::domain::{Hive, RegistryValue};
+
+/// https://learn.microsoft.com/en-us/windows/win32/sysinfo/registry-element-size-limits
+const MAX_KEY_NAME_LEN: usize = 255;
+const MAX_VALUE_NAME_LEN: usize = 16383;
+/// keys writable by any webview, the rest of the registry is only writable by the settings
+const SHARED_WRITABLE_KEYS: [&str; 1] = [r"Software\Seelen"];
+
+pub struct Registry;
+impl Registry {
+    /// HKLM is only accessible if explicitly allowed by the caller
+    fn root(hive: Hive, allow_machine: bool) -> Result<RegKey> {
+        match hive {
+            Hive::CurrentUser => Ok(RegKey::predef(HKEY_CURRENT_USER)),
+            Hive::LocalMachine if allow_machine => Ok(RegKey::predef(HKEY_LOCAL_MACHINE)),
+            Hive::LocalMachine => Err("Access to HKLM requires the allow_machine flag".into()),
+        }
+    }
+
+    /// returns the normalized key path, rejecting anything that could escape the intended key
+    fn sanitize_key(key: &str) -> Result<String> {
+        let key = key.trim().trim_matches('\\');
+        if key.is_empty() {
+            return Err("Writing or reading the root of a hive is not allowed".into());
+        }
+
+        for part in key.split('\\') {
+            if part.is_empty() || part == "." || part == ".." {
+                return Err(format!("Invalid registry key: {}", key).into());
+            }
+            if part.len() > MAX_KEY_NAME_LEN || part.chars().any(|c| c.is_control()) {
+                return Err(format!("Invalid registry key segment: {}", part).into());
+            }
+        }
+        Ok(key.to_string())
+    }
+
+    /// `key` should be already sanitized, compared by whole segments ignoring case
+    fn is_shared_writable(hive: Hive, key: &str) -> bool {
+        let key = key.to_lowercase();
+        hive == Hive::CurrentUser
+            && SHARED_WRITABLE_KEYS.iter().any(|prefix| {
+                let prefix = prefix.to_lowercase();
+                key == prefix || key.starts_with(&format!("{}\\", prefix))
+            })
+    }
+
+    fn sanitize_value_name(name: &str) -> Result<()> {
+        if name.len() > MAX_VALUE_NAME_LEN || name.chars().any(|c| c.is_control()) {
+            return Err(format!("Invalid registry value name: {}", name).into());
+        }
+        Ok(())
+    }
+
+    fn decode(raw: &RegValue) -> Result<RegistryValue> {
+        let value = match raw.vtype {
+            RegType::REG_SZ => RegistryValue::String(String::from_reg_value(raw)?),
+            RegType::REG_EXPAND_SZ => RegistryValue::ExpandString(String::from_reg_value(raw)?),
+            RegType::REG_MULTI_SZ => RegistryValue::MultiString(Vec::from_reg_value(raw)?),
+            RegType::REG_DWORD => RegistryValue::Dword(u32::from_reg_value(raw)?),
+            RegType::REG_QWORD => RegistryValue::Qword(u64::from_reg_value(raw)?),
+            _ => RegistryValue::Binary(raw.bytes.clone()),
+        };
+        Ok(value)
+    }
+
+    fn encode(value: &RegistryValue) -> RegValue {
+        match value {
+            RegistryValue::String(s) => s.to_reg_value(),
+            RegistryValue::ExpandString(s) => {
+                let mut raw = s.to_reg_value();
+                raw.vtype = REG_EXPAND_SZ;
+                raw
+            }
+            RegistryValue::MultiString(list) => list.to_reg_value(),
+            RegistryValue::Dword(n) => n.to_reg_value(),
+            RegistryValue::Qword(n) => n.to_reg_value(),
+            RegistryValue::Binary(bytes) => RegValue {
+                bytes: bytes.clone(),
+                vtype: REG_BINARY,
+            },
+        }
+    }
+
+    pub fn read(hive: Hive, key: &str, name: &str, allow_machine: bool) -> Result<RegistryValue> {
+        let key = Self::sanitize_key(key)?;
+        Self::sanitize_value_name(name)?;
+        let reg_key = Self::root(hive, allow_machine)?.open_subkey_with_flags(&key, KEY_READ)?;
+        Self::decode(&reg_key.get_raw_value(name)?)
+    }
+
+    /// only the settings (`privileged`) can write outside of `SHARED_WRITABLE_KEYS`
+    pub fn write(
+        hive: Hive,
+        key: &str,
+        name: &str,
+        value: &RegistryValue,
+        allow_machine: bool,
+        privileged: bool,
+    ) -> Result<()> {
+        let key = Self::sanitize_key(key)?;
+        Self::sanitize_value_name(name)?;
+        if !privileged && !Self::is_shared_writable(hive, &key) {
+            return Err(format!(
+                "Only the settings can write outside of HKCU\\{}",
+                SHARED_WRITABLE_KEYS.join(", HKCU\\")
+            )
+            .into());
+        }
+        if hive == Hive::LocalMachine && allow_machine && !WindowsApi::is_elevated()? {
+            return Err("Writing to HKLM requires running Seelen UI as administrator".into());
+        }
+
+        let root = Self::root(hive, allow_machine)?;
+        let (reg_key, _) = root.create_subkey_with_flags(&key, KEY_WRITE)?;
+        reg_key.set_raw_value(name, &Self::encode(value))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use tauri::WebviewWindow;
+
+use crate::{error_handler::Result, seelen::Seelen};
+
+use super::{
+    application::Registry,
+    domain::{Hive, RegistryValue},
+};
+
+#[tauri::command(async)]
+pub fn registry_read(
+    hive: Hive,
+    key: String,
+    value: String,
+    allow_machine: Option<bool>,
+) -> Result<RegistryValue> {
+    Registry::read(hive, &key, &value, allow_machine.unwrap_or(false))
+}
+
+/// widgets can only write under `HKCU\Software\Seelen`, so they can't change system or
+/// startup behaviour as the `Run` key, the settings window can write anywhere.
+#[tauri::command(async)]
+pub fn registry_write(
+    webview: WebviewWindow<tauri::Wry>,
+    hive: Hive,
+    key: String,
+    value: String,
+    data: RegistryValue,
+    allow_machine: Option<bool>,
+) -> Result<()> {
+    let privileged = webview.label() == Seelen::settings_label();
+    Registry::write(
+        hive,
+        &key,
+        &value,
+        &data,
+        allow_machine.unwrap_or(false),
+        privileged,
+    )
+}
@@ -0,0 +1,254 @@
+use chrono::{Local, NaiveDate, TimeZone};
+use windows::{
+    core::{Interface, BSTR, VARIANT},
+    Win32::{
+        Foundation::{VARIANT_FALSE, VARIANT_TRUE},
+        System::TaskScheduler::{
+            IDailyTrigger, IExecAction, ILogonTrigger, IRegisteredTask, ITaskFolder, ITaskService,
+            TaskScheduler, TASK_ACTION_EXEC, TASK_CREATE_OR_UPDATE, TASK_ENUM_HIDDEN,
+            TASK_LOGON_INTERACTIVE_TOKEN, TASK_LOGON_SERVICE_ACCOUNT, TASK_RUNLEVEL_HIGHEST,
+            TASK_RUNLEVEL_LUA, TASK_TRIGGER_DAILY, TASK_TRIGGER_LOGON, TASK_TRIGGER_TIME,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    windows_api::{Com, WindowsApi},
+};
+
+use super::domain::{ScheduledTask, TaskAction, TaskTrigger};
+
+/// folder of the Seelen UI startup task (see `schedule.ps1`)
+const SEELEN_FOLDER: &str = "\\Seelen";
+/// all the tasks created by the user via Seelen are placed in this subfolder to be easily
+/// found and removed, apart from the tasks owned by the app itself
+const AUTOMATIONS_FOLDER_NAME: &str = "Automations";
+const AUTOMATIONS_FOLDER: &str = "\\Seelen\\Automations";
+/// names used by the app own tasks
+const RESERVED_NAMES: [&str; 1] = ["Seelen-UI"];
+
+pub struct TaskSchedulerManager;
+impl TaskSchedulerManager {
+    fn validate_name(name: &str) -> Result<()> {
+        if name.trim().is_empty() || name.contains(['\\', '/']) {
+            return Err(format!("Invalid task name: {name:?}").into());
+        }
+        if RESERVED_NAMES
+            .iter()
+            .any(|reserved| reserved.eq_ignore_ascii_case(name.trim()))
+        {
+            return Err(format!("The task name {name:?} is reserved").into());
+        }
+        Ok(())
+    }
+
+    fn validate(trigger: &TaskTrigger, action: &TaskAction) -> Result<()> {
+        match *trigger {
+            TaskTrigger::Daily { hour, minute } if hour > 23 || minute > 59 => {
+                return Err(format!("Invalid time {:02}:{:02}", hour, minute).into());
+            }
+            TaskTrigger::Once { at } if at <= Local::now().timestamp() => {
+                return Err("The trigger time should be in the future".into());
+            }
+            _ => {}
+        }
+        if action.program.trim().is_empty() {
+            return Err("The action program can not be empty".into());
+        }
+        Ok(())
+    }
+
+    /// Task Scheduler dates are local times without offset
+    fn start_boundary(trigger: &TaskTrigger) -> Result<Option<String>> {
+        let boundary = match *trigger {
+            TaskTrigger::Logon => None,
+            TaskTrigger::Daily { hour, minute } => Some(
+                Local::now()
+                    .date_naive()
+                    .and_hms_opt(hour, minute, 0)
+                    .ok_or("Invalid time")?,
+            ),
+            TaskTrigger::Once { at } => Some(
+                Local
+                    .timestamp_opt(at, 0)
+                    .single()
+                    .ok_or("Invalid timestamp")?
+                    .naive_local(),
+            ),
+        };
+        Ok(boundary.map(|date| date.format("%Y-%m-%dT%H:%M:%S").to_string()))
+    }
+
+    /// OLE automation date (days since 1899-12-30 in local time) to unix timestamp, zero means never
+    fn ole_date_to_timestamp(date: f64) -> Option<i64> {
+        if date <= 0.0 {
+            return None;
+        }
+        let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)?.and_hms_opt(0, 0, 0)?;
+        let local = epoch + chrono::Duration::milliseconds((date * 86_400_000.0) as i64);
+        Some(Local.from_local_datetime(&local).earliest()?.timestamp())
+    }
+
+    fn service() -> Result<ITaskService> {
+        let service: ITaskService = Com::create_instance(&TaskScheduler)?;
+        let empty = VARIANT::default();
+        unsafe { service.Connect(&empty, &empty, &empty, &empty)? };
+        Ok(service)
+    }
+
+    fn automations_folder(service: &ITaskService) -> Result<ITaskFolder> {
+        unsafe {
+            if let Ok(folder) = service.GetFolder(&BSTR::from(AUTOMATIONS_FOLDER)) {
+                return Ok(folder);
+            }
+            let seelen = match service.GetFolder(&BSTR::from(SEELEN_FOLDER)) {
+                Ok(folder) => folder,
+                Err(_) => service
+                    .GetFolder(&BSTR::from("\\"))?
+                    .CreateFolder(&BSTR::from(SEELEN_FOLDER), &VARIANT::default())?,
+            };
+            Ok(seelen.CreateFolder(&BSTR::from(AUTOMATIONS_FOLDER_NAME), &VARIANT::default())?)
+        }
+    }
+
+    fn read_task(task: &IRegisteredTask) -> Result<ScheduledTask> {
+        unsafe {
+            let principal = task.Definition()?.Principal()?;
+            Ok(ScheduledTask {
+                name: task.Name()?.to_string(),
+                enabled: task.Enabled()?.as_bool(),
+                system: principal.LogonType()? == TASK_LOGON_SERVICE_ACCOUNT,
+                next_run: Self::ole_date_to_timestamp(task.NextRunTime()?),
+                last_run: Self::ole_date_to_timestamp(task.LastRunTime()?),
+            })
+        }
+    }
+
+    /// System level tasks run as the SYSTEM account with highest privileges and
+    /// require Seelen to be elevated, the rest run as the current user.
+    pub fn create(
+        name: &str,
+        trigger: TaskTrigger,
+        action: TaskAction,
+        system: bool,
+    ) -> Result<ScheduledTask> {
+        Self::validate_name(name)?;
+        Self::validate(&trigger, &action)?;
+        if system && !WindowsApi::is_elevated()? {
+            return Err("System level tasks can only be created running as administrator".into());
+        }
+        let start_boundary = Self::start_boundary(&trigger)?;
+
+        Com::run_with_context(|| unsafe {
+            let service = Self::service()?;
+            let folder = Self::automations_folder(&service)?;
+            let definition = service.NewTask(0)?;
+
+            let info = definition.RegistrationInfo()?;
+            info.SetAuthor(&BSTR::from("Seelen UI"))?;
+
+            let user = format!(
+                "{}\\{}",
+                std::env::var("USERDOMAIN").unwrap_or_default(),
+                std::env::var("USERNAME").unwrap_or_default()
+            );
+            let principal = definition.Principal()?;
+            if system {
+                principal.SetUserId(&BSTR::from("SYSTEM"))?;
+                principal.SetLogonType(TASK_LOGON_SERVICE_ACCOUNT)?;
+                principal.SetRunLevel(TASK_RUNLEVEL_HIGHEST)?;
+            } else {
+                principal.SetUserId(&BSTR::from(user.as_str()))?;
+                principal.SetLogonType(TASK_LOGON_INTERACTIVE_TOKEN)?;
+                principal.SetRunLevel(TASK_RUNLEVEL_LUA)?;
+            }
+
+            let settings = definition.Settings()?;
+            settings.SetStartWhenAvailable(VARIANT_TRUE)?;
+            settings.SetDisallowStartIfOnBatteries(VARIANT_FALSE)?;
+            settings.SetStopIfGoingOnBatteries(VARIANT_FALSE)?;
+
+            let triggers = definition.Triggers()?;
+            let created = match trigger {
+                TaskTrigger::Logon => {
+                    let logon: ILogonTrigger = triggers.Create(TASK_TRIGGER_LOGON)?.cast()?;
+                    if !system {
+                        logon.SetUserId(&BSTR::from(user.as_str()))?;
+                    }
+                    logon.cast()?
+                }
+                TaskTrigger::Daily { .. } => {
+                    let daily: IDailyTrigger = triggers.Create(TASK_TRIGGER_DAILY)?.cast()?;
+                    daily.SetDaysInterval(1)?;
+                    daily.cast()?
+                }
+                TaskTrigger::Once { .. } => triggers.Create(TASK_TRIGGER_TIME)?,
+            };
+            if let Some(start_boundary) = &start_boundary {
+                created.SetStartBoundary(&BSTR::from(start_boundary.as_str()))?;
+            }
+
+            let exec: IExecAction = definition.Actions()?.Create(TASK_ACTION_EXEC)?.cast()?;
+            exec.SetPath(&BSTR::from(action.program.as_str()))?;
+            exec.SetArguments(&BSTR::from(action.args.as_str()))?;
+            if let Some(working_dir) = &action.working_dir {
+                exec.SetWorkingDirectory(&BSTR::from(working_dir.as_str()))?;
+            }
+
+            let logon_type = if system {
+                TASK_LOGON_SERVICE_ACCOUNT
+            } else {
+                TASK_LOGON_INTERACTIVE_TOKEN
+            };
+            let registered = folder.RegisterTaskDefinition(
+                &BSTR::from(name),
+                &definition,
+                TASK_CREATE_OR_UPDATE.0,
+                &VARIANT::default(),
+                &VARIANT::default(),
+                logon_type,
+                &VARIANT::default(),
+            )?;
+            Self::read_task(&registered)
+        })
+    }
+
+    pub fn list() -> Result<Vec<ScheduledTask>> {
+        Com::run_with_context(|| unsafe {
+            let service = Self::service()?;
+            let Ok(folder) = service.GetFolder(&BSTR::from(AUTOMATIONS_FOLDER)) else {
+                return Ok(Vec::new());
+            };
+            let tasks = folder.GetTasks(TASK_ENUM_HIDDEN.0)?;
+            let mut result = Vec::new();
+            // the collection is 1-based
+            for index in 1..=tasks.Count()? {
+                let task = tasks.get_Item(&VARIANT::from(index))?;
+                result.push(Self::read_task(&task)?);
+            }
+            Ok(result)
+        })
+    }
+
+    pub fn set_enabled(name: &str, enabled: bool) -> Result<ScheduledTask> {
+        Self::validate_name(name)?;
+        Com::run_with_context(|| unsafe {
+            let service = Self::service()?;
+            let folder = Self::automations_folder(&service)?;
+            let task = folder.GetTask(&BSTR::from(name))?;
+            task.SetEnabled(if enabled { VARIANT_TRUE } else { VARIANT_FALSE })?;
+            Self::read_task(&task)
+        })
+    }
+
+    pub fn delete(name: &str) -> Result<()> {
+        Self::validate_name(name)?;
+        Com::run_with_context(|| unsafe {
+            let service = Self::service()?;
+            let folder = Self::automations_folder(&service)?;
+            folder.DeleteTask(&BSTR::from(name), 0)?;
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TaskTrigger {
+    /// on logon of the current user, or of any user for system level tasks
+    Logon,
+    #[serde(rename_all = "camelCase")]
+    Daily { hour: u32, minute: u32 },
+    #[serde(rename_all = "camelCase")]
+    Once {
+        /// unix timestamp in seconds
+        at: i64,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskAction {
+    pub program: String,
+    #[serde(default)]
+    pub args: String,
+    pub working_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledTask {
+    pub name: String,
+    pub enabled: bool,
+    /// runs as the SYSTEM account
+    pub system: bool,
+    /// unix timestamp in seconds, None if the task will not run again
+    pub next_run: Option<i64>,
+    /// unix timestamp in seconds, None if the task never ran
+    pub last_run: Option<i64>,
+}
@@ -0,0 +1,40 @@
+use tauri::WebviewWindow;
+
+use crate::{error_handler::Result, seelen::Seelen};
+
+use super::{
+    application::TaskSchedulerManager,
+    domain::{ScheduledTask, TaskAction, TaskTrigger},
+};
+
+/// system level tasks can only be requested by the settings window, so any other
+/// webview (as third party widgets) can't get things running as SYSTEM.
+#[tauri::command(async)]
+pub fn create_scheduled_task(
+    webview: WebviewWindow<tauri::Wry>,
+    name: String,
+    trigger: TaskTrigger,
+    action: TaskAction,
+    system: Option<bool>,
+) -> Result<ScheduledTask> {
+    let system = system.unwrap_or(false);
+    if system && webview.label() != Seelen::settings_label() {
+        return Err("System level tasks can only be created from the settings".into());
+    }
+    TaskSchedulerManager::create(&name, trigger, action, system)
+}
+
+#[tauri::command(async)]
+pub fn list_seelen_tasks() -> Result<Vec<ScheduledTask>> {
+    TaskSchedulerManager::list()
+}
+
+#[tauri::command(async)]
+pub fn set_scheduled_task_enabled(name: String, enabled: bool) -> Result<ScheduledTask> {
+    TaskSchedulerManager::set_enabled(&name, enabled)
+}
+
+#[tauri::command(async)]
+pub fn delete_scheduled_task(name: String) -> Result<()> {
+    TaskSchedulerManager::delete(&name)
+}
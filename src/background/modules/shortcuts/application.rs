@@ -0,0 +1,147 @@
+use std::path::Path;
+
+use windows::{
+    core::{Interface, BSTR, HSTRING, PROPVARIANT},
+    Win32::{
+        Storage::{EnhancedStorage::PKEY_AppUserModel_ID, FileSystem::WIN32_FIND_DATAW},
+        System::Com::{CoTaskMemFree, IPersistFile, STGM_READ},
+        UI::Shell::{
+            IShellLinkW, PropertiesSystem::IPropertyStore, SHGetNameFromIDList, ShellLink,
+            SIGDN_PARENTRELATIVEPARSING,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    windows_api::{Com, WindowsApi},
+};
+
+use super::domain::{NewShortcut, ShortcutInfo};
+
+pub struct Shortcuts;
+impl Shortcuts {
+    fn from_utf16(buffer: &[u16]) -> Option<String> {
+        let len = buffer.iter().position(|c| *c == 0).unwrap_or(buffer.len());
+        let value = String::from_utf16_lossy(&buffer[..len]);
+        (!value.is_empty()).then_some(value)
+    }
+
+    /// explicit id of the shortcut or the id of the packaged app it points to
+    unsafe fn read_umid(link: &IShellLinkW) -> Option<String> {
+        if let Ok(store) = link.cast::<IPropertyStore>() {
+            if let Ok(value) = store.GetValue(&PKEY_AppUserModel_ID) {
+                if !value.is_empty() {
+                    return BSTR::try_from(&value).ok().map(|umid| umid.to_string());
+                }
+            }
+        }
+
+        // items of the AppsFolder are named by the umid, e.g. `Microsoft.WindowsCalculator_8wekyb3d8bbwe!App`
+        let pidl = link.GetIDList().ok()?;
+        let name = SHGetNameFromIDList(pidl, SIGDN_PARENTRELATIVEPARSING);
+        CoTaskMemFree(Some(pidl as _));
+        let name = name.ok()?;
+        let umid = name.to_string().ok();
+        CoTaskMemFree(Some(name.0 as _));
+        umid.filter(|umid| umid.contains('!'))
+    }
+
+    /// Fails for dangling shortcuts, those without target or whose target no longer exists.
+    pub fn resolve(path: &Path) -> Result<ShortcutInfo> {
+        if !path.exists() {
+            return Err(format!("{} does not exist", path.display()).into());
+        }
+
+        Com::run_with_context(|| unsafe {
+            let link: IShellLinkW = Com::create_instance(&ShellLink)?;
+            link.cast::<IPersistFile>()?
+                .Load(&HSTRING::from(path), STGM_READ)?;
+
+            let mut target = [0u16; 1024];
+            let mut find_data = WIN32_FIND_DATAW::default();
+            // shortcuts to shell items, like packaged apps, have no file system path
+            let _ = link.GetPath(&mut target, &mut find_data, 0);
+            let target = Self::from_utf16(&target);
+
+            let mut args = [0u16; 1024];
+            link.GetArguments(&mut args)?;
+
+            let mut working_dir = [0u16; 1024];
+            link.GetWorkingDirectory(&mut working_dir)?;
+
+            let mut description = [0u16; 1024];
+            link.GetDescription(&mut description)?;
+
+            let mut icon_path = [0u16; 1024];
+            let mut icon_index = 0;
+            link.GetIconLocation(&mut icon_path, &mut icon_index)?;
+
+            let umid = Self::read_umid(&link);
+            match (&target, &umid) {
+                (None, None) => {
+                    return Err(
+                        format!("Broken shortcut {}: it has no target", path.display()).into(),
+                    );
+                }
+                (Some(target), None) if !Path::new(target).exists() => {
+                    return Err(format!(
+                        "Broken shortcut {}: target {target} does not exist",
+                        path.display()
+                    )
+                    .into());
+                }
+                _ => {}
+            }
+
+            Ok(ShortcutInfo {
+                target,
+                args: Self::from_utf16(&args),
+                working_dir: Self::from_utf16(&working_dir),
+                icon_path: Self::from_utf16(&icon_path),
+                icon_index,
+                description: Self::from_utf16(&description),
+                umid,
+            })
+        })
+    }
+
+    /// Existing shortcuts are only replaced with `force`. The umid makes the windows
+    /// launched from the shortcut be grouped with the app on the taskbar.
+    pub fn create(path: &Path, shortcut: NewShortcut, force: bool) -> Result<()> {
+        if !path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"))
+        {
+            return Err("Shortcut path should end with .lnk".into());
+        }
+        if path.exists() && !force {
+            return Err(format!("{} already exists", path.display()).into());
+        }
+        if !path.parent().is_some_and(|parent| parent.is_dir()) {
+            return Err(format!("Parent folder of {} does not exist", path.display()).into());
+        }
+        if !Path::new(&shortcut.target).exists() {
+            return Err(format!("Target {} does not exist", shortcut.target).into());
+        }
+        if shortcut.umid.as_ref().is_some_and(|umid| umid.len() > 128) {
+            return Err("AppUserModelID can not be longer than 128 characters".into());
+        }
+
+        let args = shortcut.args.as_deref().unwrap_or_default();
+        WindowsApi::create_shortcut(path, &shortcut.target, args, |link| unsafe {
+            if let Some(working_dir) = &shortcut.working_dir {
+                link.SetWorkingDirectory(&HSTRING::from(working_dir))?;
+            }
+            if let Some(icon) = &shortcut.icon {
+                link.SetIconLocation(&HSTRING::from(icon), 0)?;
+            }
+            if let Some(umid) = &shortcut.umid {
+                let store: IPropertyStore = link.cast()?;
+                store.SetValue(&PKEY_AppUserModel_ID, &PROPVARIANT::from(umid.as_str()))?;
+                store.Commit()?;
+            }
+            Ok(())
+        })
+    }
+}
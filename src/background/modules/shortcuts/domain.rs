@@ -0,0 +1,25 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutInfo {
+    /// None for shortcuts to packaged apps, see `umid`
+    pub target: Option<String>,
+    pub args: Option<String>,
+    pub working_dir: Option<String>,
+    pub icon_path: Option<String>,
+    pub icon_index: i32,
+    pub description: Option<String>,
+    /// AppUserModelID of the app, explicit or the packaged app pointed by the shortcut
+    pub umid: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewShortcut {
+    pub target: String,
+    pub args: Option<String>,
+    pub working_dir: Option<String>,
+    /// icon resource, used with index 0
+    pub icon: Option<String>,
+    pub umid: Option<String>,
+}
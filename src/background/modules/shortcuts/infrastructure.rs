@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use crate::error_handler::Result;
+
+use super::{
+    application::Shortcuts,
+    domain::{NewShortcut, ShortcutInfo},
+};
+
+#[tauri::command(async)]
+pub fn resolve_shortcut(path: PathBuf) -> Result<ShortcutInfo> {
+    Shortcuts::resolve(&path)
+}
+
+#[tauri::command(async)]
+pub fn create_shortcut(
+    path: PathBuf,
+    target: String,
+    args: Option<String>,
+    working_dir: Option<String>,
+    icon: Option<String>,
+    umid: Option<String>,
+    force: Option<bool>,
+) -> Result<()> {
+    let shortcut = NewShortcut {
+        target,
+        args,
+        working_dir,
+        icon,
+        umid,
+    };
+    Shortcuts::create(&path, shortcut, force.unwrap_or(false))
+}
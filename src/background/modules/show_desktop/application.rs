@@ -0,0 +1,103 @@
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use windows::Win32::{
+    Foundation::HWND,
+    UI::WindowsAndMessaging::{SW_MINIMIZE, SW_RESTORE},
+};
+
+use crate::{
+    log_error, trace_lock,
+    windows_api::{window::Window, WindowsApi},
+    winevent::WinEvent,
+};
+
+lazy_static! {
+    /// windows minimized by show desktop in front-to-back order
+    static ref MINIMIZED_BY_US: Mutex<Option<Vec<isize>>> = Mutex::new(None);
+    /// minimize/restore events caused by us that are still to be received, by window
+    static ref EXPECTED_EVENTS: Mutex<HashMap<isize, WinEvent>> = Mutex::new(HashMap::new());
+}
+
+pub struct ShowDesktop;
+impl ShowDesktop {
+    /// Returns true if the desktop is now shown
+    pub fn toggle() -> bool {
+        let mut state = trace_lock!(MINIMIZED_BY_US);
+        trace_lock!(EXPECTED_EVENTS).clear();
+        match state.take() {
+            Some(minimized) => {
+                Self::restore(minimized);
+                false
+            }
+            None => {
+                *state = Some(Self::minimize_all());
+                true
+            }
+        }
+    }
+
+    fn minimize_all() -> Vec<isize> {
+        let mut windows = Vec::new();
+        let mut visited = HashSet::new();
+        let mut hwnd = WindowsApi::get_top_window();
+        while !hwnd.is_invalid() && visited.insert(hwnd.0 as isize) {
+            let window = Window::from(hwnd);
+            if window.is_real_window() && !window.is_cloaked() && !window.is_minimized() {
+                windows.push(window.address());
+            }
+            hwnd = WindowsApi::get_next_window(hwnd);
+        }
+
+        let mut expected = trace_lock!(EXPECTED_EVENTS);
+        for addr in &windows {
+            // expected so our own changes don't invalidate the state
+            expected.insert(*addr, WinEvent::SystemMinimizeStart);
+            log_error!(WindowsApi::show_window_async(HWND(*addr as _), SW_MINIMIZE));
+        }
+        windows
+    }
+
+    fn restore(minimized: Vec<isize>) {
+        let mut expected = trace_lock!(EXPECTED_EVENTS);
+        // back to front so the z-order ends as it was before
+        for addr in minimized.into_iter().rev() {
+            let hwnd = HWND(addr as _);
+            if WindowsApi::is_window(hwnd) && WindowsApi::is_iconic(hwnd) {
+                expected.insert(addr, WinEvent::SystemMinimizeEnd);
+                log_error!(WindowsApi::show_window_async(hwnd, SW_RESTORE));
+            }
+        }
+    }
+
+    /// Any state change done by the user while the desktop is shown invalidates the
+    /// remembered set, so the next toggle minimizes again instead of restoring.
+    pub fn on_win_event(event: WinEvent, window: &Window) {
+        if !matches!(
+            event,
+            WinEvent::SystemMinimizeStart | WinEvent::SystemMinimizeEnd
+        ) || window.is_seelen_overlay()
+        {
+            return;
+        }
+        // events are not skipped globally so the wm and the dock keep tracking the real state,
+        // the ones caused by us are only ignored here
+        let mut expected = trace_lock!(EXPECTED_EVENTS);
+        if expected.get(&window.address()) == Some(&event) {
+            expected.remove(&window.address());
+            return;
+        }
+        drop(expected);
+
+        let mut state = trace_lock!(MINIMIZED_BY_US);
+        if state.is_some() {
+            log::trace!(
+                "Show desktop state invalidated by {:?} on {}",
+                event,
+                window
+            );
+            *state = None;
+        }
+    }
+}
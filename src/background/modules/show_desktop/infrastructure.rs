@@ -0,0 +1,7 @@
+use super::application::ShowDesktop;
+
+/// Returns true if the desktop is now shown
+#[tauri::command(async)]
+pub fn toggle_show_desktop() -> bool {
+    ShowDesktop::toggle()
+}
@@ -0,0 +1,148 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use tauri::Emitter;
+use windows::{
+    core::HSTRING,
+    Foundation::{EventRegistrationToken, TypedEventHandler},
+    Media::{
+        Audio::{
+            SetDefaultSpatialAudioFormatStatus, SpatialAudioDeviceConfiguration,
+            SpatialAudioFormatSubtype,
+        },
+        Devices::{AudioDeviceRole, MediaDevice},
+    },
+};
+
+use crate::{error_handler::Result, log_error, seelen::get_app_handle, trace_lock};
+
+use super::domain::{SpatialAudioMode, SpatialAudioState};
+
+static WATCHING: AtomicBool = AtomicBool::new(false);
+
+/// configuration of the current default output device and its change event token
+type WatchedDevice = (SpatialAudioDeviceConfiguration, EventRegistrationToken);
+
+lazy_static! {
+    static ref WATCHED_DEVICE: Mutex<Option<WatchedDevice>> = Mutex::new(None);
+}
+
+pub struct SpatialAudio;
+impl SpatialAudio {
+    fn default_device() -> Result<SpatialAudioDeviceConfiguration> {
+        let device_id = MediaDevice::GetDefaultAudioRenderId(AudioDeviceRole::Default)?;
+        Ok(SpatialAudioDeviceConfiguration::GetForDeviceId(&device_id)?)
+    }
+
+    /// Dolby Atmos has a different format per output kind, ordered by preference
+    fn dolby_formats() -> Result<Vec<HSTRING>> {
+        Ok(vec![
+            SpatialAudioFormatSubtype::DolbyAtmosForHeadphones()?,
+            SpatialAudioFormatSubtype::DolbyAtmosForHomeTheater()?,
+            SpatialAudioFormatSubtype::DolbyAtmosForSpeakers()?,
+        ])
+    }
+
+    fn mode_from_format(format: &HSTRING) -> Result<SpatialAudioMode> {
+        if format.is_empty() {
+            return Ok(SpatialAudioMode::Off);
+        }
+        if *format == SpatialAudioFormatSubtype::WindowsSonic()? {
+            return Ok(SpatialAudioMode::WindowsSonic);
+        }
+        if Self::dolby_formats()?.contains(format) {
+            return Ok(SpatialAudioMode::DolbyAtmos);
+        }
+        Ok(SpatialAudioMode::Other)
+    }
+
+    /// the format to be set on the device for the given mode, if it is supported
+    fn supported_format(
+        device: &SpatialAudioDeviceConfiguration,
+        mode: SpatialAudioMode,
+    ) -> Result<Option<HSTRING>> {
+        let candidates = match mode {
+            SpatialAudioMode::Off => return Ok(Some(HSTRING::new())),
+            SpatialAudioMode::WindowsSonic => vec![SpatialAudioFormatSubtype::WindowsSonic()?],
+            SpatialAudioMode::DolbyAtmos => Self::dolby_formats()?,
+            SpatialAudioMode::Other => return Ok(None),
+        };
+        for format in candidates {
+            if device.IsSpatialAudioFormatSupported(&format)? {
+                return Ok(Some(format));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn get() -> Result<SpatialAudioState> {
+        let device = Self::default_device()?;
+        let mut available_modes = vec![SpatialAudioMode::Off];
+        if device.IsSpatialAudioSupported()? {
+            for mode in [SpatialAudioMode::WindowsSonic, SpatialAudioMode::DolbyAtmos] {
+                if Self::supported_format(&device, mode)?.is_some() {
+                    available_modes.push(mode);
+                }
+            }
+        }
+        Ok(SpatialAudioState {
+            device_id: device.DeviceId()?.to_string(),
+            mode: Self::mode_from_format(&device.ActiveSpatialAudioFormat()?)?,
+            available_modes,
+        })
+    }
+
+    pub fn set(mode: SpatialAudioMode) -> Result<()> {
+        let device = Self::default_device()?;
+        if !device.IsSpatialAudioSupported()? {
+            return Err("The output device exposes no spatial audio options".into());
+        }
+        let format = Self::supported_format(&device, mode)?
+            .ok_or_else(|| format!("{mode:?} is not available for the output device"))?;
+
+        let status = device
+            .SetDefaultSpatialAudioFormatAsync(&format)?
+            .get()?
+            .Status()?;
+        if status != SetDefaultSpatialAudioFormatStatus::Succeeded {
+            return Err(format!("Failed to set spatial audio mode: {status:?}").into());
+        }
+        Ok(())
+    }
+
+    pub fn emit_state() {
+        match Self::get() {
+            Ok(state) => log_error!(get_app_handle().emit("spatial-audio-changed", state)),
+            Err(err) => log::error!("Failed to get spatial audio state: {:?}", err),
+        }
+    }
+
+    /// the configuration change event is per device, so it is moved to the new default device on changes
+    fn watch_default_device() -> Result<()> {
+        let mut watched = trace_lock!(WATCHED_DEVICE);
+        if let Some((device, token)) = watched.take() {
+            device.RemoveConfigurationChanged(token)?;
+        }
+        let device = Self::default_device()?;
+        let token = device.ConfigurationChanged(&TypedEventHandler::new(|_, _| {
+            Self::emit_state();
+            Ok(())
+        }))?;
+        *watched = Some((device, token));
+        Ok(())
+    }
+
+    pub fn watch() -> Result<()> {
+        if WATCHING.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        Self::watch_default_device()?;
+        MediaDevice::DefaultAudioRenderDeviceChanged(&TypedEventHandler::new(|_, _| {
+            log_error!(Self::watch_default_device());
+            Self::emit_state();
+            Ok(())
+        }))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpatialAudioMode {
+    Off,
+    WindowsSonic,
+    DolbyAtmos,
+    /// formats of other vendors (DTS, etc), can be reported but not selected
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpatialAudioState {
+    pub device_id: String,
+    pub mode: SpatialAudioMode,
+    /// modes installed and supported by the device, `Off` is always included
+    pub available_modes: Vec<SpatialAudioMode>,
+}
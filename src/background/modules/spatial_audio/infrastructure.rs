@@ -0,0 +1,21 @@
+use crate::{error_handler::Result, log_error};
+
+use super::{
+    application::SpatialAudio,
+    domain::{SpatialAudioMode, SpatialAudioState},
+};
+
+pub fn register_spatial_audio_events() {
+    log_error!(SpatialAudio::watch());
+    SpatialAudio::emit_state();
+}
+
+#[tauri::command(async)]
+pub fn get_spatial_audio_mode() -> Result<SpatialAudioState> {
+    SpatialAudio::get()
+}
+
+#[tauri::command(async)]
+pub fn set_spatial_audio_mode(mode: SpatialAudioMode) -> Result<()> {
+    SpatialAudio::set(mode)
+}
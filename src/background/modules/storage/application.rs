@@ -0,0 +1,57 @@
+use windows::{
+    core::HSTRING,
+    Win32::Storage::FileSystem::{
+        GetDiskFreeSpaceExW, GetDriveTypeW, GetLogicalDrives, GetVolumeInformationW,
+    },
+};
+
+use super::domain::{Volume, VolumeKind};
+
+pub struct Storage;
+impl Storage {
+    fn from_utf16(buffer: &[u16]) -> Option<String> {
+        let len = buffer.iter().position(|c| *c == 0).unwrap_or(buffer.len());
+        let value = String::from_utf16_lossy(&buffer[..len]);
+        (!value.is_empty()).then_some(value)
+    }
+
+    fn read_volume(mount_point: String) -> Volume {
+        let root = HSTRING::from(&mount_point);
+        let kind = VolumeKind::from(unsafe { GetDriveTypeW(&root) });
+
+        let mut label = [0u16; 261];
+        let mut fs = [0u16; 261];
+        // fails when there is no media on the drive
+        let is_ready = unsafe {
+            GetVolumeInformationW(&root, Some(&mut label), None, None, None, Some(&mut fs))
+        }
+        .is_ok();
+
+        let mut free_bytes = 0u64;
+        let mut total_bytes = 0u64;
+        let has_sizes = is_ready
+            && unsafe {
+                GetDiskFreeSpaceExW(&root, Some(&mut free_bytes), Some(&mut total_bytes), None)
+            }
+            .is_ok();
+
+        Volume {
+            mount_point,
+            label: Self::from_utf16(&label),
+            fs: Self::from_utf16(&fs),
+            kind,
+            is_removable: matches!(kind, VolumeKind::Removable | VolumeKind::CdRom),
+            is_ready,
+            total_bytes: has_sizes.then_some(total_bytes),
+            free_bytes: has_sizes.then_some(free_bytes),
+        }
+    }
+
+    pub fn volumes() -> Vec<Volume> {
+        let drives = unsafe { GetLogicalDrives() };
+        (0..26u8)
+            .filter(|i| drives & (1 << i) != 0)
+            .map(|i| Self::read_volume(format!("{}:\\", (b'A' + i) as char)))
+            .collect()
+    }
+}
@@ -0,0 +1,43 @@
+use serde::Serialize;
+use windows::Win32::System::WindowsProgramming::{
+    DRIVE_CDROM, DRIVE_FIXED, DRIVE_RAMDISK, DRIVE_REMOTE, DRIVE_REMOVABLE,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum VolumeKind {
+    Fixed,
+    Removable,
+    Network,
+    CdRom,
+    RamDisk,
+    Unknown,
+}
+
+impl From<u32> for VolumeKind {
+    fn from(drive_type: u32) -> Self {
+        match drive_type {
+            DRIVE_FIXED => VolumeKind::Fixed,
+            DRIVE_REMOVABLE => VolumeKind::Removable,
+            DRIVE_REMOTE => VolumeKind::Network,
+            DRIVE_CDROM => VolumeKind::CdRom,
+            DRIVE_RAMDISK => VolumeKind::RamDisk,
+            _ => VolumeKind::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Volume {
+    pub mount_point: String,
+    pub label: Option<String>,
+    pub fs: Option<String>,
+    pub kind: VolumeKind,
+    pub is_removable: bool,
+    /// false for drives without media, like an empty card reader
+    pub is_ready: bool,
+    /// None if the drive is not ready
+    pub total_bytes: Option<u64>,
+    /// available to the current user, None if the drive is not ready
+    pub free_bytes: Option<u64>,
+}
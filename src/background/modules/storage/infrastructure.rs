@@ -0,0 +1,6 @@
+use super::{application::Storage, domain::Volume};
+
+#[tauri::command(async)]
+pub fn get_volumes() -> Vec<Volume> {
+    Storage::volumes()
+}
@@ -1,15 +1,25 @@
 use std::sync::Arc;
 
-use crate::{error_handler::Result, log_error, trace_lock};
+use crate::{
+    error_handler::Result,
+    log_error, trace_lock,
+    windows_api::{window::Window, WindowsApi},
+};
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use seelen_core::system_state::UIColors;
 use windows::{
     Foundation::{EventRegistrationToken, TypedEventHandler},
+    Win32::{
+        Foundation::{COLORREF, HWND},
+        Graphics::Dwm::{DWMWA_CAPTION_COLOR, DWMWA_COLOR_DEFAULT, DWMWA_COLOR_NONE},
+    },
     UI::ViewManagement::{UIColorType, UISettings},
 };
 use windows_core::IInspectable;
 
+use super::domain::WindowAccentColor;
+
 lazy_static! {
     pub static ref SYSTEM_SETTINGS: Arc<Mutex<SystemSettings>> = Arc::new(Mutex::new(
         SystemSettings::new().expect("Failed to create settings manager")
@@ -23,6 +33,11 @@ fn color_to_string(color: windows::UI::Color) -> String {
     )
 }
 
+/// the system foreground color is white when Windows is using the dark mode
+pub fn is_dark_mode(colors: &UIColors) -> bool {
+    u8::from_str_radix(colors.foreground.get(1..3).unwrap_or("00"), 16).is_ok_and(|red| red > 127)
+}
+
 enum SettingsEvent {
     ColorChanged,
 }
@@ -91,6 +106,33 @@ impl SystemSettings {
         })
     }
 
+    /// Caption color set by the app via DWM (Windows 11 only), falls back to the system accent.
+    pub fn get_window_accent_color(&self, hwnd: HWND) -> Result<WindowAccentColor> {
+        if !Window::from(hwnd).is_window() {
+            return Err("Invalid window".into());
+        }
+
+        let mut caption = COLORREF(DWMWA_COLOR_DEFAULT);
+        let has_caption_color =
+            WindowsApi::dwm_get_window_attribute(hwnd, DWMWA_CAPTION_COLOR, &mut caption).is_ok()
+                && caption.0 != DWMWA_COLOR_DEFAULT
+                && caption.0 != DWMWA_COLOR_NONE;
+
+        if has_caption_color {
+            // COLORREF is 0x00BBGGRR
+            let [r, g, b, _] = caption.0.to_le_bytes();
+            return Ok(WindowAccentColor {
+                color: format!("#{:02X}{:02X}{:02X}FF", r, g, b),
+                app_specified: true,
+            });
+        }
+
+        Ok(WindowAccentColor {
+            color: color_to_string(self.settings.GetColorValue(UIColorType::Accent)?),
+            app_specified: false,
+        })
+    }
+
     pub fn on_colors_change(&mut self, callback: ColorChangeCallback) {
         self.color_client_callbacks.push(callback);
     }
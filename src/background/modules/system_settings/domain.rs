@@ -1 +1,10 @@
+use serde::Serialize;
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowAccentColor {
+    /// hex color as #RRGGBBAA
+    pub color: String,
+    /// false if the window doesn't set a caption color and the system accent is used instead
+    pub app_specified: bool,
+}
@@ -1,9 +1,10 @@
 use seelen_core::{handlers::SeelenEvent, system_state::UIColors};
 use tauri::Emitter;
+use windows::Win32::Foundation::HWND;
 
 use crate::{error_handler::Result, log_error, seelen::get_app_handle, trace_lock};
 
-use super::application::SYSTEM_SETTINGS;
+use super::{application::SYSTEM_SETTINGS, domain::WindowAccentColor};
 
 fn emit_colors(colors: &UIColors) {
     get_app_handle()
@@ -26,3 +27,8 @@ pub fn release_colors_events() {
 pub fn get_system_colors() -> Result<UIColors> {
     trace_lock!(SYSTEM_SETTINGS).get_colors()
 }
+
+#[tauri::command(async)]
+pub fn get_window_accent_color(hwnd: isize) -> Result<WindowAccentColor> {
+    trace_lock!(SYSTEM_SETTINGS).get_window_accent_color(HWND(hwnd as _))
+}
@@ -0,0 +1,128 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::system_state::UIColors;
+use tauri::{Manager, Webview, Wry};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::system_settings::application::{is_dark_mode, SYSTEM_SETTINGS},
+    seelen::get_app_handle,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::WindowsApi,
+};
+
+use super::domain::{ACCENT, BATTERY, FOCUSED_APP, THEME_MODE};
+
+/// changes are batched and injected at most once per interval to avoid layout thrashing
+const FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
+static THREAD_STARTED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    /// current value of all the variables, used to initialize new webviews
+    static ref VARIABLES: Mutex<HashMap<&'static str, String>> = Mutex::new(HashMap::new());
+    /// changes not yet injected
+    static ref PENDING: Mutex<HashMap<&'static str, String>> = Mutex::new(HashMap::new());
+}
+
+pub struct ThemeVariables;
+impl ThemeVariables {
+    /// sets the initial values and subscribes to the color changes, the rest of variables
+    /// are updated by their own modules.
+    pub fn init() -> Result<()> {
+        let mut settings = trace_lock!(SYSTEM_SETTINGS);
+        Self::on_colors_change(&settings.get_colors()?);
+        settings.on_colors_change(Box::new(Self::on_colors_change));
+        drop(settings);
+
+        let power_status = WindowsApi::get_system_power_status()?;
+        Self::on_battery_change(power_status.BatteryLifePercent);
+        Self::ensure_thread()
+    }
+
+    /// empty values remove the variable
+    pub fn set(name: &'static str, value: String) {
+        let mut variables = trace_lock!(VARIABLES);
+        if variables.get(name) == Some(&value) {
+            return;
+        }
+        variables.insert(name, value.clone());
+        trace_lock!(PENDING).insert(name, value);
+    }
+
+    fn on_colors_change(colors: &UIColors) {
+        Self::set(ACCENT, colors.accent.clone());
+        let mode = if is_dark_mode(colors) {
+            "dark"
+        } else {
+            "light"
+        };
+        Self::set(THEME_MODE, mode.to_string());
+    }
+
+    /// 255 means unknown status (there is no battery)
+    pub fn on_battery_change(percent: u8) {
+        let value = if percent > 100 {
+            String::new()
+        } else {
+            percent.to_string()
+        };
+        Self::set(BATTERY, value);
+    }
+
+    pub fn on_focused_app_change(name: &str) {
+        // quoted so it can be used as a css string
+        Self::set(FOCUSED_APP, serde_json::to_string(name).unwrap_or_default());
+    }
+
+    fn script(variables: &HashMap<&'static str, String>) -> String {
+        let mut script = String::from(
+            "requestAnimationFrame(() => { const style = document.documentElement.style;",
+        );
+        for (name, value) in variables {
+            let value = serde_json::to_string(value).unwrap_or_default();
+            if value == "\"\"" {
+                script.push_str(&format!("style.removeProperty('{name}');"));
+            } else {
+                script.push_str(&format!("style.setProperty('{name}', {value});"));
+            }
+        }
+        script.push_str("});");
+        script
+    }
+
+    fn flush() {
+        let pending = std::mem::take(&mut *trace_lock!(PENDING));
+        if pending.is_empty() {
+            return;
+        }
+        let script = Self::script(&pending);
+        for webview in get_app_handle().webview_windows().values() {
+            log_error!(webview.eval(&script));
+        }
+    }
+
+    pub fn on_page_load(webview: &Webview<Wry>) {
+        let script = Self::script(&trace_lock!(VARIABLES));
+        log_error!(webview.eval(&script));
+    }
+
+    fn ensure_thread() -> Result<()> {
+        if THREAD_STARTED.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        spawn_named_thread("Theme Variables", || loop {
+            std::thread::sleep(FLUSH_INTERVAL);
+            Self::flush();
+        })?;
+        Ok(())
+    }
+}
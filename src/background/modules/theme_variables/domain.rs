@@ -0,0 +1,14 @@
+//! CSS custom properties injected on the root element of every Seelen UI webview,
+//! so themes can react to the system state without scripts:
+//!
+//! | variable                | value                                                          |
+//! | ----------------------- | -------------------------------------------------------------- |
+//! | `--seelen-accent`       | system accent color as `#RRGGBBAA`                             |
+//! | `--seelen-theme-mode`   | `dark` or `light`, following the apps mode of Windows          |
+//! | `--seelen-battery`      | battery charge percent as a number (0-100), unset without one  |
+//! | `--seelen-focused-app`  | quoted display name of the focused app, usable on `content`    |
+
+pub const ACCENT: &str = "--seelen-accent";
+pub const THEME_MODE: &str = "--seelen-theme-mode";
+pub const BATTERY: &str = "--seelen-battery";
+pub const FOCUSED_APP: &str = "--seelen-focused-app";
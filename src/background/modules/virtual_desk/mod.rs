@@ -29,6 +29,9 @@ trait VirtualDesktopTrait: std::fmt::Debug + Clone {
 
 trait VirtualDesktopManagerTrait {
     fn create_desktop(&self) -> Result<()>;
+    /// windows on the removed desktop are moved to `fallback`
+    fn remove_desktop(&self, idx: usize, fallback: usize) -> Result<()>;
+    fn has_windows(&self, idx: usize) -> Result<bool>;
 
     fn get(&self, idx: usize) -> Result<Option<VirtualDesktop>>;
     fn get_all(&self) -> Result<Vec<VirtualDesktop>>;
@@ -99,6 +102,56 @@ impl VirtualDesktopManager {
         }
     }
 
+    pub fn remove_desktop(&self, idx: usize, fallback: usize) -> Result<()> {
+        match self {
+            VirtualDesktopManager::Native(m) => m.remove_desktop(idx, fallback),
+            VirtualDesktopManager::Seelen(m) => m.remove_desktop(idx, fallback),
+        }
+    }
+
+    pub fn has_windows(&self, idx: usize) -> Result<bool> {
+        match self {
+            VirtualDesktopManager::Native(m) => m.has_windows(idx),
+            VirtualDesktopManager::Seelen(m) => m.has_windows(idx),
+        }
+    }
+
+    pub fn get_desktop_count(&self) -> Result<usize> {
+        Ok(self.get_all()?.len())
+    }
+
+    /// Creates or removes trailing desktops until there are `count` of them.
+    /// Desktops with windows are only removed if `force` is true, their windows
+    /// are moved to the last remaining desktop.
+    ///
+    /// On unsupported Windows builds the strategy is already forced to Seelen
+    /// on settings load, so this works on the internal workspaces instead.
+    pub fn set_desktop_count(&self, count: usize, force: bool) -> Result<()> {
+        if count == 0 {
+            return Err("At least one desktop is required".into());
+        }
+
+        let current = self.get_desktop_count()?;
+        for _ in current..count {
+            self.create_desktop()?;
+        }
+
+        if !force {
+            for idx in count..current {
+                if self.has_windows(idx)? {
+                    return Err(
+                        format!("Desktop {} has windows, use force to remove it", idx + 1).into(),
+                    );
+                }
+            }
+        }
+
+        for idx in (count..current).rev() {
+            self.remove_desktop(idx, count - 1)?;
+        }
+        Ok(())
+    }
+
     pub fn get(&self, idx: usize) -> Result<Option<VirtualDesktop>> {
         match self {
             VirtualDesktopManager::Native(m) => m.get(idx),
@@ -2,7 +2,11 @@ use parking_lot::Mutex;
 use windows::Win32::Foundation::HWND;
 use winvd::{Desktop, DesktopEvent, DesktopEventThread};
 
-use crate::{error_handler::Result, trace_lock};
+use crate::{
+    error_handler::Result,
+    trace_lock,
+    windows_api::{window::Window, WindowEnumerator},
+};
 
 use super::{VirtualDesktop, VirtualDesktopEvent, VirtualDesktopManagerTrait, VirtualDesktopTrait};
 
@@ -57,6 +61,24 @@ impl VirtualDesktopManagerTrait for NativeVirtualDesktopManager {
         Ok(())
     }
 
+    fn remove_desktop(&self, idx: usize, fallback: usize) -> Result<()> {
+        winvd::remove_desktop(idx as u32, fallback as u32)?;
+        Ok(())
+    }
+
+    fn has_windows(&self, idx: usize) -> Result<bool> {
+        let desktop_id = winvd::get_desktop(idx as u32).get_id()?;
+        let mut found = false;
+        WindowEnumerator::new().for_each(|hwnd| {
+            if !found && Window::from(hwnd).is_real_window() {
+                found = winvd::get_desktop_by_window(hwnd)
+                    .and_then(|d| d.get_id())
+                    .is_ok_and(|id| id == desktop_id);
+            }
+        })?;
+        Ok(found)
+    }
+
     fn get(&self, idx: usize) -> Result<Option<VirtualDesktop>> {
         let desktop = winvd::get_desktops()?.get(idx).map(|d| d.into());
         Ok(desktop)
@@ -170,6 +170,55 @@ impl VirtualDesktopManagerTrait for SeelenWorkspacesManager {
         Ok(())
     }
 
+    fn remove_desktop(&self, idx: usize, fallback: usize) -> Result<()> {
+        if idx == fallback {
+            return Err("Can not use the removed workspace as fallback".into());
+        }
+
+        if self.current_idx() == idx {
+            self.switch_to(fallback)?;
+        }
+
+        let mut workspaces = trace_lock!(self.workspaces);
+        if idx >= workspaces.len() || fallback >= workspaces.len() {
+            return Err(none_err());
+        }
+
+        let destroyed = workspaces.remove(idx);
+        // indexes after the removed one are shifted
+        let fallback = if fallback > idx {
+            fallback - 1
+        } else {
+            fallback
+        };
+        let current = self.current_idx();
+        if current > idx {
+            self.current.store(current - 1, Ordering::SeqCst);
+        }
+
+        let fallback_workspace = workspaces.get_mut(fallback).ok_or_else(none_err)?;
+        fallback_workspace
+            .windows
+            .extend(destroyed.windows.iter().copied());
+        if self.current_idx() == fallback {
+            fallback_workspace.restore();
+        }
+
+        self.emit(VirtualDesktopEvent::DesktopDestroyed {
+            destroyed: destroyed.into(),
+            fallback: (&*fallback_workspace).into(),
+        })
+    }
+
+    fn has_windows(&self, idx: usize) -> Result<bool> {
+        let workspaces = trace_lock!(self.workspaces);
+        let workspace = workspaces.get(idx).ok_or_else(none_err)?;
+        Ok(workspace
+            .windows
+            .iter()
+            .any(|w| WindowsApi::is_window(HWND(*w as _))))
+    }
+
     fn get(&self, idx: usize) -> Result<Option<VirtualDesktop>> {
         if let Some(workspace) = trace_lock!(self.workspaces).get_mut(idx) {
             return Ok(Some(workspace.clone().into()));
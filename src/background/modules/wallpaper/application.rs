@@ -0,0 +1,75 @@
+use std::path::{Path, PathBuf};
+
+use windows::{
+    core::{HSTRING, PCWSTR, PWSTR},
+    Win32::{
+        System::Com::CoTaskMemFree,
+        UI::Shell::{DesktopWallpaper, IDesktopWallpaper},
+    },
+};
+
+use crate::{error_handler::Result, windows_api::Com};
+
+use super::domain::WallpaperStyle;
+
+pub struct SystemWallpaper;
+impl SystemWallpaper {
+    /// strings returned by IDesktopWallpaper are allocated by COM
+    unsafe fn take_string(value: PWSTR) -> Result<String> {
+        let result = value.to_string();
+        CoTaskMemFree(Some(value.0 as _));
+        Ok(result?)
+    }
+
+    /// IDesktopWallpaper identifies monitors by device path, which is the same id
+    /// used by Seelen with the `\\?\` prefix.
+    unsafe fn monitor_path(wallpaper: &IDesktopWallpaper, device_id: &str) -> Result<HSTRING> {
+        for index in 0..wallpaper.GetMonitorDevicePathCount()? {
+            let path = Self::take_string(wallpaper.GetMonitorDevicePathAt(index)?)?;
+            if path
+                .trim_start_matches(r"\\?\")
+                .eq_ignore_ascii_case(device_id.trim_start_matches(r"\\?\"))
+            {
+                return Ok(HSTRING::from(path));
+            }
+        }
+        Err(format!("Monitor not found: {device_id}").into())
+    }
+
+    fn validate_image(path: &Path) -> Result<()> {
+        if !path.is_file() {
+            return Err(format!("{} is not a file", path.display()).into());
+        }
+        image::image_dimensions(path)
+            .map_err(|err| format!("{} is not a valid image: {err}", path.display()))?;
+        Ok(())
+    }
+
+    /// Sets the wallpaper of the OS, visible to other apps and when Seelen is closed.
+    /// Without monitor the wallpaper is applied to all of them. Returns the previous
+    /// wallpaper, None if there was none or if each monitor had a different one.
+    ///
+    /// The style is global as Windows doesn't support a style per monitor.
+    pub fn set(
+        path: &Path,
+        style: WallpaperStyle,
+        monitor_id: Option<&str>,
+    ) -> Result<Option<PathBuf>> {
+        Self::validate_image(path)?;
+        Com::run_with_context(|| unsafe {
+            let wallpaper: IDesktopWallpaper = Com::create_instance(&DesktopWallpaper)?;
+            let monitor = match monitor_id {
+                Some(id) => Some(Self::monitor_path(&wallpaper, id)?),
+                None => None,
+            };
+            let monitor = monitor
+                .as_ref()
+                .map_or(PCWSTR::null(), |monitor| PCWSTR(monitor.as_ptr()));
+
+            let previous = Self::take_string(wallpaper.GetWallpaper(monitor)?)?;
+            wallpaper.SetPosition(style.into())?;
+            wallpaper.SetWallpaper(monitor, &HSTRING::from(path))?;
+            Ok((!previous.is_empty()).then(|| PathBuf::from(previous)))
+        })
+    }
+}
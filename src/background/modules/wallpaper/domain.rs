@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use windows::Win32::UI::Shell::{
+    DESKTOP_WALLPAPER_POSITION, DWPOS_CENTER, DWPOS_FILL, DWPOS_FIT, DWPOS_SPAN, DWPOS_STRETCH,
+    DWPOS_TILE,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WallpaperStyle {
+    /// covers the monitor keeping the aspect ratio, cropping the overflow
+    #[default]
+    Fill,
+    /// the whole image is visible keeping the aspect ratio
+    Fit,
+    Stretch,
+    Center,
+    Tile,
+    /// a single image across all the monitors
+    Span,
+}
+
+impl From<WallpaperStyle> for DESKTOP_WALLPAPER_POSITION {
+    fn from(style: WallpaperStyle) -> Self {
+        match style {
+            WallpaperStyle::Fill => DWPOS_FILL,
+            WallpaperStyle::Fit => DWPOS_FIT,
+            WallpaperStyle::Stretch => DWPOS_STRETCH,
+            WallpaperStyle::Center => DWPOS_CENTER,
+            WallpaperStyle::Tile => DWPOS_TILE,
+            WallpaperStyle::Span => DWPOS_SPAN,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use std::path::PathBuf;
+
+use crate::error_handler::Result;
+
+use super::{application::SystemWallpaper, domain::WallpaperStyle};
+
+/// Sets the OS wallpaper with style and optionally for a single monitor, returns the
+/// previous one. Use it when other apps should see the wallpaper, `state_set_wallpaper`
+/// is the simple variant used by the Seelen wall that applies it everywhere.
+#[tauri::command(async)]
+pub fn set_system_wallpaper(
+    path: PathBuf,
+    style: WallpaperStyle,
+    monitor_id: Option<String>,
+) -> Result<Option<PathBuf>> {
+    SystemWallpaper::set(&path, style, monitor_id.as_deref())
+}
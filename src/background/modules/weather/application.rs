@@ -0,0 +1,94 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+use crate::{error_handler::Result, state::application::FULL_STATE, trace_lock};
+
+use super::domain::{condition_from_wmo_code, OpenMeteoResponse, Units, Weather, WeatherConfig};
+
+lazy_static! {
+    static ref WEATHER_CACHE: Mutex<HashMap<String, Weather>> = Mutex::new(HashMap::new());
+}
+
+pub struct WeatherManager;
+impl WeatherManager {
+    pub fn config() -> WeatherConfig {
+        FULL_STATE.load().extended_settings().weather.clone()
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_secs()
+    }
+
+    /// rounded to ~1km to avoid cache misses by gps jitter
+    fn cache_key(lat: f64, lon: f64, units: Units) -> String {
+        format!("{:.2},{:.2},{:?}", lat, lon, units)
+    }
+
+    async fn fetch(config: &WeatherConfig, lat: f64, lon: f64, units: Units) -> Result<Weather> {
+        let url = config
+            .provider_url
+            .replace("{lat}", &lat.to_string())
+            .replace("{lon}", &lon.to_string())
+            .replace("{units}", units.as_open_meteo_param());
+
+        let response = tauri_plugin_http::reqwest::get(url)
+            .await?
+            .error_for_status()?;
+        let data: OpenMeteoResponse = serde_json::from_str(&response.text().await?)?;
+
+        Ok(Weather {
+            temp: data.current.temperature_2m,
+            condition: condition_from_wmo_code(data.current.weather_code).to_owned(),
+            icon_code: data.current.weather_code,
+            updated_at: Self::now(),
+            stale: false,
+        })
+    }
+
+    pub async fn get(lat: f64, lon: f64, units: Units) -> Result<Weather> {
+        let config = Self::config();
+        let key = Self::cache_key(lat, lon, units);
+        let cached = trace_lock!(WEATHER_CACHE).get(&key).cloned();
+
+        if !config.enabled {
+            return match cached {
+                Some(weather) => Ok(Weather {
+                    stale: true,
+                    ..weather
+                }),
+                None => Err("Network weather is disabled".into()),
+            };
+        }
+
+        if let Some(weather) = &cached {
+            if Self::now().saturating_sub(weather.updated_at) < config.ttl {
+                return Ok(weather.clone());
+            }
+        }
+
+        match Self::fetch(&config, lat, lon, units).await {
+            Ok(weather) => {
+                trace_lock!(WEATHER_CACHE).insert(key, weather.clone());
+                Ok(weather)
+            }
+            Err(err) => match cached {
+                Some(weather) => {
+                    log::warn!("Failed to fetch weather, using cached value: {:?}", err);
+                    Ok(Weather {
+                        stale: true,
+                        ..weather
+                    })
+                }
+                None => Err(err),
+            },
+        }
+    }
+}
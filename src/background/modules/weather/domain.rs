@@ -0,0 +1,78 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Units {
+    Metric,
+    Imperial,
+}
+
+impl Units {
+    pub fn as_open_meteo_param(&self) -> &'static str {
+        match self {
+            Units::Metric => "celsius",
+            Units::Imperial => "fahrenheit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Weather {
+    pub temp: f64,
+    pub condition: String,
+    /// WMO weather interpretation code
+    pub icon_code: u32,
+    /// unix timestamp in seconds
+    pub updated_at: u64,
+    /// true when the network request failed and this is the last cached value
+    pub stale: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WeatherConfig {
+    /// if disabled no network request will be done at all
+    pub enabled: bool,
+    /// url template of an Open-Meteo compatible provider,
+    /// `{lat}`, `{lon}` and `{units}` will be replaced on each request
+    pub provider_url: String,
+    /// time in seconds to reuse the cached value before fetching again
+    pub ttl: u64,
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            provider_url: "https://api.open-meteo.com/v1/forecast?latitude={lat}&longitude={lon}&current=temperature_2m,weather_code&temperature_unit={units}".to_owned(),
+            ttl: 600,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OpenMeteoCurrent {
+    pub temperature_2m: f64,
+    pub weather_code: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OpenMeteoResponse {
+    pub current: OpenMeteoCurrent,
+}
+
+/// https://open-meteo.com/en/docs#weathervariables (WMO Weather interpretation codes)
+pub fn condition_from_wmo_code(code: u32) -> &'static str {
+    match code {
+        0 => "clear",
+        1..=3 => "cloudy",
+        45 | 48 => "fog",
+        51..=57 => "drizzle",
+        61..=67 | 80..=82 => "rain",
+        71..=77 | 85 | 86 => "snow",
+        95..=99 => "thunderstorm",
+        _ => "unknown",
+    }
+}
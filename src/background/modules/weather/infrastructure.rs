@@ -0,0 +1,11 @@
+use crate::error_handler::Result;
+
+use super::{
+    application::WeatherManager,
+    domain::{Units, Weather},
+};
+
+#[tauri::command(async)]
+pub async fn get_weather(lat: f64, lon: f64, units: Units) -> Result<Weather> {
+    WeatherManager::get(lat, lon, units).await
+}
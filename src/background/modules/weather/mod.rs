@@ -0,0 +1,3 @@
+mod application;
+pub mod domain;
+pub mod infrastructure;
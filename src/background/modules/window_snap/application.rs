@@ -0,0 +1,141 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use windows::Win32::{
+    Foundation::{HWND, RECT},
+    Graphics::Gdi::HMONITOR,
+    UI::WindowsAndMessaging::SWP_NOACTIVATE,
+};
+
+use crate::{
+    error_handler::Result,
+    seelen_bar::FancyToolbar,
+    trace_lock,
+    utils::constants::SEELEN_COMMON,
+    windows_api::{monitor::Monitor, window::Window, WindowsApi},
+    winevent::WinEvent,
+};
+
+use super::domain::{SnapZone, ZoneLayout};
+
+lazy_static! {
+    /// outer rect of the window before it was snapped for first time
+    static ref PRE_SNAP_RECTS: Mutex<HashMap<isize, RECT>> = Mutex::new(HashMap::new());
+}
+
+pub struct WindowSnap;
+impl WindowSnap {
+    /// grows the rect to the minimum size of the window, towards the inside of `area`
+    fn fit_min_size(rect: &mut RECT, area: &RECT, (min_width, min_height): (i32, i32)) {
+        if rect.right - rect.left < min_width {
+            if rect.right >= area.right {
+                rect.left = rect.right - min_width;
+            } else {
+                rect.right = rect.left + min_width;
+            }
+        }
+        if rect.bottom - rect.top < min_height {
+            if rect.bottom >= area.bottom {
+                rect.top = rect.bottom - min_height;
+            } else {
+                rect.bottom = rect.top + min_height;
+            }
+        }
+    }
+
+    /// `get_zone` receives the monitor of the window and its work area
+    fn snap_with(hwnd: HWND, get_zone: impl FnOnce(HMONITOR, &RECT) -> Result<RECT>) -> Result<()> {
+        if !WindowsApi::is_window(hwnd) {
+            return Err("Invalid window".into());
+        }
+
+        let monitor = WindowsApi::monitor_from_window(hwnd);
+        let work_area = FancyToolbar::get_work_area_by_monitor(monitor)?;
+        let zone_rect = get_zone(monitor, &work_area)?;
+
+        if WindowsApi::is_iconic(hwnd) || WindowsApi::is_maximized(hwnd) {
+            WindowsApi::unmaximize_window(hwnd)?;
+        }
+
+        let mut pre_snap = trace_lock!(PRE_SNAP_RECTS);
+        if let Entry::Vacant(entry) = pre_snap.entry(hwnd.0 as isize) {
+            entry.insert(WindowsApi::get_outer_window_rect(hwnd)?);
+        }
+
+        // invisible borders are part of the outer rect
+        let shadow = WindowsApi::shadow_rect(hwnd)?;
+        let mut rect = RECT {
+            left: zone_rect.left + shadow.left,
+            top: zone_rect.top + shadow.top,
+            right: zone_rect.right + shadow.right,
+            bottom: zone_rect.bottom + shadow.bottom,
+        };
+        Self::fit_min_size(&mut rect, &work_area, WindowsApi::get_min_track_size(hwnd));
+
+        WindowsApi::set_position(hwnd, None, &rect, SWP_NOACTIVATE)
+    }
+
+    pub fn snap(hwnd: HWND, zone: SnapZone) -> Result<()> {
+        Self::snap_with(hwnd, |_, work_area| Ok(zone.rect_in(work_area)))
+    }
+
+    pub fn snap_to_named_zone(hwnd: HWND, layout_id: &str, zone_name: &str) -> Result<()> {
+        let layouts = SnapLayouts::load()?;
+        let layout = layouts
+            .iter()
+            .find(|l| l.id == layout_id)
+            .ok_or_else(|| format!("Snap layout {} not found", layout_id))?;
+        let zone = layout
+            .zones
+            .iter()
+            .find(|z| z.name == zone_name)
+            .ok_or_else(|| format!("Zone {} not found on layout {}", zone_name, layout_id))?;
+
+        Self::snap_with(hwnd, |monitor, work_area| {
+            if let Some(device_id) = &layout.monitor {
+                if &Monitor::from(monitor).device_id()? != device_id {
+                    return Err(format!(
+                        "Snap layout {} is not available on this monitor",
+                        layout_id
+                    )
+                    .into());
+                }
+            }
+            Ok(zone.rect_in(work_area))
+        })
+    }
+
+    /// restores the rect the window had before being snapped
+    pub fn unsnap(hwnd: HWND) -> Result<()> {
+        let rect = trace_lock!(PRE_SNAP_RECTS)
+            .remove(&(hwnd.0 as isize))
+            .ok_or("Window is not snapped")?;
+        if !WindowsApi::is_window(hwnd) {
+            return Err("Invalid window".into());
+        }
+        WindowsApi::set_position(hwnd, None, &rect, SWP_NOACTIVATE)
+    }
+
+    pub fn on_win_event(event: WinEvent, window: &Window) {
+        if event == WinEvent::ObjectDestroy {
+            trace_lock!(PRE_SNAP_RECTS).remove(&(window.hwnd().0 as isize));
+        }
+    }
+}
+
+pub struct SnapLayouts;
+impl SnapLayouts {
+    /// read on each call so edits to the file are applied without restarting
+    pub fn load() -> Result<Vec<ZoneLayout>> {
+        let path = SEELEN_COMMON.snap_layouts_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let layouts: Vec<ZoneLayout> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        for layout in &layouts {
+            layout.validate()?;
+        }
+        Ok(layouts)
+    }
+}
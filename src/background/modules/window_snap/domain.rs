@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use windows::Win32::Foundation::RECT;
+
+use crate::error_handler::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapZone {
+    LeftHalf,
+    RightHalf,
+    TopLeftQuarter,
+    TopRightQuarter,
+    BottomLeftQuarter,
+    BottomRightQuarter,
+    LeftThird,
+    CenterThird,
+    RightThird,
+}
+
+impl SnapZone {
+    /// (first column, columns used, total columns, first row, rows used, total rows)
+    fn grid(&self) -> (i32, i32, i32, i32, i32, i32) {
+        match self {
+            SnapZone::LeftHalf => (0, 1, 2, 0, 1, 1),
+            SnapZone::RightHalf => (1, 1, 2, 0, 1, 1),
+            SnapZone::TopLeftQuarter => (0, 1, 2, 0, 1, 2),
+            SnapZone::TopRightQuarter => (1, 1, 2, 0, 1, 2),
+            SnapZone::BottomLeftQuarter => (0, 1, 2, 1, 1, 2),
+            SnapZone::BottomRightQuarter => (1, 1, 2, 1, 1, 2),
+            SnapZone::LeftThird => (0, 1, 3, 0, 1, 1),
+            SnapZone::CenterThird => (1, 1, 3, 0, 1, 1),
+            SnapZone::RightThird => (2, 1, 3, 0, 1, 1),
+        }
+    }
+
+    /// Rect of the zone inside `area`, cells share edges so there are no gaps between zones
+    pub fn rect_in(&self, area: &RECT) -> RECT {
+        let (col, cols, total_cols, row, rows, total_rows) = self.grid();
+        let width = area.right - area.left;
+        let height = area.bottom - area.top;
+        RECT {
+            left: area.left + width * col / total_cols,
+            top: area.top + height * row / total_rows,
+            right: area.left + width * (col + cols) / total_cols,
+            bottom: area.top + height * (row + rows) / total_rows,
+        }
+    }
+}
+
+/// Named region of a custom layout, as fractions of the monitor work area
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneRegion {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ZoneRegion {
+    fn is_inside_bounds(&self) -> bool {
+        let in_unit = |v: f64| (0.0..=1.0).contains(&v);
+        in_unit(self.x)
+            && in_unit(self.y)
+            && self.width > 0.0
+            && self.height > 0.0
+            && in_unit(self.x + self.width)
+            && in_unit(self.y + self.height)
+    }
+
+    pub fn rect_in(&self, area: &RECT) -> RECT {
+        let width = (area.right - area.left) as f64;
+        let height = (area.bottom - area.top) as f64;
+        RECT {
+            left: area.left + (width * self.x).round() as i32,
+            top: area.top + (height * self.y).round() as i32,
+            right: area.left + (width * (self.x + self.width)).round() as i32,
+            bottom: area.top + (height * (self.y + self.height)).round() as i32,
+        }
+    }
+}
+
+/// Custom snap layout, zones can overlap to allow layered layouts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneLayout {
+    pub id: String,
+    /// device id of the monitor, if set the layout is only available on that monitor
+    #[serde(default)]
+    pub monitor: Option<String>,
+    pub zones: Vec<ZoneRegion>,
+}
+
+impl ZoneLayout {
+    pub fn validate(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        for zone in &self.zones {
+            if !names.insert(zone.name.as_str()) {
+                return Err(format!("Duplicated zone {} on layout {}", zone.name, self.id).into());
+            }
+            if !zone.is_inside_bounds() {
+                return Err(format!(
+                    "Zone {} on layout {} is out of the 0..1 bounds",
+                    zone.name, self.id
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
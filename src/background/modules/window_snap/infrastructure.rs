@@ -0,0 +1,28 @@
+use windows::Win32::Foundation::HWND;
+
+use crate::error_handler::Result;
+
+use super::{
+    application::{SnapLayouts, WindowSnap},
+    domain::{SnapZone, ZoneLayout},
+};
+
+#[tauri::command(async)]
+pub fn snap_window(hwnd: isize, zone: SnapZone) -> Result<()> {
+    WindowSnap::snap(HWND(hwnd as _), zone)
+}
+
+#[tauri::command(async)]
+pub fn unsnap_window(hwnd: isize) -> Result<()> {
+    WindowSnap::unsnap(HWND(hwnd as _))
+}
+
+#[tauri::command(async)]
+pub fn snap_window_to_named_zone(hwnd: isize, layout_id: String, zone_name: String) -> Result<()> {
+    WindowSnap::snap_to_named_zone(HWND(hwnd as _), &layout_id, &zone_name)
+}
+
+#[tauri::command(async)]
+pub fn get_snap_layouts() -> Result<Vec<ZoneLayout>> {
+    SnapLayouts::load()
+}
@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use tauri::{Emitter, Manager, Webview, Wry};
+use windows::Win32::{
+    Foundation::{COLORREF, HWND},
+    Graphics::Dwm::{
+        DwmExtendFrameIntoClientArea, DWMWA_BORDER_COLOR, DWMWA_COLOR_DEFAULT,
+        DWMWA_SYSTEMBACKDROP_TYPE, DWMWA_WINDOW_CORNER_PREFERENCE, DWM_SYSTEMBACKDROP_TYPE,
+        DWM_WINDOW_CORNER_PREFERENCE,
+    },
+    UI::Controls::MARGINS,
+    UI::WindowsAndMessaging::{
+        HWND_NOTOPMOST, HWND_TOPMOST, LAYERED_WINDOW_ATTRIBUTES_FLAGS, LWA_ALPHA, LWA_COLORKEY,
+        SWP_FRAMECHANGED, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, WS_EX_LAYERED, WS_EX_TOPMOST,
+        WS_EX_TRANSPARENT,
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    hook::HookManager,
+    log_error,
+    seelen::get_app_handle,
+    state::{application::FULL_STATE, infrastructure::update_extended_settings},
+    trace_lock,
+    utils::{is_system_backdrop_supported, is_windows_11},
+    windows_api::WindowsApi,
+    winevent::WinEvent,
+};
+
+use super::domain::{BackdropKind, BackdropState, Color, CornerPreference, WindowTopmostChanged};
+
+/// below this the window becomes practically invisible and unusable
+pub const MIN_WINDOW_ALPHA: u8 = 25;
+
+lazy_static! {
+    static ref ORIGINAL_LAYERING: Mutex<HashMap<isize, OriginalLayering>> =
+        Mutex::new(HashMap::new());
+    /// Seelen webviews on click-through mode, by label
+    static ref CLICK_THROUGH: Mutex<HashMap<String, ClickThroughState>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Layering state of the window before we changed its opacity for first time
+#[derive(Debug, Clone, Copy)]
+enum OriginalLayering {
+    NotLayered,
+    Layered {
+        key: COLORREF,
+        alpha: u8,
+        flags: LAYERED_WINDOW_ATTRIBUTES_FLAGS,
+    },
+}
+
+impl OriginalLayering {
+    fn read(hwnd: HWND) -> Result<Self> {
+        if !WindowsApi::get_ex_styles(hwnd).contains(WS_EX_LAYERED) {
+            return Ok(Self::NotLayered);
+        }
+        match WindowsApi::get_layered_attributes(hwnd) {
+            Some((key, alpha, flags)) => Ok(Self::Layered { key, alpha, flags }),
+            // the window uses `UpdateLayeredWindow`, overriding it would break its rendering
+            None => Err("The window manages its own transparency".into()),
+        }
+    }
+
+    fn restore(&self, hwnd: HWND) -> Result<()> {
+        match *self {
+            Self::NotLayered => {
+                WindowsApi::set_layered_attributes(hwnd, COLORREF(0), 255, LWA_ALPHA)?;
+                WindowsApi::set_ex_styles(hwnd, WindowsApi::get_ex_styles(hwnd) & !WS_EX_LAYERED);
+            }
+            Self::Layered { key, alpha, flags } => {
+                WindowsApi::set_layered_attributes(hwnd, key, alpha, flags)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What is needed to revert the click-through mode
+#[derive(Debug, Clone, Copy)]
+struct ClickThroughState {
+    /// the window was not layered before enabling click-through
+    added_layered: bool,
+    /// the window had the input focus before enabling click-through
+    was_foreground: bool,
+}
+
+pub struct WindowStyle;
+impl WindowStyle {
+    pub fn set_opacity(hwnd: HWND, alpha: u8) -> Result<()> {
+        let mut originals = trace_lock!(ORIGINAL_LAYERING);
+        let id = hwnd.0 as isize;
+
+        if !WindowsApi::is_window(hwnd) {
+            originals.remove(&id);
+            return Err("Invalid window".into());
+        }
+
+        if alpha == 255 {
+            if let Some(original) = originals.remove(&id) {
+                original.restore(hwnd)?;
+            }
+            return Ok(());
+        }
+
+        let alpha = alpha.max(MIN_WINDOW_ALPHA);
+        let original = match originals.get(&id) {
+            Some(original) => *original,
+            None => {
+                let original = OriginalLayering::read(hwnd)?;
+                originals.insert(id, original);
+                original
+            }
+        };
+
+        match original {
+            OriginalLayering::NotLayered => {
+                WindowsApi::set_ex_styles(hwnd, WindowsApi::get_ex_styles(hwnd) | WS_EX_LAYERED);
+                WindowsApi::set_layered_attributes(hwnd, COLORREF(0), alpha, LWA_ALPHA)?;
+            }
+            OriginalLayering::Layered { key, flags, .. } => {
+                // keep the color key of the window if it uses one
+                let flags = (flags & LWA_COLORKEY) | LWA_ALPHA;
+                WindowsApi::set_layered_attributes(hwnd, key, alpha, flags)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_topmost(hwnd: HWND) -> bool {
+        WindowsApi::get_ex_styles(hwnd).contains(WS_EX_TOPMOST)
+    }
+
+    pub fn set_topmost(hwnd: HWND, topmost: bool) -> Result<()> {
+        if !WindowsApi::is_window(hwnd) {
+            return Err("Invalid window".into());
+        }
+
+        let order = if topmost {
+            HWND_TOPMOST
+        } else {
+            HWND_NOTOPMOST
+        };
+        // synchronous, so the WM reads the updated style when handling the synthetic event
+        WindowsApi::set_position(
+            hwnd,
+            Some(order),
+            &Default::default(),
+            SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+        )?;
+
+        let event = if topmost {
+            WinEvent::SyntheticTopmostStart
+        } else {
+            WinEvent::SyntheticTopmostEnd
+        };
+        HookManager::emit_event(event, hwnd);
+
+        get_app_handle().emit(
+            "window-topmost-changed",
+            WindowTopmostChanged {
+                hwnd: hwnd.0 as isize,
+                topmost,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// No-op on Windows 10, corner preference is only supported by Windows 11.
+    pub fn set_corner_preference(hwnd: HWND, pref: CornerPreference) -> Result<()> {
+        if !WindowsApi::is_window(hwnd) {
+            return Err("Invalid window".into());
+        }
+        if !is_windows_11() {
+            return Ok(());
+        }
+        let pref = DWM_WINDOW_CORNER_PREFERENCE::from(pref);
+        WindowsApi::dwm_set_window_attribute(hwnd, DWMWA_WINDOW_CORNER_PREFERENCE, &pref)
+    }
+
+    /// `None` restores the system border color. No-op on Windows 10.
+    pub fn set_border_color(hwnd: HWND, color: Option<Color>) -> Result<()> {
+        if !WindowsApi::is_window(hwnd) {
+            return Err("Invalid window".into());
+        }
+        if !is_windows_11() {
+            return Ok(());
+        }
+        let color = color.map_or(COLORREF(DWMWA_COLOR_DEFAULT), COLORREF::from);
+        WindowsApi::dwm_set_window_attribute(hwnd, DWMWA_BORDER_COLOR, &color)
+    }
+
+    /// returns the new topmost state
+    pub fn toggle_topmost(hwnd: HWND) -> Result<bool> {
+        let topmost = !Self::is_topmost(hwnd);
+        Self::set_topmost(hwnd, topmost)?;
+        Ok(topmost)
+    }
+}
+
+pub struct WidgetBackdrops;
+impl WidgetBackdrops {
+    /// backdrop chosen for the Seelen webview, by label
+    fn stored(label: &str) -> Option<BackdropKind> {
+        FULL_STATE
+            .load()
+            .extended_settings()
+            .backdrops
+            .get(label)
+            .copied()
+    }
+
+    /// returns false if backdrops are not supported by the system
+    fn apply(hwnd: HWND, kind: BackdropKind) -> Result<bool> {
+        if !is_system_backdrop_supported() {
+            return Ok(false);
+        }
+        // the backdrop is drawn behind the frame, so it has to cover the whole client area
+        let inset = if kind == BackdropKind::None { 0 } else { -1 };
+        let margins = MARGINS {
+            cxLeftWidth: inset,
+            cxRightWidth: inset,
+            cyTopHeight: inset,
+            cyBottomHeight: inset,
+        };
+        unsafe { DwmExtendFrameIntoClientArea(hwnd, &margins)? };
+        let kind = DWM_SYSTEMBACKDROP_TYPE::from(kind);
+        WindowsApi::dwm_set_window_attribute(hwnd, DWMWA_SYSTEMBACKDROP_TYPE, &kind)?;
+        Ok(true)
+    }
+
+    pub fn get(label: &str) -> BackdropState {
+        BackdropState {
+            kind: Self::stored(label).unwrap_or(BackdropKind::None),
+            native: is_system_backdrop_supported(),
+        }
+    }
+
+    /// Applies and persists the backdrop of a Seelen webview window
+    pub fn set(label: &str, kind: BackdropKind) -> Result<BackdropState> {
+        let window = get_app_handle()
+            .get_webview_window(label)
+            .ok_or("Webview window not found")?;
+        let native = Self::apply(window.hwnd()?, kind)?;
+
+        update_extended_settings(|settings| {
+            match kind {
+                BackdropKind::None => settings.backdrops.remove(label),
+                _ => settings.backdrops.insert(label.to_owned(), kind),
+            };
+        })?;
+        Ok(BackdropState { kind, native })
+    }
+
+    /// Restores the persisted backdrop when the webview is (re)loaded
+    pub fn on_page_load(webview: &Webview<Wry>) {
+        let Some(kind) = Self::stored(webview.label()) else {
+            return;
+        };
+        if let Ok(hwnd) = webview.window().hwnd() {
+            log_error!(Self::apply(hwnd, kind));
+        }
+    }
+}
+
+pub struct WebviewClickThrough;
+impl WebviewClickThrough {
+    pub fn set(label: &str, enabled: bool) -> Result<()> {
+        let window = get_app_handle()
+            .get_webview_window(label)
+            .ok_or("Webview window not found")?;
+        let hwnd = window.hwnd()?;
+
+        let mut states = trace_lock!(CLICK_THROUGH);
+        let styles = WindowsApi::get_ex_styles(hwnd);
+        if enabled {
+            if states.contains_key(label) {
+                return Ok(());
+            }
+            let state = ClickThroughState {
+                added_layered: !styles.contains(WS_EX_LAYERED),
+                was_foreground: WindowsApi::get_foreground_window() == hwnd,
+            };
+            WindowsApi::set_ex_styles(hwnd, styles | WS_EX_LAYERED | WS_EX_TRANSPARENT);
+            if state.added_layered {
+                // a layered window without attributes is not drawn
+                WindowsApi::set_layered_attributes(hwnd, COLORREF(0), 255, LWA_ALPHA)?;
+            }
+            states.insert(label.to_owned(), state);
+        } else {
+            let state = match states.remove(label) {
+                Some(state) => state,
+                None => return Ok(()),
+            };
+            let mut styles = styles & !WS_EX_TRANSPARENT;
+            if state.added_layered {
+                styles &= !WS_EX_LAYERED;
+            }
+            WindowsApi::set_ex_styles(hwnd, styles);
+            if state.was_foreground {
+                window.set_focus()?;
+            }
+        }
+
+        // extended styles are cached by the system until the frame is refreshed
+        WindowsApi::set_position(
+            hwnd,
+            None,
+            &Default::default(),
+            SWP_FRAMECHANGED | SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+        )?;
+        Ok(())
+    }
+}
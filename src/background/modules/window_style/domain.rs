@@ -0,0 +1,75 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use windows::Win32::{
+    Foundation::COLORREF,
+    Graphics::Dwm::{
+        DWMSBT_MAINWINDOW, DWMSBT_NONE, DWMSBT_TABBEDWINDOW, DWMSBT_TRANSIENTWINDOW,
+        DWMWCP_DEFAULT, DWMWCP_DONOTROUND, DWMWCP_ROUND, DWMWCP_ROUNDSMALL,
+        DWM_SYSTEMBACKDROP_TYPE, DWM_WINDOW_CORNER_PREFERENCE,
+    },
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowTopmostChanged {
+    pub hwnd: isize,
+    pub topmost: bool,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum CornerPreference {
+    Default,
+    DoNotRound,
+    Round,
+    RoundSmall,
+}
+
+impl From<CornerPreference> for DWM_WINDOW_CORNER_PREFERENCE {
+    fn from(pref: CornerPreference) -> Self {
+        match pref {
+            CornerPreference::Default => DWMWCP_DEFAULT,
+            CornerPreference::DoNotRound => DWMWCP_DONOTROUND,
+            CornerPreference::Round => DWMWCP_ROUND,
+            CornerPreference::RoundSmall => DWMWCP_ROUNDSMALL,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl From<Color> for COLORREF {
+    fn from(color: Color) -> Self {
+        // 0x00BBGGRR
+        COLORREF(u32::from_le_bytes([color.r, color.g, color.b, 0]))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum BackdropKind {
+    None,
+    Mica,
+    Acrylic,
+    Tabbed,
+}
+
+impl From<BackdropKind> for DWM_SYSTEMBACKDROP_TYPE {
+    fn from(kind: BackdropKind) -> Self {
+        match kind {
+            BackdropKind::None => DWMSBT_NONE,
+            BackdropKind::Mica => DWMSBT_MAINWINDOW,
+            BackdropKind::Acrylic => DWMSBT_TRANSIENTWINDOW,
+            BackdropKind::Tabbed => DWMSBT_TABBEDWINDOW,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackdropState {
+    pub kind: BackdropKind,
+    /// false if the system doesn't support backdrops, widgets should paint a solid color instead
+    pub native: bool,
+}
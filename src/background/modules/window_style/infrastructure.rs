@@ -0,0 +1,52 @@
+use windows::Win32::Foundation::HWND;
+
+use crate::error_handler::Result;
+
+use super::{
+    application::WindowStyle,
+    application::{WebviewClickThrough, WidgetBackdrops},
+    domain::{BackdropKind, BackdropState, Color, CornerPreference},
+};
+
+#[tauri::command(async)]
+pub fn set_window_opacity(hwnd: isize, alpha: u8) -> Result<()> {
+    WindowStyle::set_opacity(HWND(hwnd as _), alpha)
+}
+
+#[tauri::command(async)]
+pub fn set_window_topmost(hwnd: isize, topmost: bool) -> Result<()> {
+    WindowStyle::set_topmost(HWND(hwnd as _), topmost)
+}
+
+#[tauri::command(async)]
+pub fn toggle_window_topmost(hwnd: isize) -> Result<bool> {
+    WindowStyle::toggle_topmost(HWND(hwnd as _))
+}
+
+#[tauri::command(async)]
+pub fn set_window_corner_preference(hwnd: isize, pref: CornerPreference) -> Result<()> {
+    WindowStyle::set_corner_preference(HWND(hwnd as _), pref)
+}
+
+#[tauri::command(async)]
+pub fn set_window_border_color(hwnd: isize, color: Option<Color>) -> Result<()> {
+    WindowStyle::set_border_color(HWND(hwnd as _), color)
+}
+
+/// Applies a system backdrop to a Seelen webview window and remembers it across restarts,
+/// if `native` is false on the result the widget should paint a solid background instead.
+#[tauri::command(async)]
+pub fn set_backdrop(webview_label: String, kind: BackdropKind) -> Result<BackdropState> {
+    WidgetBackdrops::set(&webview_label, kind)
+}
+
+#[tauri::command(async)]
+pub fn get_backdrop(webview_label: String) -> BackdropState {
+    WidgetBackdrops::get(&webview_label)
+}
+
+/// Lets the clicks pass through a Seelen webview window to the windows below it
+#[tauri::command(async)]
+pub fn set_webview_click_through(label: String, enabled: bool) -> Result<()> {
+    WebviewClickThrough::set(&label, enabled)
+}
@@ -22,7 +22,7 @@ impl RestorationAndMigration {
 
     pub fn recreate_user_folders() -> Result<()> {
         let path = get_app_handle().path();
-        let data_path = path.app_data_dir()?;
+        let data_path = SEELEN_COMMON.data_path().to_path_buf();
 
         // migration of user settings files below v1.8.3
         let old_path = path.resolve(".config/seelen", BaseDirectory::Home)?;
@@ -52,6 +52,7 @@ impl RestorationAndMigration {
         create_if_needed("wallpapers")?;
         create_if_needed("plugins")?;
         create_if_needed("widgets")?;
+        create_if_needed("widget_configs")?;
         Self::recreate_profiles()?;
 
         Ok(())
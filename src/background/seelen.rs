@@ -1,6 +1,7 @@
 use std::{
     env::temp_dir,
     sync::{atomic::AtomicBool, Arc},
+    time::{Duration, Instant},
 };
 
 use base64::Engine;
@@ -20,17 +21,26 @@ use crate::{
     hook::register_win_hook,
     instance::SeelenInstanceContainer,
     log_error,
-    modules::monitors::{MonitorManager, MonitorManagerEvent, MONITOR_MANAGER},
+    modules::{
+        capture::application::ScreenRecorder,
+        desktop_icons::application::DesktopIcons,
+        file_watcher::application::release_path_watchers,
+        monitors::{MonitorManager, MonitorManagerEvent, MONITOR_MANAGER},
+    },
     restoration_and_migrations::RestorationAndMigration,
     seelen_rofi::SeelenRofi,
     seelen_wall::SeelenWall,
     seelen_weg::SeelenWeg,
     seelen_wm_v2::instance::WindowManagerV2,
-    state::application::{FullState, FULL_STATE},
+    state::{
+        application::{is_widget_label, FullState, FULL_STATE},
+        domain::WidgetId,
+    },
     system::{declare_system_events_handlers, release_system_events_handlers},
     trace_lock,
     utils::{ahk::AutoHotKey, is_running_as_appx_package, PERFORMANCE_HELPER},
-    windows_api::WindowsApi,
+    widget_loader::WidgetInstance,
+    windows_api::{monitor::Monitor, AppBarData, WindowsApi},
     APP_HANDLE,
 };
 
@@ -125,7 +135,7 @@ impl Seelen {
             false => Self::kill_ahk_shortcuts()?,
         }
 
-        if state.is_weg_enabled() {
+        if SeelenWeg::should_hide_taskbar() {
             SeelenWeg::hide_taskbar();
         } else {
             SeelenWeg::restore_taskbar()?;
@@ -229,7 +239,12 @@ impl Seelen {
         SEELEN_IS_RUNNING.store(false, std::sync::atomic::Ordering::SeqCst);
 
         release_system_events_handlers();
-        if self.state().is_weg_enabled() {
+        // finalize any recording in progress so the file is not left unreadable
+        log_error!(ScreenRecorder::stop());
+        log_error!(DesktopIcons::restore());
+        release_path_watchers();
+        AppBarData::unregister_all();
+        if self.state().is_weg_enabled() || SeelenWeg::is_taskbar_hidden() {
             log_error!(SeelenWeg::restore_taskbar());
         }
         if self.state().is_ahk_enabled() {
@@ -244,6 +259,130 @@ impl Seelen {
         Ok(())
     }
 
+    pub fn settings_label() -> String {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode("@seelen/settings")
+    }
+
+    /// Webviews are destroyed asynchronously by the event loop and their labels
+    /// can't be reused until then, so we wait for them to be gone.
+    fn wait_for_webviews_destroyed(labels: &[String]) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(3);
+        let handle = get_app_handle();
+        while labels
+            .iter()
+            .any(|l| handle.get_webview_window(l).is_some())
+        {
+            if Instant::now() > deadline {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        true
+    }
+
+    /// Recreates the toolbar, weg, window manager, wall, launcher and widgets webviews
+    /// without restarting the process, background services and system listeners are kept alive.
+    /// The `SEELEN` lock is only taken to drop and to recreate the widgets, not while waiting.
+    pub fn reload_widgets() -> Result<()> {
+        log::info!("Reloading widgets");
+        let labels = trace_lock!(SEELEN).drop_widgets();
+        if !Self::wait_for_webviews_destroyed(&labels) {
+            // better to recreate what we can than leaving the user without widgets
+            log::warn!("Timeout while waiting for the widgets to be destroyed, recreating anyway");
+        }
+        trace_lock!(SEELEN).recreate_widgets();
+        Ok(())
+    }
+
+    /// returns the labels of the destroyed webviews, other webviews like settings are kept
+    fn drop_widgets(&mut self) -> Vec<String> {
+        let handle = get_app_handle();
+        let widgets: Vec<WidgetId> = ["toolbar", "weg", "wm", "launcher", "wall"]
+            .into_iter()
+            .map(|id| WidgetId(id.into()))
+            .chain(self.state().widgets.keys().cloned())
+            .collect();
+        let labels: Vec<String> = handle
+            .webview_windows()
+            .into_keys()
+            .filter(|label| widgets.iter().any(|widget| is_widget_label(label, widget)))
+            .collect();
+
+        // dropping the containers destroys their webviews
+        self.instances.clear();
+        self.rofi = None;
+        self.wall = None;
+
+        // webviews not owned by a container (or that failed to be destroyed) would be orphaned
+        for label in &labels {
+            if let Some(window) = handle.get_webview_window(label) {
+                log_error!(window.destroy());
+            }
+        }
+        labels
+    }
+
+    /// a failing widget doesn't prevent the others from being created
+    fn recreate_widgets(&mut self) {
+        let state = self.state();
+        if state.is_rofi_enabled() {
+            log_error!(self.add_rofi());
+        }
+        if state.is_wall_enabled() {
+            log_error!(self.add_wall());
+        }
+        let monitors = { trace_lock!(MONITOR_MANAGER).monitors.clone() };
+        for (_id, hmonitor) in monitors {
+            log_error!(self.add_monitor(hmonitor));
+        }
+    }
+
+    /// Shows the widget only on the given monitor, if the monitor is disconnected
+    /// the widget stays hidden until it is connected again.
+    pub fn set_widget_monitor(widget_id: &str, device_id: &str) -> Result<()> {
+        if !FULL_STATE
+            .load()
+            .widgets
+            .contains_key(&WidgetId(widget_id.to_owned()))
+        {
+            return Err(format!("Widget {} not found", widget_id).into());
+        }
+        if Monitor::by_id(device_id).is_none() {
+            return Err("Monitor not found".into());
+        }
+        WidgetInstance::set_monitor_affinity(widget_id, device_id)?;
+
+        let labels = trace_lock!(SEELEN).drop_widget_instances(widget_id, device_id);
+        if !Self::wait_for_webviews_destroyed(&labels) {
+            log::warn!("Timeout while waiting for the widget to be destroyed, moving it anyway");
+        }
+
+        let mut seelen = trace_lock!(SEELEN);
+        let state = seelen.state();
+        if let Some(instance) = seelen.monitor_by_device_id_mut(device_id) {
+            instance.load_settings(&state)?;
+        }
+        Ok(())
+    }
+
+    /// drops the instances of the widget on other monitors, returns the labels of their webviews
+    fn drop_widget_instances(&mut self, widget_id: &str, device_id: &str) -> Vec<String> {
+        let mut labels = Vec::new();
+        for instance in &mut self.instances {
+            if instance.id() == device_id {
+                continue;
+            }
+            instance.widgets_mut().retain(|id, widget| {
+                let keep = id.to_string() != widget_id;
+                if !keep {
+                    labels.push(widget.label());
+                }
+                keep
+            });
+        }
+        labels
+    }
+
     fn remove_monitor(&mut self, id: &str) -> Result<()> {
         self.instances.retain(|m| m.id() != id);
         self.refresh_windows_positions()?;
@@ -397,7 +536,7 @@ impl Seelen {
 
     pub fn show_settings() -> Result<()> {
         log::trace!("Show settings window");
-        let label = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode("@seelen/settings");
+        let label = Self::settings_label();
         let handle = get_app_handle();
         let window = handle.get_webview_window(&label).or_else(|| {
             tauri::WebviewWindowBuilder::new(
@@ -0,0 +1,54 @@
+use seelen_core::state::SeelenWegSide;
+use tauri::WebviewWindow;
+use windows::Win32::{Foundation::RECT, UI::WindowsAndMessaging::SWP_NOACTIVATE};
+
+use crate::{
+    error_handler::Result,
+    windows_api::{AppBarData, WindowsApi},
+};
+
+/// Reserves `size` (logical pixels) of the work area at the `edge` of the monitor
+/// where the calling window is, so maximized windows don't cover it.
+#[tauri::command(async)]
+pub fn register_app_bar(
+    webview: WebviewWindow<tauri::Wry>,
+    edge: SeelenWegSide,
+    size: u32,
+) -> Result<()> {
+    let hwnd = webview.hwnd()?;
+    let monitor = WindowsApi::monitor_from_window(hwnd);
+    let rc_monitor = WindowsApi::monitor_info(monitor)?.monitorInfo.rcMonitor;
+    let size = (size as f32 * WindowsApi::get_device_pixel_ratio(monitor)?) as i32;
+
+    let rect = match edge {
+        SeelenWegSide::Top => RECT {
+            bottom: rc_monitor.top + size,
+            ..rc_monitor
+        },
+        SeelenWegSide::Bottom => RECT {
+            top: rc_monitor.bottom - size,
+            ..rc_monitor
+        },
+        SeelenWegSide::Left => RECT {
+            right: rc_monitor.left + size,
+            ..rc_monitor
+        },
+        SeelenWegSide::Right => RECT {
+            left: rc_monitor.right - size,
+            ..rc_monitor
+        },
+    };
+
+    let mut abd = AppBarData::from_handle(hwnd);
+    abd.set_edge(edge.into());
+    abd.set_rect(rect);
+    let reserved = abd.register_as_new_bar();
+    WindowsApi::set_position(hwnd, None, &reserved, SWP_NOACTIVATE)?;
+    Ok(())
+}
+
+#[tauri::command(async)]
+pub fn unregister_app_bar(webview: WebviewWindow<tauri::Wry>) -> Result<()> {
+    AppBarData::from_handle(webview.hwnd()?).unregister_bar();
+    Ok(())
+}
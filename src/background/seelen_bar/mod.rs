@@ -1,4 +1,5 @@
 pub mod cli;
+pub mod handler;
 pub mod hook;
 
 use crate::{
@@ -0,0 +1,211 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use base64::Engine;
+use crossbeam_channel::{Receiver, Sender};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::presentation::application::PresentationMode,
+    seelen::get_app_handle,
+    state::{application::FULL_STATE, infrastructure::update_extended_settings},
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{monitor::Monitor, window::Window, MonitorEnumerator, WindowEnumerator},
+    winevent::WinEvent,
+};
+
+use super::SeelenWall;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WallDimConfig {
+    pub enabled: bool,
+    /// number of windows on a monitor to reach the max dim, a maximized window always does
+    pub full_dim_windows: u32,
+    /// opacity of the dim layer, from 0 to 1
+    pub max_opacity: f32,
+    /// blur in pixels
+    pub max_blur: f32,
+}
+
+impl Default for WallDimConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            full_dim_windows: 3,
+            max_opacity: 0.6,
+            max_blur: 12.0,
+        }
+    }
+}
+
+impl WallDimConfig {
+    fn validate(&self) -> Result<()> {
+        if self.full_dim_windows == 0 {
+            return Err("At least one window should be required to dim the wall".into());
+        }
+        if !(0.0..=1.0).contains(&self.max_opacity) {
+            return Err("Max opacity should be between 0 and 1".into());
+        }
+        if self.max_blur < 0.0 {
+            return Err("Max blur can not be negative".into());
+        }
+        Ok(())
+    }
+}
+
+/// dim of a monitor, payload of `wall-dim` by monitor device id
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WallDim {
+    pub opacity: f32,
+    pub blur: f32,
+}
+
+lazy_static! {
+    static ref LAST_EMITTED: Mutex<HashMap<String, WallDim>> = Mutex::new(HashMap::new());
+    static ref CHANNEL: (Sender<()>, Receiver<()>) = crossbeam_channel::unbounded();
+}
+
+/// window events come in bursts (e.g. moving or closing a group of windows)
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+static DISPATCHER_STARTED: AtomicBool = AtomicBool::new(false);
+
+impl WallDim {
+    fn monitor_key(monitor: &Monitor) -> isize {
+        monitor.handle().0 as isize
+    }
+
+    /// from 0 to 1 by monitor, based on the windows covering it
+    fn levels(config: &WallDimConfig) -> Result<Vec<(Monitor, f32)>> {
+        let mut counts: HashMap<isize, u32> = HashMap::new();
+        let mut maximized: HashSet<isize> = HashSet::new();
+        WindowEnumerator::new().for_each(|hwnd| {
+            let window = Window::from(hwnd);
+            if window.is_minimized() || window.is_cloaked() || !window.is_real_window() {
+                return;
+            }
+            let monitor = Self::monitor_key(&window.monitor());
+            *counts.entry(monitor).or_default() += 1;
+            if window.is_maximized() {
+                maximized.insert(monitor);
+            }
+        })?;
+
+        let mut levels = Vec::new();
+        for monitor in MonitorEnumerator::get_all_v2()? {
+            let key = Self::monitor_key(&monitor);
+            let level = if maximized.contains(&key) {
+                1.0
+            } else {
+                let count = counts.get(&key).copied().unwrap_or(0);
+                (count as f32 / config.full_dim_windows as f32).min(1.0)
+            };
+            levels.push((monitor, level));
+        }
+        Ok(levels)
+    }
+
+    fn refresh() -> Result<()> {
+        let config = Self::config();
+        let mut dims = HashMap::new();
+        if config.enabled {
+            let quiet_monitor = PresentationMode::quiet_monitor().map(|m| m.0 as isize);
+            for (monitor, level) in Self::levels(&config)? {
+                // games, presentations and fullscreen apps hide the wall entirely
+                let dim = if quiet_monitor == Some(Self::monitor_key(&monitor)) {
+                    WallDim {
+                        opacity: 1.0,
+                        blur: config.max_blur,
+                    }
+                } else {
+                    WallDim {
+                        opacity: level * config.max_opacity,
+                        blur: level * config.max_blur,
+                    }
+                };
+                dims.insert(monitor.device_id()?, dim);
+            }
+        }
+
+        let mut last = trace_lock!(LAST_EMITTED);
+        if *last != dims {
+            let label = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(SeelenWall::TARGET);
+            get_app_handle().emit_to(&label, "wall-dim", &dims)?;
+            *last = dims;
+        }
+        Ok(())
+    }
+
+    /// refreshes once per burst of requests
+    fn dispatch_refreshes() {
+        let receiver = &CHANNEL.1;
+        while receiver.recv().is_ok() {
+            while receiver.recv_timeout(DEBOUNCE).is_ok() {}
+            log_error!(Self::refresh());
+        }
+    }
+
+    /// enumerating windows and monitors is too slow for the hook thread, so the
+    /// refresh is done by a dispatcher thread.
+    fn request_refresh() -> Result<()> {
+        if !DISPATCHER_STARTED.swap(true, Ordering::SeqCst) {
+            spawn_named_thread("Wall Dim Dispatcher", Self::dispatch_refreshes)?;
+        }
+        log_error!(CHANNEL.0.send(()));
+        Ok(())
+    }
+
+    pub fn on_win_event(event: WinEvent) -> Result<()> {
+        match event {
+            WinEvent::SyntheticFullscreenStart(_)
+            | WinEvent::SyntheticFullscreenEnd(_)
+            | WinEvent::SystemForeground
+            | WinEvent::ObjectShow
+            | WinEvent::ObjectHide
+            | WinEvent::ObjectDestroy
+            | WinEvent::ObjectCloaked
+            | WinEvent::ObjectUncloaked
+            | WinEvent::SystemMinimizeStart
+            | WinEvent::SystemMinimizeEnd
+            | WinEvent::SystemMoveSizeEnd => {}
+            _ => return Ok(()),
+        }
+        if FULL_STATE.load().extended_settings().wall.dim.enabled {
+            Self::request_refresh()?;
+        }
+        Ok(())
+    }
+
+    pub fn config() -> WallDimConfig {
+        FULL_STATE.load().extended_settings().wall.dim.clone()
+    }
+
+    pub fn set_config(config: WallDimConfig) -> Result<()> {
+        config.validate()?;
+        update_extended_settings(|settings| settings.wall.dim = config.clone())?;
+        // disabling emits an empty map so the wall goes back to normal
+        Self::refresh()
+    }
+}
+
+#[tauri::command(async)]
+pub fn get_wall_dim_config() -> WallDimConfig {
+    WallDim::config()
+}
+
+#[tauri::command(async)]
+pub fn set_wall_dim_config(config: WallDimConfig) -> Result<()> {
+    WallDim::set_config(config)
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use base64::Engine;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+use crate::{
+    error_handler::Result,
+    seelen::get_app_handle,
+    state::{application::FULL_STATE, infrastructure::update_extended_settings},
+    windows_api::MonitorEnumerator,
+};
+
+use super::SeelenWall;
+
+/// how the image is drawn on the monitor by the wall widget
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum WallFitMode {
+    /// covers the monitor keeping the aspect ratio, cropping the overflow
+    #[default]
+    Fill,
+    /// the whole image is visible keeping the aspect ratio
+    Fit,
+    Stretch,
+    Center,
+    Tile,
+}
+
+/// global fit mode of the wall with overrides by monitor device id
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WallFitConfig {
+    pub fit_mode: WallFitMode,
+    pub monitors: HashMap<String, WallFitMode>,
+}
+
+/// fit mode resolved for each connected monitor, as consumed by the wall widget
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WallFit {
+    pub fit_mode: WallFitMode,
+    pub monitors: HashMap<String, WallFitMode>,
+}
+
+impl WallFitConfig {
+    pub fn load() -> Self {
+        FULL_STATE.load().extended_settings().wall.fit.clone()
+    }
+
+    /// same as the other monitor settings, the override of the monitor wins over the global
+    pub fn resolve(&self, monitor_id: &str) -> WallFitMode {
+        self.monitors
+            .get(monitor_id)
+            .copied()
+            .unwrap_or(self.fit_mode)
+    }
+
+    pub fn resolved(&self) -> WallFit {
+        let mut monitors = HashMap::new();
+        if let Ok(connected) = MonitorEnumerator::get_all_v2() {
+            for monitor in connected {
+                if let Ok(device) = monitor.display_device() {
+                    let fit_mode = self.resolve(&device.id);
+                    monitors.insert(device.id, fit_mode);
+                }
+            }
+        }
+        WallFit {
+            fit_mode: self.fit_mode,
+            monitors,
+        }
+    }
+
+    /// without monitor the global mode is changed, with monitor and without mode the
+    /// override of the monitor is removed.
+    pub fn set(monitor_id: Option<String>, fit_mode: Option<WallFitMode>) -> Result<()> {
+        match (monitor_id, fit_mode) {
+            (None, Some(fit_mode)) => {
+                update_extended_settings(|settings| settings.wall.fit.fit_mode = fit_mode)?
+            }
+            (None, None) => return Err("A fit mode is required to change the global one".into()),
+            (Some(monitor_id), Some(fit_mode)) => update_extended_settings(|settings| {
+                settings
+                    .wall
+                    .fit
+                    .monitors
+                    .insert(monitor_id.clone(), fit_mode);
+            })?,
+            (Some(monitor_id), None) => update_extended_settings(|settings| {
+                settings.wall.fit.monitors.remove(&monitor_id);
+            })?,
+        }
+
+        let config = Self::load();
+        let label = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(SeelenWall::TARGET);
+        get_app_handle().emit_to(&label, "wall-fit-changed", config.resolved())?;
+        Ok(())
+    }
+}
+
+#[tauri::command(async)]
+pub fn get_wall_fit_config() -> WallFitConfig {
+    WallFitConfig::load()
+}
+
+#[tauri::command(async)]
+pub fn set_wall_fit_mode(monitor_id: Option<String>, fit_mode: Option<WallFitMode>) -> Result<()> {
+    WallFitConfig::set(monitor_id, fit_mode)
+}
@@ -3,10 +3,11 @@ use tauri::Emitter;
 
 use crate::{error_handler::Result, windows_api::window::Window, winevent::WinEvent};
 
-use super::SeelenWall;
+use super::{dim::WallDim, SeelenWall};
 
 impl SeelenWall {
     pub fn process_win_event(&mut self, event: WinEvent, _origin: &Window) -> Result<()> {
+        WallDim::on_win_event(event)?;
         match event {
             WinEvent::SyntheticFullscreenStart(_) => {
                 self.window
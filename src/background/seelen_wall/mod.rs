@@ -1,4 +1,7 @@
+pub mod dim;
+pub mod fit_mode;
 mod hook;
+pub mod slideshow;
 
 use base64::Engine;
 use tauri::WebviewWindow;
@@ -30,7 +33,7 @@ impl Drop for SeelenWall {
 
 impl SeelenWall {
     pub const TITLE: &str = "Seelen Wall";
-    const TARGET: &str = "@seelen/wall";
+    pub const TARGET: &str = "@seelen/wall";
 
     pub fn new() -> Result<Self> {
         log::info!("Creating {}", Self::TARGET);
@@ -0,0 +1,357 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use base64::Engine;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    seelen::get_app_handle,
+    state::{application::FULL_STATE, infrastructure::update_extended_settings},
+    trace_lock,
+    utils::{constants::SEELEN_COMMON, spawn_named_thread},
+};
+
+use super::{
+    fit_mode::{WallFit, WallFitConfig},
+    SeelenWall,
+};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+const MIN_INTERVAL_SECS: u64 = 5;
+const IMAGE_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "webp", "bmp", "gif"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum SlideshowOrder {
+    #[default]
+    Sequential,
+    Random,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WallSlideshowConfig {
+    pub enabled: bool,
+    pub folder: Option<PathBuf>,
+    pub interval_secs: u64,
+    pub order: SlideshowOrder,
+}
+
+impl Default for WallSlideshowConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            folder: None,
+            interval_secs: 600,
+            order: SlideshowOrder::default(),
+        }
+    }
+}
+
+impl WallSlideshowConfig {
+    fn validate(&self) -> Result<()> {
+        if self.interval_secs < MIN_INTERVAL_SECS {
+            return Err(format!("Interval should be at least {MIN_INTERVAL_SECS} seconds").into());
+        }
+        match &self.folder {
+            Some(folder) if !folder.is_dir() => {
+                Err(format!("{} is not a folder", folder.display()).into())
+            }
+            None if self.enabled => Err("Can not enable a slideshow without folder".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// persisted so restarts resume on the same image
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct SlideshowProgress {
+    index: usize,
+    image: Option<PathBuf>,
+}
+
+/// payload of `wall-slideshow-changed`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlideshowImage {
+    pub image: PathBuf,
+    pub fit: WallFit,
+    pub index: usize,
+    pub total: usize,
+    pub paused: bool,
+}
+
+lazy_static! {
+    static ref SLIDESHOW: Mutex<WallSlideshow> = Mutex::new(WallSlideshow::new());
+}
+
+static THREAD_STARTED: AtomicBool = AtomicBool::new(false);
+
+pub struct WallSlideshow {
+    /// last config applied, compared with the settings to notice changes
+    config: WallSlideshowConfig,
+    images: Vec<PathBuf>,
+    index: usize,
+    /// visited indexes, used to go back on random order
+    history: Vec<usize>,
+    paused: bool,
+    last_change: Instant,
+}
+
+impl WallSlideshow {
+    fn new() -> Self {
+        let mut slideshow = Self {
+            config: Self::config(),
+            images: Vec::new(),
+            index: 0,
+            history: Vec::new(),
+            paused: false,
+            last_change: Instant::now(),
+        };
+        slideshow.reload_images();
+        slideshow.restore_progress();
+        slideshow
+    }
+
+    fn read_images(folder: &Path) -> Result<Vec<PathBuf>> {
+        let mut images = Vec::new();
+        for entry in std::fs::read_dir(folder)?.flatten() {
+            let path = entry.path();
+            let is_image = path.extension().is_some_and(|ext| {
+                IMAGE_EXTENSIONS
+                    .iter()
+                    .any(|allowed| ext.eq_ignore_ascii_case(allowed))
+            });
+            if is_image && path.is_file() {
+                images.push(path);
+            }
+        }
+        images.sort();
+        Ok(images)
+    }
+
+    /// the folder is read again on each change so added or removed images are noticed
+    fn reload_images(&mut self) {
+        let current = self.current().cloned();
+        self.images = match &self.config.folder {
+            Some(folder) => Self::read_images(folder).unwrap_or_else(|err| {
+                log::error!("Failed to read wall slideshow folder: {:?}", err);
+                Vec::new()
+            }),
+            None => Vec::new(),
+        };
+        self.index = current
+            .and_then(|current| self.images.iter().position(|image| *image == current))
+            .unwrap_or_else(|| self.index.min(self.images.len().saturating_sub(1)));
+    }
+
+    fn restore_progress(&mut self) {
+        let path = SEELEN_COMMON.wall_slideshow_progress_path();
+        let progress: SlideshowProgress = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default();
+        // the image is preferred as the index moves if images are added to the folder
+        self.index = progress
+            .image
+            .and_then(|image| self.images.iter().position(|current| *current == image))
+            .unwrap_or_else(|| progress.index.min(self.images.len().saturating_sub(1)));
+    }
+
+    fn save_progress(&self) -> Result<()> {
+        let progress = SlideshowProgress {
+            index: self.index,
+            image: self.current().cloned(),
+        };
+        std::fs::write(
+            SEELEN_COMMON.wall_slideshow_progress_path(),
+            serde_yaml::to_string(&progress)?,
+        )?;
+        Ok(())
+    }
+
+    fn current(&self) -> Option<&PathBuf> {
+        self.images.get(self.index)
+    }
+
+    fn current_image(&self) -> Option<SlideshowImage> {
+        Some(SlideshowImage {
+            image: self.current()?.clone(),
+            fit: WallFitConfig::load().resolved(),
+            index: self.index,
+            total: self.images.len(),
+            paused: self.paused,
+        })
+    }
+
+    fn emit(&self) -> Result<()> {
+        if let Some(image) = self.current_image() {
+            let label = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(SeelenWall::TARGET);
+            get_app_handle().emit_to(&label, "wall-slideshow-changed", image)?;
+        }
+        Ok(())
+    }
+
+    fn random_index(&self) -> usize {
+        let len = self.images.len();
+        if len < 2 {
+            return 0;
+        }
+        // offset from the current so the same image is never repeated
+        let offset = (uuid::Uuid::new_v4().as_u128() % (len as u128 - 1)) as usize + 1;
+        (self.index + offset) % len
+    }
+
+    fn go_to(&mut self, index: usize) -> Result<()> {
+        self.index = index;
+        self.last_change = Instant::now();
+        self.save_progress()?;
+        self.emit()
+    }
+
+    fn next(&mut self) -> Result<()> {
+        self.reload_images();
+        if self.images.is_empty() {
+            return Ok(());
+        }
+        let next = match self.config.order {
+            SlideshowOrder::Sequential => (self.index + 1) % self.images.len(),
+            SlideshowOrder::Random => self.random_index(),
+        };
+        self.history.push(self.index);
+        self.go_to(next)
+    }
+
+    fn previous(&mut self) -> Result<()> {
+        self.reload_images();
+        if self.images.is_empty() {
+            return Ok(());
+        }
+        let len = self.images.len();
+        let previous = match self.history.pop() {
+            Some(previous) if previous < len => previous,
+            _ => (self.index + len - 1) % len,
+        };
+        self.go_to(previous)
+    }
+
+    /// applies the config of the settings if it changed since the last call
+    fn sync_config(&mut self) -> Result<()> {
+        let config = Self::config();
+        if config == self.config {
+            return Ok(());
+        }
+        let folder_changed = self.config.folder != config.folder;
+        self.config = config;
+        self.history.clear();
+        if folder_changed {
+            self.index = 0;
+        }
+        self.reload_images();
+        self.last_change = Instant::now();
+        self.save_progress()?;
+        self.emit()
+    }
+
+    fn tick(&mut self) -> Result<()> {
+        self.sync_config()?;
+        if !self.config.enabled || self.paused {
+            return Ok(());
+        }
+        if self.last_change.elapsed() >= Duration::from_secs(self.config.interval_secs) {
+            self.next()?;
+        }
+        Ok(())
+    }
+
+    fn ensure_thread() -> Result<()> {
+        if THREAD_STARTED.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        spawn_named_thread("Wall Slideshow", || loop {
+            log_error!(trace_lock!(SLIDESHOW).tick());
+            std::thread::sleep(TICK_INTERVAL);
+        })?;
+        Ok(())
+    }
+
+    /// the thread is always running so changes made on `settings.json` are also applied
+    pub fn init() -> Result<()> {
+        Self::ensure_thread()
+    }
+
+    pub fn config() -> WallSlideshowConfig {
+        FULL_STATE.load().extended_settings().wall.slideshow.clone()
+    }
+
+    pub fn set_config(config: WallSlideshowConfig) -> Result<()> {
+        config.validate()?;
+        update_extended_settings(|settings| settings.wall.slideshow = config.clone())?;
+        trace_lock!(SLIDESHOW).sync_config()?;
+        Self::ensure_thread()
+    }
+
+    /// image currently shown, None if the slideshow is disabled or the folder has no images
+    pub fn current_slide() -> Option<SlideshowImage> {
+        let slideshow = trace_lock!(SLIDESHOW);
+        if !slideshow.config.enabled {
+            return None;
+        }
+        slideshow.current_image()
+    }
+
+    pub fn next_slide() -> Result<()> {
+        trace_lock!(SLIDESHOW).next()
+    }
+
+    pub fn previous_slide() -> Result<()> {
+        trace_lock!(SLIDESHOW).previous()
+    }
+
+    /// on resume the interval starts again from zero
+    pub fn set_paused(paused: bool) -> Result<()> {
+        let mut slideshow = trace_lock!(SLIDESHOW);
+        slideshow.paused = paused;
+        slideshow.last_change = Instant::now();
+        slideshow.emit()
+    }
+}
+
+#[tauri::command(async)]
+pub fn get_wall_slideshow() -> WallSlideshowConfig {
+    WallSlideshow::config()
+}
+
+#[tauri::command(async)]
+pub fn set_wall_slideshow(config: WallSlideshowConfig) -> Result<()> {
+    WallSlideshow::set_config(config)
+}
+
+#[tauri::command(async)]
+pub fn wall_get_current_slide() -> Option<SlideshowImage> {
+    WallSlideshow::current_slide()
+}
+
+#[tauri::command(async)]
+pub fn wall_next() -> Result<()> {
+    WallSlideshow::next_slide()
+}
+
+#[tauri::command(async)]
+pub fn wall_previous() -> Result<()> {
+    WallSlideshow::previous_slide()
+}
+
+#[tauri::command(async)]
+pub fn wall_pause(paused: bool) -> Result<()> {
+    WallSlideshow::set_paused(paused)
+}
@@ -1,4 +1,4 @@
-use std::{ffi::OsStr, path::PathBuf, sync::atomic::Ordering};
+use std::{collections::HashMap, ffi::OsStr, path::PathBuf, sync::atomic::Ordering};
 
 use image::ImageFormat;
 use seelen_core::state::{PinnedWegItemData, WegItem, WegItems};
@@ -18,11 +18,33 @@ use windows::Win32::UI::WindowsAndMessaging::{SW_MINIMIZE, SW_RESTORE, WM_CLOSE}
 
 use super::SeelenWeg;
 
+#[tauri::command(async)]
+pub fn set_native_taskbar_visible(visible: bool) -> Result<()> {
+    SeelenWeg::set_native_taskbar_visible(visible)
+}
+
 #[tauri::command(async)]
 pub fn weg_get_items_for_widget() -> WegItems {
     trace_lock!(WEG_ITEMS_IMPL).get()
 }
 
+/// Open windows by app identifier (umid or exe path), by default hidden and cloaked windows
+/// are not counted, so it matches what the user sees.
+#[tauri::command(async)]
+pub fn get_window_counts(only_visible: Option<bool>) -> HashMap<String, usize> {
+    trace_lock!(WEG_ITEMS_IMPL).window_counts(only_visible.unwrap_or(true))
+}
+
+#[tauri::command(async)]
+pub fn minimize_app_windows(identifier: String, all_desktops: Option<bool>) -> Result<()> {
+    SeelenWeg::minimize_app_windows(&identifier, all_desktops.unwrap_or(false))
+}
+
+#[tauri::command(async)]
+pub fn restore_app_windows(identifier: String) -> Result<()> {
+    SeelenWeg::restore_app_windows(&identifier)
+}
+
 #[tauri::command(async)]
 pub fn weg_request_update_previews(handles: Vec<isize>) -> Result<()> {
     let temp_dir = std::env::temp_dir();
@@ -109,6 +131,12 @@ pub fn weg_toggle_window_state(hwnd: isize) -> Result<()> {
     Ok(())
 }
 
+/// returns the handle of the most recently active window of the app
+#[tauri::command(async)]
+pub fn is_app_running(identifier: String) -> Result<Option<isize>> {
+    Ok(SeelenWeg::find_app_window(&identifier)?.map(|w| w.address()))
+}
+
 #[tauri::command(async)]
 pub fn weg_pin_item(path: PathBuf) -> Result<()> {
     let display_name = if let Some(name) = path.file_name() {
@@ -14,11 +14,11 @@ use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use seelen_core::{handlers::SeelenEvent, state::AppExtraFlag};
 use tauri::Emitter;
-use weg_items_impl::WEG_ITEMS_IMPL;
+use weg_items_impl::{WegItemsImpl, WEG_ITEMS_IMPL};
 use win_screenshot::capture::capture_window;
 use windows::Win32::{
     Foundation::HWND,
-    UI::WindowsAndMessaging::{SW_HIDE, SW_SHOWNORMAL},
+    UI::WindowsAndMessaging::{SW_HIDE, SW_MINIMIZE, SW_RESTORE, SW_SHOWNOACTIVATE, SW_SHOWNORMAL},
 };
 
 use crate::{
@@ -38,6 +38,8 @@ lazy_static! {
         "DesktopWindowXamlSource",
         "Program Manager",
     ]);
+    /// windows minimized by `minimize_app_windows` in z-order, by lowercased app identifier
+    static ref MINIMIZED_GROUPS: Mutex<HashMap<String, Vec<isize>>> = Mutex::new(HashMap::new());
 }
 
 impl SeelenWeg {
@@ -64,6 +66,7 @@ impl SeelenWeg {
         get_app_handle()
             .emit(SeelenEvent::WegInstanceChanged, items.get())
             .expect("Failed to emit");
+        Self::emit_window_counts(&items);
         Ok(())
     }
 
@@ -73,6 +76,11 @@ impl SeelenWeg {
         get_app_handle()
             .emit(SeelenEvent::WegInstanceChanged, items.get())
             .expect("Failed to emit");
+        Self::emit_window_counts(&items);
+    }
+
+    fn emit_window_counts(items: &WegItemsImpl) {
+        log_error!(get_app_handle().emit("weg-window-counts-changed", items.window_counts(true)));
     }
 
     pub fn should_be_added(window: &Window) -> bool {
@@ -90,6 +98,78 @@ impl SeelenWeg {
         !TITLE_BLACK_LIST.contains(&window.title().as_str())
     }
 
+    /// `identifier` can be an app user model id, an exe path or an exe file name.
+    /// Windows are enumerated in z-order so the first match is the most recently active one.
+    pub fn find_app_window(identifier: &str) -> Result<Option<Window>> {
+        Ok(Self::find_app_windows(identifier)?.into_iter().next())
+    }
+
+    /// same as `find_app_window` but returns all the windows of the app in z-order
+    pub fn find_app_windows(identifier: &str) -> Result<Vec<Window>> {
+        let windows = WindowEnumerator::new().map(Window::from)?;
+        Ok(windows
+            .into_iter()
+            .filter(|w| Self::should_be_added(w) && Self::is_window_of_app(w, identifier))
+            .collect())
+    }
+
+    /// Minimizes all the windows of the app, windows on other virtual desktops (cloaked)
+    /// are only affected if `all_desktops` is set.
+    pub fn minimize_app_windows(identifier: &str, all_desktops: bool) -> Result<()> {
+        let windows: Vec<Window> = Self::find_app_windows(identifier)?
+            .into_iter()
+            .filter(|w| !w.is_minimized() && (all_desktops || !w.is_cloaked()))
+            .collect();
+        for window in &windows {
+            WindowsApi::show_window_async(window.hwnd(), SW_MINIMIZE)?;
+        }
+        let mut groups = trace_lock!(MINIMIZED_GROUPS);
+        let minimized = groups.entry(identifier.to_lowercase()).or_default();
+        for window in windows {
+            if !minimized.contains(&window.address()) {
+                minimized.push(window.address());
+            }
+        }
+        Ok(())
+    }
+
+    /// Restores only the windows minimized by `minimize_app_windows`, keeping their
+    /// previous z-order, the frontmost one ends activated.
+    pub fn restore_app_windows(identifier: &str) -> Result<()> {
+        let Some(minimized) = trace_lock!(MINIMIZED_GROUPS).remove(&identifier.to_lowercase())
+        else {
+            return Ok(());
+        };
+        let windows: Vec<Window> = minimized
+            .into_iter()
+            .map(Window::from)
+            .filter(|w| w.is_window() && w.is_minimized())
+            .collect();
+        for (idx, window) in windows.iter().enumerate().rev() {
+            let command = if idx == 0 {
+                SW_RESTORE
+            } else {
+                SW_SHOWNOACTIVATE
+            };
+            WindowsApi::show_window_async(window.hwnd(), command)?;
+        }
+        Ok(())
+    }
+
+    fn is_window_of_app(window: &Window, identifier: &str) -> bool {
+        if let Some(umid) = window.app_user_model_id() {
+            if umid.eq_ignore_ascii_case(identifier) {
+                return true;
+            }
+        }
+        window.exe().is_ok_and(|exe| {
+            exe.to_string_lossy().eq_ignore_ascii_case(identifier)
+                || exe
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().eq_ignore_ascii_case(identifier))
+        })
+    }
+
     pub fn capture_window(hwnd: HWND) -> Option<DynamicImage> {
         capture_window(hwnd.0 as isize).ok().map(|buf| {
             let image = RgbaImage::from_raw(buf.width, buf.height, buf.pixels).unwrap_or_default();
@@ -107,6 +187,8 @@ lazy_static! {
         Mutex::new(HashMap::new());
     pub static ref TASKBAR_CLASS: Vec<&'static str> =
         Vec::from(["Shell_TrayWnd", "Shell_SecondaryTrayWnd",]);
+    /// visibility explicitly requested by the user, `None` means it follows the weg setting
+    static ref NATIVE_TASKBAR_VISIBILITY: Mutex<Option<bool>> = Mutex::new(None);
 }
 
 pub fn get_taskbars_handles() -> Result<Vec<HWND>> {
@@ -121,15 +203,28 @@ pub fn get_taskbars_handles() -> Result<Vec<HWND>> {
 }
 
 impl SeelenWeg {
+    pub fn should_hide_taskbar() -> bool {
+        match *trace_lock!(NATIVE_TASKBAR_VISIBILITY) {
+            Some(visible) => !visible,
+            None => FULL_STATE.load().is_weg_enabled(),
+        }
+    }
+
+    pub fn is_taskbar_hidden() -> bool {
+        !trace_lock!(TASKBAR_STATE_ON_INIT).is_empty()
+    }
+
     pub fn hide_taskbar() -> JoinHandle<()> {
         std::thread::spawn(move || match get_taskbars_handles() {
             Ok(handles) => {
                 let mut attempts = 0;
-                while attempts < 10 && FULL_STATE.load().is_weg_enabled() {
+                while attempts < 10 && Self::should_hide_taskbar() {
                     for handle in &handles {
                         let app_bar = AppBarData::from_handle(*handle);
+                        // only the first state is stored, next attempts would read our own AutoHide
                         trace_lock!(TASKBAR_STATE_ON_INIT)
-                            .insert(handle.0 as isize, app_bar.state());
+                            .entry(handle.0 as isize)
+                            .or_insert_with(|| app_bar.state());
                         app_bar.set_state(AppBarDataState::AutoHide);
                         let _ = WindowsApi::show_window(*handle, SW_HIDE);
                     }
@@ -144,12 +239,40 @@ impl SeelenWeg {
     pub fn restore_taskbar() -> Result<()> {
         for hwnd in get_taskbars_handles()? {
             AppBarData::from_handle(hwnd).set_state(
-                *trace_lock!(TASKBAR_STATE_ON_INIT)
-                    .get(&(hwnd.0 as isize))
-                    .unwrap_or(&AppBarDataState::AlwaysOnTop),
+                trace_lock!(TASKBAR_STATE_ON_INIT)
+                    .remove(&(hwnd.0 as isize))
+                    .unwrap_or(AppBarDataState::AlwaysOnTop),
             );
             WindowsApi::show_window(hwnd, SW_SHOWNORMAL)?;
         }
         Ok(())
     }
+
+    /// Lock-free variant of `restore_taskbar` for the panic hook, the panicking thread could
+    /// be holding the state lock so the stored states are only used if the lock is free.
+    pub fn restore_taskbar_on_panic() {
+        let Ok(handles) = get_taskbars_handles() else {
+            return;
+        };
+        let mut states = TASKBAR_STATE_ON_INIT.try_lock();
+        for hwnd in handles {
+            let state = states
+                .as_mut()
+                .and_then(|states| states.remove(&(hwnd.0 as isize)))
+                .unwrap_or(AppBarDataState::AlwaysOnTop);
+            AppBarData::from_handle(hwnd).set_state(state);
+            let _ = WindowsApi::show_window(hwnd, SW_SHOWNORMAL);
+        }
+    }
+
+    /// overrides the weg setting until the app is restarted
+    pub fn set_native_taskbar_visible(visible: bool) -> Result<()> {
+        *trace_lock!(NATIVE_TASKBAR_VISIBILITY) = Some(visible);
+        if visible {
+            Self::restore_taskbar()?;
+        } else {
+            Self::hide_taskbar();
+        }
+        Ok(())
+    }
 }
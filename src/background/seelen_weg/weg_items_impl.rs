@@ -4,7 +4,7 @@ use seelen_core::{
     handlers::SeelenEvent,
     state::{PinnedWegItemData, WegAppGroupItem, WegItem, WegItems},
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tauri::Emitter;
 
 use crate::{
@@ -175,6 +175,32 @@ impl WegItemsImpl {
         self.items.sanitize();
     }
 
+    /// Open windows by app identifier (umid or exe path), apps without windows are omitted.
+    /// `only_visible` skips hidden and cloaked windows, like the ones minimized to the tray
+    /// or living on another virtual desktop.
+    pub fn window_counts(&self, only_visible: bool) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for item in self.iter_all() {
+            if let WegItem::Pinned(data) | WegItem::Temporal(data) = item {
+                let count = data
+                    .windows
+                    .iter()
+                    .map(|w| Window::from(w.handle))
+                    .filter(|w| !only_visible || (w.is_visible() && !w.is_cloaked()))
+                    .count();
+                if count == 0 {
+                    continue;
+                }
+                let id = data
+                    .umid
+                    .clone()
+                    .unwrap_or_else(|| data.path.to_string_lossy().to_string());
+                *counts.entry(id).or_insert(0) += count;
+            }
+        }
+        counts
+    }
+
     pub fn get(&self) -> WegItems {
         self.items.clone()
     }
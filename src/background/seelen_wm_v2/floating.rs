@@ -0,0 +1,80 @@
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use windows::Win32::{
+    Foundation::RECT,
+    UI::WindowsAndMessaging::{SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE},
+};
+
+use crate::{
+    error_handler::Result,
+    trace_lock,
+    windows_api::{window::Window, WindowsApi},
+};
+
+use super::WindowManagerV2;
+
+lazy_static! {
+    /// windows floated by the user, they are skipped by the wm until toggled back.
+    /// Kept while Seelen UI runs so workspace switches or wm widget reloads don't tile them
+    /// again. Intentionally not persisted: window handles are only valid for the lifetime of
+    /// the window, so after a restart of Seelen UI all windows are tiled again.
+    static ref FLOATING: Mutex<HashSet<isize>> = Mutex::new(HashSet::new());
+    /// outer rect of the windows before being tiled for the first time
+    static ref PRE_TILING_RECTS: Mutex<HashMap<isize, RECT>> = Mutex::new(HashMap::new());
+}
+
+impl WindowManagerV2 {
+    pub fn is_floating(window: &Window) -> bool {
+        trace_lock!(FLOATING).contains(&window.address())
+    }
+
+    /// Returns true if the window is now floating
+    pub fn toggle_float(window: &Window) -> Result<bool> {
+        let _retile = Self::begin_retile();
+        if trace_lock!(FLOATING).remove(&window.address()) {
+            if Self::should_be_managed(window.hwnd()) {
+                Self::add(window)?;
+                Self::force_retiling()?;
+            }
+            return Ok(false);
+        }
+
+        if !Self::is_managed(window) {
+            return Err("Window is not tiled".into());
+        }
+
+        trace_lock!(FLOATING).insert(window.address());
+        Self::remove(window)?;
+        Self::force_retiling()?;
+
+        let rect = trace_lock!(PRE_TILING_RECTS)
+            .get(&window.address())
+            .cloned();
+        if let Some(rect) = rect {
+            WindowsApi::unmaximize_window(window.hwnd())?;
+            WindowsApi::set_position(
+                window.hwnd(),
+                None,
+                &rect,
+                SWP_NOACTIVATE | SWP_ASYNCWINDOWPOS,
+            )?;
+        }
+        Ok(true)
+    }
+
+    pub(super) fn save_pre_tiling_rect(window: &Window) {
+        let mut rects = trace_lock!(PRE_TILING_RECTS);
+        if !rects.contains_key(&window.address()) {
+            if let Ok(rect) = WindowsApi::get_outer_window_rect(window.hwnd()) {
+                rects.insert(window.address(), rect);
+            }
+        }
+    }
+
+    pub(super) fn forget_floating(window: &Window) {
+        trace_lock!(FLOATING).remove(&window.address());
+        trace_lock!(PRE_TILING_RECTS).remove(&window.address());
+    }
+}
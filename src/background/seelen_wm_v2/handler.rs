@@ -1,18 +1,27 @@
 use windows::Win32::{
     Foundation::{HWND, RECT},
-    UI::WindowsAndMessaging::{
-        SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE, SWP_NOCOPYBITS, SWP_NOSENDCHANGING,
-    },
+    UI::WindowsAndMessaging::{FLASHW_ALL, FLASHW_TIMERNOFG, HWND_TOP, SW_SHOWNOACTIVATE},
 };
 
-use crate::{error_handler::Result, windows_api::WindowsApi};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use crate::{
+    error_handler::Result,
+    hook::HookManager,
+    modules::input::domain::{CompiledWindowFilter, WindowFilter, WindowInfo},
+    seelen_wm_v2::{instance::WindowManagerV2, metrics::WmMetrics, state::WmLayoutState},
+    windows_api::{window::Window, WindowEnumerator, WindowsApi},
+};
 use seelen_core::rect::Rect;
 
 #[tauri::command(async)]
 pub fn set_window_position(hwnd: isize, rect: Rect) -> Result<()> {
     let hwnd = HWND(hwnd as _);
 
-    if !WindowsApi::is_window(hwnd) || WindowsApi::is_iconic(hwnd) {
+    if !WindowsApi::is_window(hwnd) || WindowsApi::is_iconic(hwnd) || WindowManagerV2::is_paused() {
         return Ok(());
     }
 
@@ -26,14 +35,8 @@ pub fn set_window_position(hwnd: isize, rect: Rect) -> Result<()> {
         bottom: rect.bottom + shadow.bottom,
     };
 
-    // WindowsApi::move_window(hwnd, &rect)?;
-    WindowsApi::set_position(
-        hwnd,
-        None,
-        &rect,
-        SWP_NOACTIVATE | SWP_NOCOPYBITS | SWP_ASYNCWINDOWPOS | SWP_NOSENDCHANGING,
-    )?;
-    Ok(())
+    // the positions of all the tiles are committed together to avoid flickering
+    WindowManagerV2::queue_position(hwnd, rect)
 }
 
 #[tauri::command(async)]
@@ -53,3 +56,157 @@ pub fn request_focus(hwnd: isize) -> Result<()> {
     WindowsApi::async_force_set_foreground(hwnd);
     Ok(())
 }
+
+/// Flashes the caption and taskbar button of the window `count` times, the taskbar button keeps
+/// highlighted until the window comes to the foreground. With `count` 0 it flashes until then.
+#[tauri::command(async)]
+pub fn flash_window(hwnd: isize, count: u32) {
+    let hwnd = HWND(hwnd as _);
+    if WindowsApi::is_window(hwnd) {
+        WindowsApi::flash_window(hwnd, FLASHW_ALL | FLASHW_TIMERNOFG, count);
+    }
+}
+
+/// Manageable windows of the current desktop in front-to-back order,
+/// minimized windows are included and flagged.
+#[tauri::command(async)]
+pub fn get_windows_in_z_order() -> Vec<WindowInfo> {
+    let mut windows = Vec::new();
+    // the z-order can change while walking it, so we guard against cycles
+    let mut visited = HashSet::new();
+    let mut hwnd = WindowsApi::get_top_window();
+    while !hwnd.is_invalid() && visited.insert(hwnd.0 as isize) {
+        let window = Window::from(hwnd);
+        if window.is_real_window() && !window.is_cloaked() {
+            windows.push(WindowInfo::from(&window));
+        }
+        hwnd = WindowsApi::get_next_window(hwnd);
+    }
+    windows
+}
+
+/// Raises all the windows of an app, `identifier` can be an UMID or an exe (name or full path).
+/// The windows keep their relative z-order and the most recently active one ends focused.
+#[tauri::command(async)]
+pub fn raise_app_windows(identifier: String) -> Result<()> {
+    let by_umid = CompiledWindowFilter::try_from(WindowFilter {
+        umid: Some(identifier.clone()),
+        ..Default::default()
+    })?;
+    let by_exe = CompiledWindowFilter::try_from(WindowFilter {
+        exe: Some(identifier),
+        ..Default::default()
+    })?;
+
+    let windows: Vec<WindowInfo> = get_windows_in_z_order()
+        .into_iter()
+        .filter(|w| by_umid.matches(w) || by_exe.matches(w))
+        .collect();
+    if windows.is_empty() {
+        return Ok(());
+    }
+
+    let to_focus = HookManager::mru_windows()
+        .into_iter()
+        .find(|w| windows.iter().any(|info| info.hwnd == w.address()))
+        .map(|w| w.hwnd())
+        .unwrap_or(HWND(windows[0].hwnd as _));
+
+    // from back to front so the topmost of the group ends on top
+    for info in windows.iter().rev() {
+        let hwnd = HWND(info.hwnd as _);
+        if info.minimized {
+            WindowsApi::show_window_async(hwnd, SW_SHOWNOACTIVATE)?;
+        }
+        WindowsApi::bring_to(hwnd, HWND_TOP)?;
+    }
+
+    // same path as `request_focus` to bypass the foreground lock
+    WindowsApi::async_force_set_foreground(to_focus);
+    Ok(())
+}
+
+/// Top level windows matching all the set fields of the filter, see [`WindowFilter`].
+#[tauri::command(async)]
+pub fn find_windows(filter: WindowFilter) -> Result<Vec<WindowInfo>> {
+    let filter = CompiledWindowFilter::try_from(filter)?;
+    let windows = WindowEnumerator::new().map(Window::from)?;
+    Ok(windows
+        .iter()
+        .filter(|w| !filter.only_manageable || (w.is_real_window() && !w.is_cloaked()))
+        .map(WindowInfo::from)
+        .filter(|info| filter.matches(info))
+        .collect())
+}
+
+/// Suspends retiling, used during interactive drags. Without duration or with a longer one
+/// than 30 seconds the pause expires after that, so an unbalanced pause can't leave the wm off.
+#[tauri::command(async)]
+pub fn wm_pause(duration_ms: Option<u64>) -> Result<()> {
+    WindowManagerV2::pause(duration_ms.map(Duration::from_millis))
+}
+
+/// Resumes retiling and re-tiles the current layout
+#[tauri::command(async)]
+pub fn wm_resume() -> Result<()> {
+    WindowManagerV2::resume()
+}
+
+/// Floats a tiled window restoring its size before being tiled, or tiles it again if floating.
+/// Returns true if the window is now floating.
+#[tauri::command(async)]
+pub fn wm_toggle_float(hwnd: isize) -> Result<bool> {
+    WindowManagerV2::toggle_float(&Window::from(hwnd))
+}
+
+/// Groups the windows in the tile of the first one, only the active window of a stack is shown
+#[tauri::command(async)]
+pub fn wm_stack(hwnds: Vec<isize>) -> Result<()> {
+    WindowManagerV2::stack(&hwnds)
+}
+
+/// Shows the next window of a stack, `node_id` is the one reported by `wm_get_layout_state`
+#[tauri::command(async)]
+pub fn wm_stack_cycle(node_id: String, monitor_id: Option<String>) -> Result<()> {
+    WindowManagerV2::cycle_stack(&node_id, monitor_id)
+}
+
+/// Layout of the current workspace by monitor id, including the stacks membership
+#[tauri::command(async)]
+pub fn wm_get_layout_state() -> Result<HashMap<String, WmLayoutState>> {
+    WindowManagerV2::layout_state()
+}
+
+/// Saves the structure of the current layout as a named preset, without its windows.
+/// Uses the monitor of the foreground window if no monitor is specified.
+#[tauri::command(async)]
+pub fn wm_save_layout_preset(name: String, monitor_id: Option<String>) -> Result<()> {
+    WindowManagerV2::save_layout_preset(&name, monitor_id)
+}
+
+/// Re-tiles the current windows of the monitor into a saved preset
+#[tauri::command(async)]
+pub fn wm_apply_layout_preset(name: String, monitor_id: Option<String>) -> Result<()> {
+    WindowManagerV2::apply_layout_preset(&name, monitor_id)
+}
+
+/// Timings of the last retile, useful to know if the time goes to the layout tree or to moving windows
+#[tauri::command(async)]
+pub fn wm_get_metrics() -> WmMetrics {
+    WindowManagerV2::metrics()
+}
+
+/// None when the elevation of the window can't be read
+#[tauri::command(async)]
+pub fn is_window_elevated(hwnd: isize) -> Option<bool> {
+    Window::from(HWND(hwnd as _)).process().is_elevated()
+}
+
+/// Windows in most-recently-used order, the first one is the last focused
+#[tauri::command(async)]
+pub fn get_mru_windows() -> Vec<WindowInfo> {
+    HookManager::mru_windows()
+        .iter()
+        .map(WindowInfo::from)
+        .collect()
+}
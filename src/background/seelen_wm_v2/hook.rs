@@ -1,12 +1,17 @@
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use seelen_core::rect::Rect;
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::{
     error_handler::Result,
+    log_error,
     modules::virtual_desk::VirtualDesktopEvent,
     trace_lock,
+    utils::spawn_named_thread,
     windows_api::{monitor::Monitor, window::Window},
     winevent::WinEvent,
 };
@@ -17,9 +22,39 @@ lazy_static! {
     static ref SystemMoveSizeStartRect: Arc<Mutex<Rect>> = Arc::new(Mutex::new(Rect::default()));
     static ref SystemMoveSizeStartMonitor: Arc<Mutex<Monitor>> =
         Arc::new(Mutex::new(Monitor::from(0)));
+    /// retiling is suspended until this instant
+    static ref PAUSED_UNTIL: Mutex<Option<Instant>> = Mutex::new(None);
 }
 
+/// pauses without duration expire after this, so a missing resume can't leave the wm stuck off
+const MAX_PAUSE: Duration = Duration::from_secs(30);
+
 impl WindowManagerV2 {
+    pub fn pause(duration: Option<Duration>) -> Result<()> {
+        let duration = duration.unwrap_or(MAX_PAUSE).min(MAX_PAUSE);
+        *trace_lock!(PAUSED_UNTIL) = Some(Instant::now() + duration);
+        spawn_named_thread("WM Pause", move || {
+            std::thread::sleep(duration);
+            // a newer pause could have extended the deadline
+            let expired = trace_lock!(PAUSED_UNTIL).is_some_and(|until| until <= Instant::now());
+            if expired {
+                log_error!(Self::resume());
+            }
+        })?;
+        Ok(())
+    }
+
+    pub fn resume() -> Result<()> {
+        if trace_lock!(PAUSED_UNTIL).take().is_some() {
+            Self::force_retiling()?;
+        }
+        Ok(())
+    }
+
+    pub fn is_paused() -> bool {
+        trace_lock!(PAUSED_UNTIL).is_some_and(|until| until > Instant::now())
+    }
+
     pub fn process_vd_event(event: &VirtualDesktopEvent) -> Result<()> {
         match event {
             VirtualDesktopEvent::DesktopChanged { new, old: _ } => {
@@ -30,6 +65,7 @@ impl WindowManagerV2 {
                 let window = &Window::from(*window);
                 if Self::is_managed(window) {
                     log::trace!("window changed: {:?}", window);
+                    let _retile = Self::begin_retile();
                     Self::remove(window)?;
                     Self::add(window)?;
                 }
@@ -43,6 +79,7 @@ impl WindowManagerV2 {
         if !Self::is_managed(window) {
             return Ok(());
         }
+        let _retile = Self::begin_retile();
 
         if *SystemMoveSizeStartMonitor.lock() != window.monitor() {
             log::trace!("window moved of monitor");
@@ -92,6 +129,17 @@ impl WindowManagerV2 {
     }
 
     pub fn process_win_event(event: WinEvent, window: &Window) -> Result<()> {
+        if Self::is_paused()
+            && matches!(
+                event,
+                WinEvent::SystemMoveSizeStart
+                    | WinEvent::SystemMoveSizeEnd
+                    | WinEvent::ObjectLocationChange
+            )
+        {
+            return Ok(());
+        }
+
         match event {
             WinEvent::SystemMoveSizeStart => {
                 if Self::is_managed(window) {
@@ -108,6 +156,9 @@ impl WindowManagerV2 {
                 }
             }
             WinEvent::ObjectDestroy | WinEvent::ObjectHide | WinEvent::SystemMinimizeStart => {
+                if event == WinEvent::ObjectDestroy {
+                    Self::forget_floating(window);
+                }
                 if Self::is_managed(window) {
                     Self::remove(window)?;
                 }
@@ -133,6 +184,20 @@ impl WindowManagerV2 {
             }
             WinEvent::SyntheticFullscreenStart(_) => Self::set_overlay_visibility(false)?,
             WinEvent::SyntheticFullscreenEnd(_) => Self::set_overlay_visibility(true)?,
+            // pinned windows float over the layout so they should not be retiled
+            WinEvent::SyntheticTopmostStart => {
+                if Self::is_managed(window) {
+                    let _retile = Self::begin_retile();
+                    Self::remove(window)?;
+                    Self::force_retiling()?;
+                }
+            }
+            WinEvent::SyntheticTopmostEnd => {
+                if !Self::is_managed(window) && Self::should_be_managed(window.hwnd()) {
+                    Self::add(window)?;
+                    Self::set_overlay_visibility(true)?;
+                }
+            }
             _ => {}
         };
         Ok(())
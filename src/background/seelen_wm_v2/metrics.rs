@@ -0,0 +1,123 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::trace_lock;
+
+use super::{state::WM_STATE, WindowManagerV2};
+
+/// retiles used for the rolling average
+const HISTORY_SIZE: usize = 20;
+
+lazy_static! {
+    static ref METRICS: Mutex<MetricsState> = Mutex::new(MetricsState::default());
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WmMetrics {
+    /// time spent updating the layout tree on the last window added or removed
+    pub tree_update_ms: f64,
+    /// time from the last layout change until its last window was positioned
+    pub retile_ms: f64,
+    /// average of `retile_ms` over the last 20 retiles
+    pub avg_retile_ms: f64,
+    /// positioning commits done by the last retile, a batch of windows moved with
+    /// `DeferWindowPos` counts as one, fallbacks to `SetWindowPos` count once per window
+    pub set_window_pos_calls: u32,
+    /// time spent inside those calls, compare it with `retile_ms` to see where the time goes
+    pub set_window_pos_ms: f64,
+    pub managed_windows: usize,
+}
+
+#[derive(Debug, Default)]
+struct MetricsState {
+    retile_started: Option<Instant>,
+    current: WmMetrics,
+    history: VecDeque<f64>,
+    /// open `RetileBatch` guards, retiles requested meanwhile belong to the batch one
+    open_batches: usize,
+}
+
+/// Groups all the layout changes made while alive into a single retile measurement,
+/// e.g. stacking removes windows from several tiles and then retiles everything.
+pub(super) struct RetileBatch;
+
+impl Drop for RetileBatch {
+    fn drop(&mut self) {
+        trace_lock!(METRICS).open_batches -= 1;
+    }
+}
+
+impl MetricsState {
+    fn start_retile(&mut self) {
+        self.push_history();
+        self.retile_started = Some(Instant::now());
+        self.current.retile_ms = 0.0;
+        self.current.set_window_pos_calls = 0;
+        self.current.set_window_pos_ms = 0.0;
+    }
+
+    fn push_history(&mut self) {
+        if self.current.set_window_pos_calls == 0 {
+            return;
+        }
+        self.history.push_back(self.current.retile_ms);
+        if self.history.len() > HISTORY_SIZE {
+            self.history.pop_front();
+        }
+    }
+}
+
+impl WindowManagerV2 {
+    /// Called when a new layout is sent to be tiled
+    pub(super) fn on_retile_requested() {
+        let mut metrics = trace_lock!(METRICS);
+        if metrics.open_batches == 0 {
+            metrics.start_retile();
+        }
+    }
+
+    /// Starts a retile measurement that lasts until the returned guard is dropped
+    pub(super) fn begin_retile() -> RetileBatch {
+        let mut metrics = trace_lock!(METRICS);
+        if metrics.open_batches == 0 {
+            metrics.start_retile();
+        }
+        metrics.open_batches += 1;
+        RetileBatch
+    }
+
+    pub(super) fn on_tree_updated(elapsed: Duration) {
+        trace_lock!(METRICS).current.tree_update_ms = elapsed.as_secs_f64() * 1000.0;
+    }
+
+    pub fn on_windows_positioned(calls: u32, elapsed: Duration) {
+        let mut metrics = trace_lock!(METRICS);
+        metrics.current.set_window_pos_calls += calls;
+        metrics.current.set_window_pos_ms += elapsed.as_secs_f64() * 1000.0;
+        if let Some(started) = metrics.retile_started {
+            metrics.current.retile_ms = started.elapsed().as_secs_f64() * 1000.0;
+        }
+    }
+
+    pub fn metrics() -> WmMetrics {
+        let managed_windows = trace_lock!(WM_STATE).managed_count();
+        let metrics = trace_lock!(METRICS);
+        let mut result = metrics.current.clone();
+        let mut samples: Vec<f64> = metrics.history.iter().cloned().collect();
+        if result.set_window_pos_calls > 0 {
+            samples.push(result.retile_ms);
+        }
+        if !samples.is_empty() {
+            result.avg_retile_ms = samples.iter().sum::<f64>() / samples.len() as f64;
+        }
+        result.managed_windows = managed_windows;
+        result
+    }
+}
@@ -1,10 +1,17 @@
 pub mod cli;
+pub mod floating;
 pub mod handler;
 pub mod hook;
 pub mod instance;
+pub mod metrics;
 pub mod node_impl;
+pub mod positioning;
+pub mod presets;
+pub mod stack;
 pub mod state;
 
+use std::time::{Duration, Instant};
+
 use instance::WindowManagerV2;
 use seelen_core::{handlers::SeelenEvent, state::AppExtraFlag};
 use state::{WmV2StateWorkspace, WM_STATE};
@@ -45,6 +52,10 @@ impl WindowManagerV2 {
     }
 
     fn should_be_managed(hwnd: HWND) -> bool {
+        if Self::is_floating(&Window::from(hwnd)) {
+            return false;
+        }
+
         if let Some(config) = FULL_STATE.load().get_app_config_by_window(hwnd) {
             if config.options.contains(&AppExtraFlag::Force) {
                 return true;
@@ -64,11 +75,13 @@ impl WindowManagerV2 {
     }
 
     pub fn force_retiling() -> Result<()> {
+        Self::on_retile_requested();
         get_app_handle().emit(SeelenEvent::WMForceRetiling, ())?;
         Ok(())
     }
 
     fn render_workspace(monitor_id: &str, w: &WmV2StateWorkspace) -> Result<()> {
+        Self::on_retile_requested();
         get_app_handle().emit_to(
             Self::get_label(monitor_id),
             SeelenEvent::WMSetLayout,
@@ -88,6 +101,7 @@ impl WindowManagerV2 {
     }
 
     fn add(window: &Window) -> Result<()> {
+        Self::save_pre_tiling_rect(window);
         let mut state = trace_lock!(WM_STATE);
         let vd_manager = get_vd_manager();
         let current_workspace_id = vd_manager.get_current()?.id();
@@ -114,8 +128,11 @@ impl WindowManagerV2 {
 
         if let Some(monitor) = state.get_monitor_mut(&monitor_id) {
             let workspace = monitor.get_workspace_mut(&workspace_id);
+            let started = Instant::now();
             workspace.add_window(window);
+            Self::on_tree_updated(started.elapsed());
             if workspace_id == current_workspace_id {
+                Self::on_retile_requested();
                 get_app_handle().emit_to(
                     Self::get_label(&monitor_id),
                     SeelenEvent::WMSetLayout,
@@ -131,10 +148,18 @@ impl WindowManagerV2 {
         let current_workspace = get_vd_manager().get_current()?.id();
 
         // TODO this can be optimized, later
+        let mut tree_update = Duration::ZERO;
+        // the layouts of all the monitors are part of the same retile
+        let mut retile = None;
         for (monitor_id, monitor) in state.monitors.iter_mut() {
             for (workspace_id, workspace) in monitor.workspaces.iter_mut() {
+                let started = Instant::now();
                 workspace.remove_window(window);
+                tree_update += started.elapsed();
                 if workspace_id == &current_workspace {
+                    if retile.is_none() {
+                        retile = Some(Self::begin_retile());
+                    }
                     get_app_handle().emit_to(
                         Self::get_label(monitor_id),
                         SeelenEvent::WMSetLayout,
@@ -143,6 +168,7 @@ impl WindowManagerV2 {
                 }
             }
         }
+        Self::on_tree_updated(tree_update);
         Ok(())
     }
 
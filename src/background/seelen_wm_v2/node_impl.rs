@@ -1,9 +1,21 @@
 use evalexpr::{context_map, eval_with_context, HashMapContext};
 use itertools::Itertools;
-use seelen_core::state::WmNode;
+use seelen_core::state::{NodeSubtype, WmLeafNode, WmNode, WmStackNode};
+use serde::Serialize;
+use serde_json::{json, Value};
 
 use crate::{error_handler::Result, modules::input::domain::Point, windows_api::window::Window};
 
+/// A stack of windows sharing a tile, only the active one is shown
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WmStackInfo {
+    /// path of child indexes from the root of the layout, joined by `/`, the root is `""`
+    pub node_id: String,
+    pub handles: Vec<isize>,
+    pub active_index: Option<usize>,
+}
+
 #[derive(Debug)]
 pub struct WmNodeImpl(WmNode);
 
@@ -91,9 +103,8 @@ impl WmNodeImpl {
                     handles.push(handle);
                 }
             }
-            WmNode::Stack(stack) => {
-                handles.append(&mut stack.handles);
-            }
+            // stacks are explicit groupings made by the user so they keep their windows on reindexing
+            WmNode::Stack(_) => {}
             WmNode::Fallback(fallback) => {
                 handles.append(&mut fallback.handles);
             }
@@ -244,6 +255,9 @@ impl WmNodeImpl {
     ///
     /// **Note:** Reindexing can fail on add some windows so it will return failed handles as residual
     pub fn remove_window(&mut self, window: &Window) -> Vec<isize> {
+        if let Err(err) = Self::_remove_from_stacks(self.inner_mut(), window.address()) {
+            log::error!("Failed to remove window from stack: {:?}", err);
+        }
         let handles = Self::_drain(self.inner_mut());
         let context = Self::create_context(
             if handles.contains(&window.address()) {
@@ -265,6 +279,271 @@ impl WmNodeImpl {
         residual
     }
 
+    /// The stack takes the place of the leaf keeping its priority, grow factor and condition,
+    /// it is marked as temporal to be turned back into a leaf when it is no longer needed.
+    fn leaf_to_stack(node: &mut WmNode) {
+        let WmNode::Leaf(leaf) = node else {
+            return;
+        };
+        let stack = WmStackNode {
+            subtype: NodeSubtype::Temporal,
+            priority: leaf.priority,
+            grow_factor: leaf.grow_factor,
+            condition: leaf.condition.clone(),
+            active: leaf.handle,
+            handles: leaf.handle.into_iter().collect(),
+        };
+        *node = WmNode::Stack(stack);
+    }
+
+    fn stack_to_leaf(node: &mut WmNode) {
+        let WmNode::Stack(stack) = node else {
+            return;
+        };
+        let leaf = WmLeafNode {
+            subtype: stack.subtype.clone(),
+            priority: stack.priority,
+            grow_factor: stack.grow_factor,
+            condition: stack.condition.clone(),
+            handle: stack.handles.first().copied(),
+        };
+        *node = WmNode::Leaf(leaf);
+    }
+
+    fn is_temporal(node: &WmNode) -> bool {
+        matches!(node, WmNode::Stack(stack) if matches!(stack.subtype, NodeSubtype::Temporal))
+    }
+
+    /// stacks created at runtime are turned back into leaves when only one window remains
+    fn _remove_from_stacks(root: &mut WmNode, addr: isize) -> Result<()> {
+        match root {
+            WmNode::Stack(stack) => {
+                if !stack.handles.contains(&addr) {
+                    return Ok(());
+                }
+                stack.handles.retain(|h| *h != addr);
+                if stack.active == Some(addr) {
+                    stack.active = stack.handles.first().cloned();
+                }
+                if stack.handles.len() <= 1 && Self::is_temporal(root) {
+                    Self::stack_to_leaf(root);
+                }
+            }
+            WmNode::Vertical(vertical) => {
+                for child in vertical.children.iter_mut() {
+                    Self::_remove_from_stacks(child, addr)?;
+                }
+            }
+            WmNode::Horizontal(horizontal) => {
+                for child in horizontal.children.iter_mut() {
+                    Self::_remove_from_stacks(child, addr)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn _find_container_mut(root: &mut WmNode, addr: isize) -> Option<&mut WmNode> {
+        let found = match root {
+            WmNode::Leaf(leaf) => leaf.handle == Some(addr),
+            WmNode::Stack(stack) => stack.handles.contains(&addr),
+            WmNode::Fallback(fallback) => fallback.handles.contains(&addr),
+            WmNode::Vertical(vertical) => {
+                return vertical
+                    .children
+                    .iter_mut()
+                    .find_map(|child| Self::_find_container_mut(child, addr));
+            }
+            WmNode::Horizontal(horizontal) => {
+                return horizontal
+                    .children
+                    .iter_mut()
+                    .find_map(|child| Self::_find_container_mut(child, addr));
+            }
+        };
+        if found {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    fn _node_at_path_mut<'a>(root: &'a mut WmNode, path: &[usize]) -> Option<&'a mut WmNode> {
+        let Some((idx, rest)) = path.split_first() else {
+            return Some(root);
+        };
+        match root {
+            WmNode::Vertical(vertical) => {
+                Self::_node_at_path_mut(vertical.children.get_mut(*idx)?, rest)
+            }
+            WmNode::Horizontal(horizontal) => {
+                Self::_node_at_path_mut(horizontal.children.get_mut(*idx)?, rest)
+            }
+            _ => None,
+        }
+    }
+
+    fn _stacks(node: &WmNode, path: String, stacks: &mut Vec<WmStackInfo>) {
+        let children = match node {
+            WmNode::Stack(stack) => {
+                stacks.push(WmStackInfo {
+                    node_id: path,
+                    handles: stack.handles.clone(),
+                    active_index: stack
+                        .active
+                        .and_then(|active| stack.handles.iter().position(|h| *h == active)),
+                });
+                return;
+            }
+            WmNode::Vertical(vertical) => &vertical.children,
+            WmNode::Horizontal(horizontal) => &horizontal.children,
+            _ => return,
+        };
+        for (idx, child) in children.iter().enumerate() {
+            let child_path = if path.is_empty() {
+                idx.to_string()
+            } else {
+                format!("{path}/{idx}")
+            };
+            Self::_stacks(child, child_path, stacks);
+        }
+    }
+
+    /// Groups the windows on the tile of the first one, the first window ends as the active one.
+    pub fn stack(&mut self, windows: &[Window]) -> Result<()> {
+        let (first, rest) = windows.split_first().ok_or("No windows to stack")?;
+        if !self.contains(first) {
+            return Err("Window is not tiled on this workspace".into());
+        }
+
+        for window in rest {
+            let residual = self.remove_window(window);
+            if !residual.is_empty() {
+                log::warn!("Current Layout is full, and fallback container was not found");
+            }
+        }
+
+        let node =
+            Self::_find_container_mut(self.inner_mut(), first.address()).ok_or("Node not found")?;
+        if let WmNode::Leaf(_) = node {
+            Self::leaf_to_stack(node);
+        }
+        match node {
+            WmNode::Stack(stack) => {
+                for window in rest {
+                    if !stack.handles.contains(&window.address()) {
+                        stack.handles.push(window.address());
+                    }
+                }
+                stack.active = Some(first.address());
+            }
+            _ => return Err("The tile of the window can't be stacked".into()),
+        }
+        Ok(())
+    }
+
+    /// Shows the next window of the stack, returns the new active window.
+    pub fn cycle_stack(&mut self, node_id: &str) -> Result<isize> {
+        let path = node_id
+            .split('/')
+            .filter(|p| !p.is_empty())
+            .map(|p| p.parse::<usize>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| "Invalid node id")?;
+        match Self::_node_at_path_mut(self.inner_mut(), &path) {
+            Some(WmNode::Stack(stack)) if !stack.handles.is_empty() => {
+                let current = stack
+                    .active
+                    .and_then(|active| stack.handles.iter().position(|h| *h == active))
+                    .unwrap_or(0);
+                let next = stack.handles[(current + 1) % stack.handles.len()];
+                stack.active = Some(next);
+                Ok(next)
+            }
+            _ => Err("Stack not found".into()),
+        }
+    }
+
+    fn _clear(node: &mut WmNode) -> Result<()> {
+        match node {
+            WmNode::Leaf(leaf) => leaf.handle = None,
+            WmNode::Stack(stack) => {
+                stack.handles.clear();
+                stack.active = None;
+                if Self::is_temporal(node) {
+                    Self::stack_to_leaf(node);
+                }
+            }
+            WmNode::Fallback(fallback) => {
+                fallback.handles.clear();
+                fallback.active = None;
+            }
+            WmNode::Vertical(vertical) => {
+                for child in vertical.children.iter_mut() {
+                    Self::_clear(child)?;
+                }
+            }
+            WmNode::Horizontal(horizontal) => {
+                for child in horizontal.children.iter_mut() {
+                    Self::_clear(child)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn _handles(node: &WmNode, handles: &mut Vec<isize>) {
+        match node {
+            WmNode::Leaf(leaf) => handles.extend(leaf.handle),
+            WmNode::Stack(stack) => handles.extend(&stack.handles),
+            WmNode::Fallback(fallback) => handles.extend(&fallback.handles),
+            WmNode::Vertical(vertical) => {
+                for child in vertical.children.iter() {
+                    Self::_handles(child, handles);
+                }
+            }
+            WmNode::Horizontal(horizontal) => {
+                for child in horizontal.children.iter() {
+                    Self::_handles(child, handles);
+                }
+            }
+        }
+    }
+
+    /// the structure of the layout without windows, stacks made at runtime are reverted to leaves
+    pub fn topology(&self) -> Result<WmNode> {
+        let mut node = self.inner().clone();
+        Self::_clear(&mut node)?;
+        Ok(node)
+    }
+
+    /// all the windows in the layout, including the hidden ones of stacks and fallbacks
+    pub fn handles(&self) -> Vec<isize> {
+        let mut handles = Vec::new();
+        Self::_handles(self.inner(), &mut handles);
+        handles
+    }
+
+    pub fn stacks(&self) -> Vec<WmStackInfo> {
+        let mut stacks = Vec::new();
+        Self::_stacks(self.inner(), String::new(), &mut stacks);
+        stacks
+    }
+
+    /// other windows sharing the stack with the window
+    pub fn stack_siblings(&self, window: &Window) -> Vec<isize> {
+        match Self::_trace(self.inner(), window).last() {
+            Some(WmNode::Stack(stack)) => stack
+                .handles
+                .iter()
+                .filter(|h| **h != window.address())
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
     pub fn contains(&self, window: &Window) -> bool {
         !Self::_trace(self.inner(), window).is_empty()
     }
@@ -0,0 +1,284 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use windows::Win32::{
+    Foundation::{HWND, RECT},
+    UI::WindowsAndMessaging::{
+        SET_WINDOW_POS_FLAGS, SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE, SWP_NOCOPYBITS,
+        SWP_NOSENDCHANGING,
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error, trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{window::Window, WindowsApi},
+};
+
+use super::WindowManagerV2;
+
+/// positions requested within this time are committed together, the leaves of the
+/// layout request their positions one by one after each render
+const BATCH_WINDOW: Duration = Duration::from_millis(10);
+const FLAGS: SET_WINDOW_POS_FLAGS = SWP_NOACTIVATE | SWP_NOCOPYBITS | SWP_NOSENDCHANGING;
+
+#[derive(Debug, Clone)]
+struct PendingPosition {
+    hwnd: isize,
+    /// placed just behind this window
+    after: Option<isize>,
+    rect: RECT,
+    /// receives the outcome, only set for the window that requested the position
+    notify: Option<Sender<std::result::Result<(), String>>>,
+}
+
+/// Win32 calls used to commit the queued positions, abstracted to be tested without windows
+trait Positioner {
+    /// hung windows would block the whole deferred pass until they respond
+    fn is_hung(&self, hwnd: isize) -> bool;
+    /// all the positions in a single `DeferWindowPos` pass
+    fn commit_deferred(&mut self, batch: &[&PendingPosition]) -> Result<()>;
+    /// `SetWindowPos` with `SWP_ASYNCWINDOWPOS`, it doesn't wait for the window thread
+    fn set_position_async(&mut self, position: &PendingPosition) -> Result<()>;
+}
+
+struct Win32Positioner;
+impl Positioner for Win32Positioner {
+    fn is_hung(&self, hwnd: isize) -> bool {
+        WindowsApi::is_hung_app_window(HWND(hwnd as _))
+    }
+
+    fn commit_deferred(&mut self, batch: &[&PendingPosition]) -> Result<()> {
+        let mut hdwp = WindowsApi::begin_defer_window_pos(batch.len())?;
+        for p in batch {
+            hdwp = WindowsApi::defer_window_pos(
+                hdwp,
+                HWND(p.hwnd as _),
+                p.after.map(|after| HWND(after as _)),
+                &p.rect,
+                FLAGS,
+            )?;
+        }
+        WindowsApi::end_defer_window_pos(hdwp)
+    }
+
+    fn set_position_async(&mut self, p: &PendingPosition) -> Result<()> {
+        WindowsApi::set_position(
+            HWND(p.hwnd as _),
+            p.after.map(|after| HWND(after as _)),
+            &p.rect,
+            FLAGS | SWP_ASYNCWINDOWPOS,
+        )
+    }
+}
+
+/// Returns the positioning calls done and the outcome of each position. Hung windows are
+/// moved asynchronously apart from the deferred pass, as before batching.
+fn commit_positions<'a>(
+    pending: &'a [PendingPosition],
+    positioner: &mut impl Positioner,
+) -> (u32, Vec<(&'a PendingPosition, Result<()>)>) {
+    let (hung, responsive): (Vec<&PendingPosition>, Vec<&PendingPosition>) =
+        pending.iter().partition(|p| positioner.is_hung(p.hwnd));
+
+    let mut calls = 0;
+    let mut outcomes = Vec::with_capacity(pending.len());
+    if !responsive.is_empty() {
+        match positioner.commit_deferred(&responsive) {
+            Ok(()) => {
+                calls += 1;
+                outcomes.extend(responsive.iter().map(|p| (*p, Ok(()))));
+            }
+            Err(err) => {
+                // a single invalid window (closed, elevated, etc) invalidates the whole batch
+                log::warn!("Deferred positioning failed, moving one by one: {:?}", err);
+                for p in responsive {
+                    calls += 1;
+                    outcomes.push((p, positioner.set_position_async(p)));
+                }
+            }
+        }
+    }
+    for p in hung {
+        calls += 1;
+        outcomes.push((p, positioner.set_position_async(p)));
+    }
+    (calls, outcomes)
+}
+
+lazy_static! {
+    static ref PENDING: Mutex<Vec<PendingPosition>> = Mutex::new(Vec::new());
+}
+
+static FLUSH_SCHEDULED: AtomicBool = AtomicBool::new(false);
+
+impl WindowManagerV2 {
+    /// Queues the position of a tiled window, the hidden windows of its stack share the tile
+    /// just behind it. All the queued positions are applied in a single `DeferWindowPos` pass,
+    /// this waits for that pass and returns the outcome for the window.
+    pub fn queue_position(hwnd: HWND, rect: RECT) -> Result<()> {
+        let addr = hwnd.0 as isize;
+        let siblings = Self::stack_siblings(&Window::from(hwnd));
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+        {
+            let mut pending = trace_lock!(PENDING);
+            pending.retain(|p| p.hwnd != addr && !siblings.contains(&p.hwnd));
+            pending.push(PendingPosition {
+                hwnd: addr,
+                after: None,
+                rect,
+                notify: Some(sender),
+            });
+            pending.extend(siblings.iter().map(|sibling| PendingPosition {
+                hwnd: *sibling,
+                after: Some(addr),
+                rect,
+                notify: None,
+            }));
+        }
+
+        if !FLUSH_SCHEDULED.swap(true, Ordering::SeqCst) {
+            spawn_named_thread("WM Positioning", || {
+                std::thread::sleep(BATCH_WINDOW);
+                FLUSH_SCHEDULED.store(false, Ordering::SeqCst);
+                Self::flush_positions();
+            })?;
+        }
+
+        match receiver.recv() {
+            Ok(Err(err)) => Err(err.into()),
+            // disconnected means that a newer position for the window replaced this one
+            Ok(Ok(())) | Err(_) => Ok(()),
+        }
+    }
+
+    fn flush_positions() {
+        let pending = std::mem::take(&mut *trace_lock!(PENDING));
+        if pending.is_empty() {
+            return;
+        }
+
+        let started = Instant::now();
+        let (calls, outcomes) = commit_positions(&pending, &mut Win32Positioner);
+        Self::on_windows_positioned(calls, started.elapsed());
+
+        for (p, outcome) in outcomes {
+            match &p.notify {
+                Some(notify) => {
+                    let _ = notify.send(outcome.map_err(|err| err.message().to_owned()));
+                }
+                None => log_error!(outcome),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MockPositioner {
+        hung: HashSet<isize>,
+        fail_deferred: bool,
+        deferred_commits: Vec<Vec<isize>>,
+        async_positions: Vec<isize>,
+    }
+
+    impl Positioner for MockPositioner {
+        fn is_hung(&self, hwnd: isize) -> bool {
+            self.hung.contains(&hwnd)
+        }
+
+        fn commit_deferred(&mut self, batch: &[&PendingPosition]) -> Result<()> {
+            if self.fail_deferred {
+                return Err("invalid window handle".into());
+            }
+            self.deferred_commits
+                .push(batch.iter().map(|p| p.hwnd).collect());
+            Ok(())
+        }
+
+        fn set_position_async(&mut self, p: &PendingPosition) -> Result<()> {
+            self.async_positions.push(p.hwnd);
+            if p.hwnd < 0 {
+                return Err("invalid window handle".into());
+            }
+            Ok(())
+        }
+    }
+
+    fn pending(handles: impl IntoIterator<Item = isize>) -> Vec<PendingPosition> {
+        handles
+            .into_iter()
+            .map(|hwnd| PendingPosition {
+                hwnd,
+                after: None,
+                rect: RECT {
+                    left: 0,
+                    top: 0,
+                    right: 100,
+                    bottom: 100,
+                },
+                notify: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn retile_of_n_windows_is_a_single_commit() {
+        let pending = pending(1..=50);
+        let mut positioner = MockPositioner::default();
+        let (calls, outcomes) = commit_positions(&pending, &mut positioner);
+
+        assert_eq!(calls, 1);
+        assert_eq!(
+            positioner.deferred_commits,
+            vec![(1..=50).collect::<Vec<_>>()]
+        );
+        assert!(positioner.async_positions.is_empty());
+        assert_eq!(outcomes.len(), 50);
+        assert!(outcomes.iter().all(|(_, outcome)| outcome.is_ok()));
+    }
+
+    #[test]
+    fn hung_windows_are_moved_async_out_of_the_batch() {
+        let pending = pending(1..=5);
+        let mut positioner = MockPositioner {
+            hung: HashSet::from([3]),
+            ..Default::default()
+        };
+        let (calls, _) = commit_positions(&pending, &mut positioner);
+
+        assert_eq!(calls, 2);
+        assert_eq!(positioner.deferred_commits, vec![vec![1, 2, 4, 5]]);
+        assert_eq!(positioner.async_positions, vec![3]);
+    }
+
+    #[test]
+    fn failed_batch_falls_back_and_reports_each_window() {
+        let pending = pending([1, -2, 3]);
+        let mut positioner = MockPositioner {
+            fail_deferred: true,
+            ..Default::default()
+        };
+        let (calls, outcomes) = commit_positions(&pending, &mut positioner);
+
+        assert_eq!(calls, 3);
+        assert_eq!(positioner.async_positions, vec![1, -2, 3]);
+        let failed: Vec<isize> = outcomes
+            .iter()
+            .filter(|(_, outcome)| outcome.is_err())
+            .map(|(p, _)| p.hwnd)
+            .collect();
+        assert_eq!(failed, vec![-2]);
+    }
+}
@@ -0,0 +1,94 @@
+use seelen_core::state::WindowManagerLayout;
+use serde_json::json;
+
+use crate::{
+    error_handler::Result, modules::virtual_desk::get_vd_manager, trace_lock,
+    utils::constants::SEELEN_COMMON,
+};
+
+use super::{state::WM_STATE, WindowManagerV2};
+
+impl WindowManagerV2 {
+    /// Presets are stored as user layouts named `<name>.yml`, names can only contain
+    /// letters, numbers, spaces, `-` and `_`, and can't match the name of a bundled layout.
+    fn preset_filename(name: &str) -> Result<String> {
+        let name = name.trim();
+        if name.is_empty()
+            || name.len() > 64
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_')
+        {
+            return Err(format!("Invalid layout preset name: {name:?}").into());
+        }
+
+        let clobbers_bundled = std::fs::read_dir(SEELEN_COMMON.bundled_layouts_path())?
+            .flatten()
+            .any(|entry| {
+                entry
+                    .path()
+                    .file_stem()
+                    .is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(name))
+            });
+        if clobbers_bundled {
+            return Err(format!("{name} is already used by a bundled layout").into());
+        }
+
+        Ok(format!("{name}.yml"))
+    }
+
+    /// Saves the structure of the current layout of the monitor, without its windows.
+    pub fn save_layout_preset(name: &str, monitor_id: Option<String>) -> Result<()> {
+        let filename = Self::preset_filename(name)?;
+        let monitor_id = Self::monitor_or_foreground(monitor_id)?;
+        let current_workspace = get_vd_manager().get_current()?.id();
+
+        let layout: WindowManagerLayout = {
+            let mut state = trace_lock!(WM_STATE);
+            let monitor = state
+                .get_monitor_mut(&monitor_id)
+                .ok_or("Monitor not found")?;
+            let workspace = monitor.get_workspace_mut(&current_workspace);
+            serde_json::from_value(json!({
+                "info": {
+                    "displayName": name.trim(),
+                    "author": "User",
+                    "description": "Preset saved from a workspace arrangement",
+                },
+                "no_fallback_behavior": workspace.no_fallback_behavior(),
+                "structure": workspace.topology()?,
+            }))?
+        };
+
+        // the layouts folder is watched so the preset will be loaded into the state
+        std::fs::write(
+            SEELEN_COMMON.user_layouts_path().join(filename),
+            serde_yaml::to_string(&layout)?,
+        )?;
+        Ok(())
+    }
+
+    /// Tiles the current windows of the monitor into the preset
+    pub fn apply_layout_preset(name: &str, monitor_id: Option<String>) -> Result<()> {
+        let filename = Self::preset_filename(name)?;
+        let path = SEELEN_COMMON.user_layouts_path().join(filename);
+        if !path.exists() {
+            return Err(format!("Layout preset {name} not found").into());
+        }
+        let layout: WindowManagerLayout = serde_yaml::from_str(&std::fs::read_to_string(path)?)?;
+
+        let monitor_id = Self::monitor_or_foreground(monitor_id)?;
+        let current_workspace = get_vd_manager().get_current()?.id();
+
+        let _retile = Self::begin_retile();
+        let mut state = trace_lock!(WM_STATE);
+        let monitor = state
+            .get_monitor_mut(&monitor_id)
+            .ok_or("Monitor not found")?;
+        let workspace = monitor.get_workspace_mut(&current_workspace);
+        workspace.set_layout(layout);
+        Self::render_workspace(&monitor_id, workspace)?;
+        Self::force_retiling()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use windows::Win32::Foundation::HWND;
+
+use crate::{
+    error_handler::Result,
+    modules::virtual_desk::get_vd_manager,
+    trace_lock,
+    windows_api::{window::Window, WindowsApi},
+};
+
+use super::{
+    state::{WmLayoutState, WM_STATE},
+    WindowManagerV2,
+};
+
+impl WindowManagerV2 {
+    /// Groups the windows on the tile of the first one, all of them should be tiled.
+    pub fn stack(handles: &[isize]) -> Result<()> {
+        let windows: Vec<Window> = handles.iter().map(|h| Window::from(*h)).collect();
+        let first = windows.first().ok_or("No windows to stack")?;
+        if !windows.iter().all(Self::is_managed) {
+            return Err("All the windows to stack should be tiled".into());
+        }
+        let _retile = Self::begin_retile();
+
+        // the windows could be on other monitors or workspaces
+        for window in &windows[1..] {
+            Self::remove(window)?;
+        }
+
+        let current_workspace = get_vd_manager().get_current()?.id();
+        let mut state = trace_lock!(WM_STATE);
+        for (monitor_id, monitor) in state.monitors.iter_mut() {
+            for (workspace_id, workspace) in monitor.workspaces.iter_mut() {
+                if !workspace.contains(first) {
+                    continue;
+                }
+                workspace.stack_windows(&windows)?;
+                if workspace_id == &current_workspace {
+                    Self::render_workspace(monitor_id, workspace)?;
+                }
+                Self::force_retiling()?;
+                return Ok(());
+            }
+        }
+        Err("Window is not tiled".into())
+    }
+
+    /// `node_id` is the id reported by `layout_state` for the monitor, if no monitor is
+    /// specified the monitor of the foreground window is used.
+    pub fn cycle_stack(node_id: &str, monitor_id: Option<String>) -> Result<()> {
+        let monitor_id = Self::monitor_or_foreground(monitor_id)?;
+        let current_workspace = get_vd_manager().get_current()?.id();
+
+        let active = {
+            let mut state = trace_lock!(WM_STATE);
+            let monitor = state
+                .get_monitor_mut(&monitor_id)
+                .ok_or("Monitor not found")?;
+            let workspace = monitor.get_workspace_mut(&current_workspace);
+            let active = workspace.cycle_stack(node_id)?;
+            Self::render_workspace(&monitor_id, workspace)?;
+            active
+        };
+
+        WindowsApi::async_force_set_foreground(HWND(active as _));
+        Ok(())
+    }
+
+    /// Layout of the current workspace by monitor id
+    pub fn layout_state() -> Result<HashMap<String, WmLayoutState>> {
+        let current_workspace = get_vd_manager().get_current()?.id();
+        let mut state = trace_lock!(WM_STATE);
+        Ok(state
+            .monitors
+            .iter_mut()
+            .map(|(monitor_id, monitor)| {
+                let workspace = monitor.get_workspace_mut(&current_workspace);
+                (monitor_id.clone(), workspace.layout_state())
+            })
+            .collect())
+    }
+
+    /// the given monitor or the one of the foreground window
+    pub(super) fn monitor_or_foreground(monitor_id: Option<String>) -> Result<String> {
+        match monitor_id {
+            Some(id) => Ok(id),
+            None => Window::from(WindowsApi::get_foreground_window())
+                .monitor()
+                .device_id(),
+        }
+    }
+
+    /// other windows sharing the stack with the window, they are placed behind it
+    pub fn stack_siblings(window: &Window) -> Vec<isize> {
+        trace_lock!(WM_STATE).stack_siblings(window)
+    }
+}
@@ -6,7 +6,8 @@ use std::{
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
-use seelen_core::state::{NoFallbackBehavior, WManagerLayoutInfo, WmNode};
+use seelen_core::state::{NoFallbackBehavior, WManagerLayoutInfo, WindowManagerLayout, WmNode};
+use serde::Serialize;
 
 use crate::{
     error_handler::Result,
@@ -15,7 +16,10 @@ use crate::{
     windows_api::{monitor::Monitor, window::Window, MonitorEnumerator},
 };
 
-use super::{cli::Axis, node_impl::WmNodeImpl};
+use super::{
+    cli::Axis,
+    node_impl::{WmNodeImpl, WmStackInfo},
+};
 
 lazy_static! {
     pub static ref WM_STATE: Arc<Mutex<WmV2State>> = Arc::new(Mutex::new({
@@ -27,6 +31,14 @@ lazy_static! {
     }));
 }
 
+/// Layout of the current workspace of a monitor
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WmLayoutState {
+    pub layout: Option<WmNode>,
+    pub stacks: Vec<WmStackInfo>,
+}
+
 #[derive(Debug)]
 pub struct WmV2StateWorkspace {
     root: Option<WmNodeImpl>,
@@ -96,6 +108,46 @@ impl WmV2StateWorkspace {
         self.root.as_ref().map_or(vec![], |n| n.trace(window))
     }
 
+    pub fn no_fallback_behavior(&self) -> &NoFallbackBehavior {
+        &self.no_fallback_behavior
+    }
+
+    pub fn topology(&self) -> Result<WmNode> {
+        self.root.as_ref().ok_or("No layout")?.topology()
+    }
+
+    /// Replaces the layout, the current windows are tiled again into the new one
+    pub fn set_layout(&mut self, layout: WindowManagerLayout) {
+        let handles = self.root.as_ref().map_or(vec![], |n| n.handles());
+        self.layout_info = Some(layout.info);
+        self.root = Some(WmNodeImpl::new(layout.structure));
+        self.no_fallback_behavior = layout.no_fallback_behavior;
+        for handle in handles {
+            self.add_window(&Window::from(handle));
+        }
+    }
+
+    pub fn stack_windows(&mut self, windows: &[Window]) -> Result<()> {
+        self.root.as_mut().ok_or("No layout")?.stack(windows)
+    }
+
+    pub fn cycle_stack(&mut self, node_id: &str) -> Result<isize> {
+        self.root.as_mut().ok_or("No layout")?.cycle_stack(node_id)
+    }
+
+    pub fn layout_state(&self) -> WmLayoutState {
+        WmLayoutState {
+            layout: self.get_root_node().cloned(),
+            stacks: self.root.as_ref().map_or(vec![], |n| n.stacks()),
+        }
+    }
+
+    pub fn stack_siblings(&self, window: &Window) -> Vec<isize> {
+        self.root
+            .as_ref()
+            .map_or(vec![], |n| n.stack_siblings(window))
+    }
+
     pub fn get_node_at_point(&mut self, point: &Point) -> Option<&mut WmNode> {
         if let Some(root) = &mut self.root {
             return root.get_node_at_point(point).ok()?;
@@ -187,6 +239,23 @@ impl WmV2State {
         None
     }
 
+    pub fn managed_count(&self) -> usize {
+        self.monitors
+            .values()
+            .flat_map(|m| m.workspaces.values())
+            .map(|w| w.root.as_ref().map_or(0, |n| n.handles().len()))
+            .sum()
+    }
+
+    pub fn stack_siblings(&self, window: &Window) -> Vec<isize> {
+        self.monitors
+            .values()
+            .flat_map(|m| m.workspaces.values())
+            .map(|w| w.stack_siblings(window))
+            .find(|siblings| !siblings.is_empty())
+            .unwrap_or_default()
+    }
+
     pub fn get_node_at_point(&mut self, point: &Point) -> Option<&mut WmNode> {
         let monitor = Monitor::from(point);
         if let Some(m) = self.monitors.get_mut(&monitor.device_id().ok()?) {
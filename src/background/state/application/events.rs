@@ -8,26 +8,20 @@ use super::FullState;
 
 impl FullState {
     pub(super) fn emit_themes(&self) -> Result<()> {
-        get_app_handle().emit(
-            SeelenEvent::StateThemesChanged,
-            self.themes().values().collect_vec(),
-        )?;
-        Ok(())
+        get_app_handle().emit(SeelenEvent::StateThemesChanged, self.sourced_themes())?;
+        self.emit_widget_theme_stacks()
     }
 
     pub(super) fn emit_placeholders(&self) -> Result<()> {
         get_app_handle().emit(
             SeelenEvent::StatePlaceholdersChanged,
-            self.placeholders().values().collect_vec(),
+            self.sourced_placeholders(),
         )?;
         Ok(())
     }
 
     pub(super) fn emit_layouts(&self) -> Result<()> {
-        get_app_handle().emit(
-            SeelenEvent::StateLayoutsChanged,
-            self.layouts().values().collect_vec(),
-        )?;
+        get_app_handle().emit(SeelenEvent::StateLayoutsChanged, self.sourced_layouts())?;
         Ok(())
     }
 
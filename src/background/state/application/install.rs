@@ -0,0 +1,418 @@
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    io::{Cursor, Read},
+    path::{Path, PathBuf},
+};
+
+use seelen_core::state::{Placeholder, Plugin, Widget, WindowManagerLayout};
+use serde_yaml::Mapping;
+use sha2::{Digest, Sha256};
+use tauri_plugin_http::reqwest::Url;
+use zip::ZipArchive;
+
+use crate::{error_handler::Result, log_error, utils::constants::SEELEN_COMMON};
+
+use super::{
+    super::domain::{ResourceKind, Theme},
+    FullState, FULL_STATE,
+};
+
+/// max size of a downloaded resource and of the files extracted from an archive
+const MAX_RESOURCE_SIZE: u64 = 20 * 1024 * 1024;
+
+/// files allowed inside an archive, anything else is rejected
+fn is_allowed_in_archive(kind: ResourceKind, file_name: &str) -> bool {
+    match kind {
+        ResourceKind::Theme => {
+            file_name == "theme.yml"
+                || (file_name.starts_with("theme.") && file_name.ends_with(".css"))
+        }
+        ResourceKind::Widget => {
+            matches!(
+                file_name,
+                "metadata.yml" | "index.js" | "index.css" | "index.html"
+            )
+        }
+        _ => false,
+    }
+}
+
+/// Last segment of the url path, without query or fragment.
+fn url_file_name(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    path.rsplit('/').next().unwrap_or_default()
+}
+
+/// File name of the url without extension, reduced to a safe file name.
+fn resource_name_from_url(url: &str) -> Result<String> {
+    let segment = url_file_name(url);
+    let stem = Path::new(segment)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name: String = stem
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    let name = name.trim_matches('.');
+    if name.is_empty() {
+        return Err("Could not get a valid resource name from the url".into());
+    }
+    Ok(name.to_string())
+}
+
+fn verify_checksum(bytes: &[u8], expected: &str) -> Result<()> {
+    let expected = expected.trim().trim_start_matches("sha256:");
+    let actual = format!("{:x}", Sha256::digest(bytes));
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(format!("Checksum mismatch, expected {expected} but got {actual}").into());
+    }
+    Ok(())
+}
+
+fn user_path(kind: ResourceKind) -> &'static Path {
+    match kind {
+        ResourceKind::Theme => SEELEN_COMMON.user_themes_path(),
+        ResourceKind::Layout => SEELEN_COMMON.user_layouts_path(),
+        ResourceKind::Placeholder => SEELEN_COMMON.user_placeholders_path(),
+        ResourceKind::Plugin => SEELEN_COMMON.user_plugins_path(),
+        ResourceKind::Widget => SEELEN_COMMON.user_widgets_path(),
+    }
+}
+
+impl FullState {
+    fn detect_resource_kind(manifest: &Mapping) -> Result<ResourceKind> {
+        let has = |key: &str| manifest.contains_key(key);
+        let kind = if has("structure") {
+            ResourceKind::Layout
+        } else if has("plugin") && has("target") {
+            ResourceKind::Plugin
+        } else if has("left") || has("center") || has("right") {
+            ResourceKind::Placeholder
+        } else if has("styles") || has("layers") {
+            ResourceKind::Theme
+        } else if has("id") {
+            ResourceKind::Widget
+        } else {
+            return Err("Unknown resource kind".into());
+        };
+        Ok(kind)
+    }
+
+    /// fails if the manifest can't be loaded as the given kind
+    fn validate_resource(kind: ResourceKind, manifest: Mapping) -> Result<()> {
+        let value = serde_yaml::Value::Mapping(manifest);
+        match kind {
+            ResourceKind::Theme => {
+                serde_yaml::from_value::<Theme>(value)?;
+            }
+            ResourceKind::Layout => {
+                serde_yaml::from_value::<WindowManagerLayout>(value)?;
+            }
+            ResourceKind::Placeholder => {
+                serde_yaml::from_value::<Placeholder>(value)?;
+            }
+            ResourceKind::Plugin => {
+                serde_yaml::from_value::<Plugin>(value)?;
+            }
+            ResourceKind::Widget => {
+                serde_yaml::from_value::<Widget>(value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn install_resource_file(name: &str, url: &str, bytes: &[u8]) -> Result<ResourceKind> {
+        let content = String::from_utf8(bytes.to_vec())?;
+        // json is also valid yaml
+        let manifest: Mapping = serde_yaml::from_str(&content)?;
+        let kind = Self::detect_resource_kind(&manifest)?;
+        Self::validate_resource(kind, manifest.clone())?;
+
+        let file_name = url_file_name(url);
+        let is_yaml = file_name.ends_with(".yml") || file_name.ends_with(".yaml");
+        let content = if is_yaml {
+            content
+        } else {
+            serde_yaml::to_string(&manifest)?
+        };
+        let path = user_path(kind).join(format!("{name}.yml"));
+        if path.exists() {
+            return Err(format!("Resource {name} is already installed, uninstall it first").into());
+        }
+        std::fs::write(path, content)?;
+        Ok(kind)
+    }
+
+    /// Archives should contain a single folder resource (theme or widget), it can be
+    /// on the root of the archive or inside a single folder.
+    fn install_resource_archive(name: &str, bytes: &[u8]) -> Result<ResourceKind> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes))?;
+        let mut root: Option<PathBuf> = None;
+        let mut files = HashMap::new();
+        let mut extracted_size = 0;
+
+        for idx in 0..archive.len() {
+            let mut file = archive.by_index(idx)?;
+            if file.is_dir() {
+                continue;
+            }
+            // enclosed_name rejects absolute paths and paths escaping the archive
+            let path = file
+                .enclosed_name()
+                .ok_or_else(|| format!("Unsafe path in archive: {}", file.name()))?;
+            let parent = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            if parent.components().count() > 1 {
+                return Err(
+                    format!("Unexpected nested folder in archive: {}", path.display()).into(),
+                );
+            }
+            match &root {
+                Some(root) if root != &parent => {
+                    return Err("Archive should contain a single resource".into());
+                }
+                Some(_) => {}
+                None => root = Some(parent),
+            }
+
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            // the declared size can't be trusted, reading is also limited
+            extracted_size += file.size();
+            if extracted_size > MAX_RESOURCE_SIZE {
+                return Err("Archive content is too large".into());
+            }
+            let mut content = Vec::new();
+            file.by_ref()
+                .take(MAX_RESOURCE_SIZE + 1)
+                .read_to_end(&mut content)?;
+            if content.len() as u64 > file.size() {
+                return Err(format!("Invalid size of file in archive: {file_name}").into());
+            }
+            files.insert(file_name, content);
+        }
+
+        let (kind, manifest_name) = if files.contains_key("theme.yml") {
+            (ResourceKind::Theme, "theme.yml")
+        } else if files.contains_key("metadata.yml") {
+            (ResourceKind::Widget, "metadata.yml")
+        } else {
+            return Err("Archive does not contain a theme or widget manifest".into());
+        };
+
+        if let Some(file_name) = files.keys().find(|f| !is_allowed_in_archive(kind, f)) {
+            return Err(format!("Unexpected file in archive: {file_name}").into());
+        }
+        if kind == ResourceKind::Widget {
+            for required in ["index.js", "index.css", "index.html"] {
+                if !files.contains_key(required) {
+                    return Err(format!("Widget archive is missing {required}").into());
+                }
+            }
+        }
+
+        let manifest: Mapping = serde_yaml::from_slice(&files[manifest_name])?;
+        Self::validate_resource(kind, manifest)?;
+
+        let folder = user_path(kind).join(name);
+        if folder.exists() {
+            return Err(format!("Resource {name} is already installed, uninstall it first").into());
+        }
+        std::fs::create_dir_all(&folder)?;
+        for (file_name, content) in files {
+            std::fs::write(folder.join(file_name), content)?;
+        }
+        Ok(kind)
+    }
+
+    pub(super) fn reload_resources(&mut self, kind: ResourceKind) -> Result<()> {
+        match kind {
+            ResourceKind::Theme => self.load_themes(),
+            ResourceKind::Layout => self.load_layouts(),
+            ResourceKind::Placeholder => self.load_placeholders(),
+            ResourceKind::Plugin => self.load_plugins(),
+            ResourceKind::Widget => self.load_widgets(),
+        }
+    }
+
+    pub(super) fn emit_resources(&self, kind: ResourceKind) -> Result<()> {
+        match kind {
+            ResourceKind::Theme => self.emit_themes(),
+            ResourceKind::Layout => self.emit_layouts(),
+            ResourceKind::Placeholder => self.emit_placeholders(),
+            ResourceKind::Plugin => self.emit_plugins(),
+            ResourceKind::Widget => self.emit_widgets(),
+        }
+    }
+
+    async fn download_resource(url: &str) -> Result<Vec<u8>> {
+        let parsed = Url::parse(url).map_err(|_| format!("Invalid url: {url}"))?;
+        if parsed.scheme() != "https" {
+            return Err("Resources can only be downloaded over https".into());
+        }
+
+        let mut response = tauri_plugin_http::reqwest::get(parsed)
+            .await?
+            .error_for_status()?;
+        if response
+            .content_length()
+            .is_some_and(|len| len > MAX_RESOURCE_SIZE)
+        {
+            return Err("Resource is too large".into());
+        }
+        // content length is optional, so the limit is also checked while downloading
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if (bytes.len() + chunk.len()) as u64 > MAX_RESOURCE_SIZE {
+                return Err("Resource is too large".into());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    }
+
+    /// `checksum` is the hex encoded SHA-256 of the downloaded file.
+    /// Existing user resources are never overwritten, they should be uninstalled first.
+    pub async fn install_resource_from_url(
+        url: &str,
+        checksum: Option<&str>,
+    ) -> Result<ResourceKind> {
+        let bytes = Self::download_resource(url).await?;
+        if let Some(checksum) = checksum {
+            verify_checksum(&bytes, checksum)?;
+        }
+
+        let name = resource_name_from_url(url)?;
+        let kind = if bytes.starts_with(b"PK\x03\x04") {
+            Self::install_resource_archive(&name, &bytes)?
+        } else {
+            Self::install_resource_file(&name, url, &bytes)?
+        };
+
+        FULL_STATE.rcu(|state| {
+            let mut state = state.cloned();
+            log_error!(state.reload_resources(kind));
+            state
+        });
+        FULL_STATE.load().emit_resources(kind)?;
+        Ok(kind)
+    }
+
+    fn has_resource(&self, kind: ResourceKind, id: &str) -> bool {
+        match kind {
+            ResourceKind::Theme => self.themes.contains_key(id),
+            ResourceKind::Layout => self.layouts.contains_key(id),
+            ResourceKind::Placeholder => self.placeholders.contains_key(id),
+            ResourceKind::Plugin => self.plugins.keys().any(|p| p.to_string() == id),
+            ResourceKind::Widget => self.widgets.keys().any(|w| w.to_string() == id),
+        }
+    }
+
+    /// Themes, layouts and placeholders are identified by their filename, plugins and
+    /// widgets by the id on their manifest so their files need to be looked up.
+    fn find_user_resource(kind: ResourceKind, id: &str) -> Result<Option<PathBuf>> {
+        let dir = user_path(kind);
+        match kind {
+            ResourceKind::Theme | ResourceKind::Layout | ResourceKind::Placeholder => {
+                if Path::new(id).file_name() != Some(OsStr::new(id)) {
+                    return Err(format!("Invalid resource id: {id}").into());
+                }
+                let path = dir.join(id);
+                Ok(path.exists().then_some(path))
+            }
+            ResourceKind::Plugin => {
+                for entry in std::fs::read_dir(dir)?.flatten() {
+                    let path = entry.path();
+                    if path.is_file()
+                        && Self::load_plugin_from_file(&path).is_ok_and(|p| p.id.to_string() == id)
+                    {
+                        return Ok(Some(path));
+                    }
+                }
+                Ok(None)
+            }
+            ResourceKind::Widget => {
+                for entry in std::fs::read_dir(dir)?.flatten() {
+                    let path = entry.path();
+                    let widget = if path.is_dir() {
+                        Self::load_widget_from_folder(path.clone())
+                    } else {
+                        Self::load_widget_from_file(path.clone())
+                    };
+                    if widget.is_ok_and(|w| w.id.to_string() == id) {
+                        return Ok(Some(path));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// removes a theme from the enabled list and from any per widget assignment
+    fn disable_theme(&mut self, id: &str) -> Result<()> {
+        self.settings.selected_themes.retain(|theme| theme != id);
+        let widget_themes = &mut self.extended_settings.widget_themes;
+        for themes in widget_themes.values_mut() {
+            themes.retain(|theme| theme != id);
+        }
+        widget_themes.retain(|_, themes| !themes.is_empty());
+        self.write_settings()?;
+        self.emit_settings()?;
+
+        if self.theme_variant_overrides.remove(id).is_some() {
+            self.write_theme_variant_overrides()?;
+        }
+        Ok(())
+    }
+
+    /// Bundled resources (on the resource dir) are never touched.
+    pub fn uninstall_resource(&mut self, kind: ResourceKind, id: &str) -> Result<()> {
+        let path = match Self::find_user_resource(kind, id)? {
+            Some(path) => path,
+            None if self.has_resource(kind, id) => {
+                return Err("Bundled resources can't be uninstalled".into());
+            }
+            None => return Err(format!("Resource not found: {id}").into()),
+        };
+
+        // always disabled, even if a bundled theme with the same id is restored on reload,
+        // the user is removing what they enabled.
+        if kind == ResourceKind::Theme {
+            self.disable_theme(id)?;
+        }
+
+        if path.is_dir() {
+            std::fs::remove_dir_all(&path)?;
+        } else {
+            std::fs::remove_file(&path)?;
+        }
+
+        // loaders don't drop removed entries, reloading restores a bundled one with the same id.
+        // Selected layout and placeholder fall back to the default on reload.
+        self.resource_origins.remove(&(kind, id.to_string()));
+        match kind {
+            ResourceKind::Theme => {
+                self.themes.remove(id);
+                self.theme_variants.remove(id);
+            }
+            ResourceKind::Layout => {
+                self.layouts.remove(id);
+            }
+            ResourceKind::Placeholder => {
+                self.placeholders.remove(id);
+            }
+            ResourceKind::Plugin => self.plugins.retain(|p, _| p.to_string() != id),
+            ResourceKind::Widget => self.widgets.retain(|w, _| w.to_string() != id),
+        }
+        self.reload_resources(kind)?;
+
+        if matches!(kind, ResourceKind::Layout | ResourceKind::Placeholder) {
+            self.write_settings()?;
+            self.emit_settings()?;
+        }
+        self.emit_resources(kind)
+    }
+}
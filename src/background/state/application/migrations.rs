@@ -0,0 +1,92 @@
+use itertools::Itertools;
+use schemars::gen::SchemaGenerator;
+use serde_json::{Map, Value};
+
+use crate::{
+    error_handler::Result,
+    state::domain::{ExtendedSettings, Settings, SettingsSchema},
+};
+
+/// Version of the settings file layout, stored on the file as `schemaVersion`.
+/// Increase it when adding a migration.
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
+const SCHEMA_VERSION_KEY: &str = "schemaVersion";
+
+/// Generated from the `Settings` type so it can't drift from what is actually read, the
+/// extended sections share the same object so the file has to match both schemas.
+/// `schemaVersion` is added by hand as it is only present on the file.
+pub fn settings_schema() -> SettingsSchema {
+    let mut generator = SchemaGenerator::default();
+    let version = generator.subschema_for::<u32>();
+    let extended = generator.subschema_for::<ExtendedSettings>();
+    let mut schema = generator.into_root_schema_for::<Settings>();
+    schema
+        .schema
+        .object()
+        .properties
+        .insert(SCHEMA_VERSION_KEY.to_string(), version);
+    schema.schema.subschemas().all_of = Some(vec![extended]);
+    SettingsSchema {
+        schema_version: SETTINGS_SCHEMA_VERSION,
+        schema,
+    }
+}
+
+type Migration = fn(&mut Map<String, Value>) -> Result<()>;
+
+/// The migration at index `n` upgrades the settings from version `n` to `n + 1`.
+const MIGRATIONS: [(&str, Migration); SETTINGS_SCHEMA_VERSION as usize] =
+    [("v1: deduplicate selected themes", dedup_selected_themes)];
+
+/// Old settings could contain the same theme more than once, as precedence is
+/// now given by the position only the first occurrence is kept.
+fn dedup_selected_themes(settings: &mut Map<String, Value>) -> Result<()> {
+    if let Some(Value::Array(themes)) = settings.get_mut("selectedThemes") {
+        *themes = themes.iter().unique().cloned().collect_vec();
+    }
+    Ok(())
+}
+
+/// files without version were written before the migrations were introduced
+pub fn settings_schema_version(settings: &Value) -> u32 {
+    settings
+        .get(SCHEMA_VERSION_KEY)
+        .and_then(Value::as_u64)
+        .map_or(0, |version| version as u32)
+}
+
+pub fn set_settings_schema_version(settings: &mut Value) {
+    if let Some(settings) = settings.as_object_mut() {
+        settings.insert(
+            SCHEMA_VERSION_KEY.to_string(),
+            SETTINGS_SCHEMA_VERSION.into(),
+        );
+    }
+}
+
+/// Applies in order the migrations pending for the stored version, returns the
+/// name of the applied ones. Stops on the first failure.
+pub fn migrate_settings(settings: &mut Value) -> Result<Vec<String>> {
+    let version = settings_schema_version(settings);
+    if version > SETTINGS_SCHEMA_VERSION {
+        log::warn!(
+            "Settings were saved by a newer version (schema v{version}), loading them as they are"
+        );
+        return Ok(Vec::new());
+    }
+
+    let object = settings
+        .as_object_mut()
+        .ok_or("Invalid settings file, expected an object")?;
+    let mut applied = Vec::new();
+    for (name, migration) in MIGRATIONS.iter().skip(version as usize) {
+        if let Err(err) = migration(object) {
+            return Err(format!("Settings migration \"{name}\" failed: {err}").into());
+        }
+        log::info!("Settings migration applied: {name}");
+        applied.push(name.to_string());
+    }
+    set_settings_schema_version(settings);
+    Ok(applied)
+}
@@ -1,12 +1,23 @@
 mod apps_config;
 mod events;
 mod icons;
+mod install;
+mod migrations;
+mod monitor_configs;
+mod origins;
 mod plugins;
 mod profiles;
 mod settings;
+mod theme_variants;
 mod weg_items;
+mod widget_configs;
+mod widget_themes;
 mod widgets;
 
+pub use migrations::settings_schema;
+pub use settings::{settings_from_partial_value, settings_from_value, settings_to_value};
+pub use widget_themes::is_widget_label;
+
 use arc_swap::ArcSwap;
 use getset::Getters;
 use itertools::Itertools;
@@ -35,7 +46,10 @@ use crate::{
     utils::constants::SEELEN_COMMON, windows_api::WindowsApi,
 };
 
-use super::domain::{AppConfig, Placeholder, Settings, Theme};
+use super::domain::{
+    AppConfig, ExtendedSettings, Placeholder, ResourceKind, ResourceOrigin, Settings, Theme,
+    ThemeVariant, ThemeVariants,
+};
 
 lazy_static! {
     pub static ref FULL_STATE: Arc<ArcSwap<FullState>> = Arc::new(ArcSwap::from_pointee({
@@ -55,8 +69,15 @@ pub struct FullState {
     // ======== data ========
     pub profiles: Vec<Profile>,
     pub settings: Settings,
+    pub extended_settings: ExtendedSettings,
     pub settings_by_app: VecDeque<AppConfig>,
     pub themes: HashMap<String, Theme>,
+    pub theme_variants: HashMap<String, ThemeVariants>,
+    pub theme_variant_overrides: HashMap<String, ThemeVariant>,
+    /// where each loaded theme, layout, placeholder, plugin and widget comes from
+    pub resource_origins: HashMap<(ResourceKind, String), ResourceOrigin>,
+    /// settings migrations applied on the last load
+    pub settings_migrations: Vec<String>,
     pub icon_packs: Arc<Mutex<HashMap<String, IconPack>>>,
     pub placeholders: HashMap<String, Placeholder>,
     pub layouts: HashMap<String, WindowManagerLayout>,
@@ -76,8 +97,13 @@ impl FullState {
             // ======== data ========
             profiles: Vec::new(),
             settings: Settings::default(),
+            extended_settings: ExtendedSettings::default(),
             settings_by_app: VecDeque::new(),
             themes: HashMap::new(),
+            theme_variants: HashMap::new(),
+            theme_variant_overrides: HashMap::new(),
+            resource_origins: HashMap::new(),
+            settings_migrations: Vec::new(),
             icon_packs: Arc::new(Mutex::new(HashMap::new())),
             placeholders: HashMap::new(),
             layouts: HashMap::new(),
@@ -142,6 +168,7 @@ impl FullState {
             log::info!("Seelen Settings changed");
             self.read_settings()?;
             self.emit_settings()?;
+            self.emit_widget_theme_stacks()?;
         }
 
         if event.paths.iter().any(|p| {
@@ -198,6 +225,12 @@ impl FullState {
             self.emit_widgets()?;
         }
 
+        for path in &event.paths {
+            if path.starts_with(SEELEN_COMMON.widget_configs_path()) {
+                self.on_widget_config_changed(path)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -237,6 +270,7 @@ impl FullState {
             SEELEN_COMMON.user_layouts_path(),
             SEELEN_COMMON.user_plugins_path(),
             SEELEN_COMMON.user_widgets_path(),
+            SEELEN_COMMON.widget_configs_path(),
             // bundled data
             SEELEN_COMMON.bundled_themes_path(),
             SEELEN_COMMON.bundled_placeholders_path(),
@@ -253,10 +287,12 @@ impl FullState {
         Ok(())
     }
 
-    pub fn get_settings_from_path(path: &Path) -> Result<Settings> {
+    pub fn get_settings_from_path(path: &Path) -> Result<(Settings, ExtendedSettings)> {
         match path.extension() {
             Some(ext) if ext == "json" => {
-                Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+                let mut value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+                migrations::migrate_settings(&mut value)?;
+                settings_from_value(value)
             }
             _ => Err("Invalid settings file extension".into()),
         }
@@ -318,10 +354,17 @@ impl FullState {
     }
 
     fn load_themes(&mut self) -> Result<()> {
-        let entries = std::fs::read_dir(SEELEN_COMMON.bundled_themes_path())?
-            .chain(std::fs::read_dir(SEELEN_COMMON.user_themes_path())?);
-        for entry in entries.flatten() {
+        let entries = Self::resource_entries(
+            SEELEN_COMMON.bundled_themes_path(),
+            SEELEN_COMMON.user_themes_path(),
+        )?;
+        for entry in entries {
             let path = entry.path();
+            let manifest = if path.is_dir() {
+                path.join("theme.yml")
+            } else {
+                path.clone()
+            };
             let theme = if path.is_dir() {
                 Self::load_theme_from_dir(path)
             } else {
@@ -330,14 +373,57 @@ impl FullState {
             match theme {
                 Ok(mut theme) => {
                     theme.info.filename = entry.file_name().to_string_lossy().to_string();
+                    let mut variants = Self::load_theme_variants(&manifest).unwrap_or_default();
+                    variants.base = theme.styles.clone();
+                    if !self.resolve_resource(
+                        ResourceKind::Theme,
+                        theme.info.filename.clone(),
+                        &entry.path(),
+                        SEELEN_COMMON.bundled_themes_path(),
+                    ) {
+                        continue;
+                    }
+                    self.theme_variants
+                        .insert(theme.info.filename.clone(), variants);
                     self.themes.insert(theme.info.filename.clone(), theme);
                 }
                 Err(err) => log::error!("Failed to load theme ({:?}): {:?}", entry.path(), err),
             }
         }
+        self.apply_theme_variants();
         Ok(())
     }
 
+    /// Enabled themes by precedence (later ones override earlier ones), followed by
+    /// the disabled ones sorted by filename.
+    pub fn ordered_themes(&self) -> Vec<&Theme> {
+        let selected = &self.settings.selected_themes;
+        let disabled = self
+            .themes
+            .iter()
+            .filter(|(id, _)| !selected.contains(id))
+            .sorted_by_key(|(id, _)| *id)
+            .map(|(_, theme)| theme);
+        selected
+            .iter()
+            .filter_map(|id| self.themes.get(id))
+            .chain(disabled)
+            .collect_vec()
+    }
+
+    /// `order` should contain the same themes that are currently enabled
+    pub fn reorder_themes(&mut self, order: Vec<String>) -> Result<()> {
+        let current = self.settings.selected_themes.iter().sorted().collect_vec();
+        let requested = order.iter().sorted().collect_vec();
+        if current != requested {
+            return Err("The new order must contain exactly the enabled themes".into());
+        }
+        self.settings.selected_themes = order;
+        self.write_settings()?;
+        self.emit_settings()?;
+        self.emit_themes()
+    }
+
     fn load_placeholder_from_file(path: PathBuf) -> Result<Placeholder> {
         match path.extension() {
             Some(ext) if ext == "yml" || ext == "yaml" => {
@@ -348,20 +434,30 @@ impl FullState {
     }
 
     fn load_placeholders(&mut self) -> Result<()> {
-        let entries = std::fs::read_dir(SEELEN_COMMON.bundled_placeholders_path())?
-            .chain(std::fs::read_dir(SEELEN_COMMON.user_placeholders_path())?);
-        for entry in entries.flatten() {
+        let entries = Self::resource_entries(
+            SEELEN_COMMON.bundled_placeholders_path(),
+            SEELEN_COMMON.user_placeholders_path(),
+        )?;
+        for entry in entries {
             let path = entry.path();
             if path.is_dir() {
                 continue;
             }
 
-            let placeholder = Self::load_placeholder_from_file(path);
+            let placeholder = Self::load_placeholder_from_file(path.clone());
 
             match placeholder {
                 Ok(mut placeholder) => {
                     placeholder.sanitize();
                     placeholder.info.filename = entry.file_name().to_string_lossy().to_string();
+                    if !self.resolve_resource(
+                        ResourceKind::Placeholder,
+                        placeholder.info.filename.clone(),
+                        &path,
+                        SEELEN_COMMON.bundled_placeholders_path(),
+                    ) {
+                        continue;
+                    }
                     self.placeholders
                         .insert(placeholder.info.filename.clone(), placeholder);
                 }
@@ -396,18 +492,26 @@ impl FullState {
     fn load_layouts(&mut self) -> Result<()> {
         let user_path = SEELEN_COMMON.user_layouts_path();
         let resources_path = SEELEN_COMMON.bundled_layouts_path();
-        let entries = std::fs::read_dir(resources_path)?.chain(std::fs::read_dir(user_path)?);
-        for entry in entries.flatten() {
+        let entries = Self::resource_entries(resources_path, user_path)?;
+        for entry in entries {
             let path = entry.path();
             if path.is_dir() {
                 continue;
             }
 
-            let layout = Self::load_layout_from_file(path);
+            let layout = Self::load_layout_from_file(path.clone());
 
             match layout {
                 Ok(mut layout) => {
                     layout.info.filename = entry.file_name().to_string_lossy().to_string();
+                    if !self.resolve_resource(
+                        ResourceKind::Layout,
+                        layout.info.filename.clone(),
+                        &path,
+                        resources_path,
+                    ) {
+                        continue;
+                    }
                     self.layouts.insert(layout.info.filename.clone(), layout);
                 }
                 Err(err) => {
@@ -482,6 +586,7 @@ impl FullState {
     fn load_all(&mut self) -> Result<()> {
         self.read_settings()?;
         self.read_weg_items()?;
+        self.load_theme_variant_overrides()?;
         self.load_themes()?;
         self.load_icons_packs()?;
         self.load_placeholders()?;
@@ -0,0 +1,79 @@
+use itertools::Itertools;
+use seelen_core::state::MonitorConfiguration;
+
+use crate::{error_handler::Result, windows_api::MonitorEnumerator};
+
+use super::FullState;
+
+impl FullState {
+    /// Fails if any connected monitor can't be identified, so its config is never
+    /// taken as orphaned by mistake.
+    pub(super) fn connected_monitor_ids() -> Result<Vec<String>> {
+        let mut ids = Vec::new();
+        for monitor in MonitorEnumerator::get_all_v2()? {
+            ids.push(monitor.display_device()?.id);
+        }
+        if ids.is_empty() {
+            return Err("No connected monitors found".into());
+        }
+        Ok(ids)
+    }
+
+    /// Returns the ids of the configs that don't belong to any connected monitor,
+    /// they are only removed if `confirm` is set.
+    pub fn prune_monitor_configs(&mut self, confirm: bool) -> Result<Vec<String>> {
+        let connected = Self::connected_monitor_ids()?;
+        let orphaned = self
+            .settings
+            .monitors_v2
+            .keys()
+            .filter(|id| !connected.contains(id))
+            .cloned()
+            .sorted()
+            .collect_vec();
+
+        if confirm && !orphaned.is_empty() {
+            for id in &orphaned {
+                log::info!("Pruning orphaned monitor config: {id}");
+                self.settings.monitors_v2.remove(id);
+            }
+            self.write_settings()?;
+            self.emit_settings()?;
+        }
+        Ok(orphaned)
+    }
+
+    /// Copies the whole config of a monitor into another one, returns the previous
+    /// config of the target so it can be restored.
+    pub fn copy_monitor_config(
+        &mut self,
+        from: &str,
+        to: &str,
+        force: bool,
+    ) -> Result<Option<MonitorConfiguration>> {
+        if from == to {
+            return Err("Source and target monitors are the same".into());
+        }
+        let config = self
+            .settings
+            .monitors_v2
+            .get(from)
+            .cloned()
+            .ok_or_else(|| format!("No config found for monitor: {from}"))?;
+
+        let target_exists = self.settings.monitors_v2.contains_key(to);
+        if !target_exists && !Self::connected_monitor_ids()?.iter().any(|id| id == to) {
+            return Err(format!("Unknown monitor: {to}").into());
+        }
+        if target_exists && !force {
+            return Err(
+                format!("Monitor {to} already has a config, use force to overwrite").into(),
+            );
+        }
+
+        let previous = self.settings.monitors_v2.insert(to.to_string(), config);
+        self.write_settings()?;
+        self.emit_settings()?;
+        Ok(previous)
+    }
+}
@@ -0,0 +1,155 @@
+use std::{fs::DirEntry, path::Path};
+
+use itertools::Itertools;
+use seelen_core::state::{Plugin, Widget, WindowManagerLayout};
+
+use crate::error_handler::Result;
+
+use super::{
+    super::domain::{
+        Placeholder, ResourceKind, ResourceOrigin, ResourceSource, SourcedResource, Theme,
+    },
+    FullState,
+};
+
+impl FullState {
+    /// Bundled entries followed by the user ones, each group sorted by path so the
+    /// resolution of duplicated ids doesn't depend on the file system order.
+    pub(super) fn resource_entries(bundled: &Path, user: &Path) -> Result<Vec<DirEntry>> {
+        let mut entries = Vec::new();
+        for dir in [bundled, user] {
+            let group = std::fs::read_dir(dir)?
+                .flatten()
+                .sorted_by_key(|entry| entry.path());
+            entries.extend(group);
+        }
+        Ok(entries)
+    }
+
+    fn is_pinned_to_bundled(&self, kind: ResourceKind, id: &str) -> bool {
+        self.extended_settings
+            .resource_pins
+            .get(&kind)
+            .is_some_and(|pins| pins.contains(id))
+    }
+
+    /// Loaders read the bundled folder before the user one, so a user resource found
+    /// after a bundled one with the same id is shadowing it. By default the user one
+    /// wins, unless the id is pinned to the bundled version.
+    ///
+    /// Returns false if the resource should not be loaded.
+    pub(super) fn resolve_resource(
+        &mut self,
+        kind: ResourceKind,
+        id: String,
+        path: &Path,
+        bundled_root: &Path,
+    ) -> bool {
+        let source = if path.starts_with(bundled_root) {
+            ResourceSource::Bundled
+        } else {
+            ResourceSource::User
+        };
+        let pinned = self.is_pinned_to_bundled(kind, &id);
+        let bundled_path = match self.resource_origins.get(&(kind, id.clone())) {
+            Some(previous)
+                if source == ResourceSource::User && previous.source == ResourceSource::Bundled =>
+            {
+                Some(previous.path.clone())
+            }
+            _ => None,
+        };
+
+        if let Some(bundled_path) = &bundled_path {
+            if pinned {
+                log::info!(
+                    "{kind:?} {id} is pinned to bundled {bundled_path:?}, ignoring {path:?}"
+                );
+                return false;
+            }
+            log::info!("{kind:?} {id} from {path:?} overrides bundled {bundled_path:?}");
+        }
+
+        self.resource_origins.insert(
+            (kind, id),
+            ResourceOrigin {
+                source,
+                path: path.to_path_buf(),
+                overrides_bundled: bundled_path.is_some(),
+                bundled_path,
+                pinned: source == ResourceSource::Bundled && pinned,
+            },
+        );
+        true
+    }
+
+    /// Forces the bundled version of a resource even if a user one with the same id exists.
+    pub fn set_resource_pinned(
+        &mut self,
+        kind: ResourceKind,
+        id: String,
+        pinned: bool,
+    ) -> Result<()> {
+        let resource_pins = &mut self.extended_settings.resource_pins;
+        let pins = resource_pins.entry(kind).or_default();
+        let changed = if pinned {
+            pins.insert(id)
+        } else {
+            pins.remove(&id)
+        };
+        if !changed {
+            return Ok(());
+        }
+        resource_pins.retain(|_, pins| !pins.is_empty());
+        self.write_settings()?;
+        self.reload_resources(kind)?;
+        self.emit_resources(kind)
+    }
+
+    pub fn sourced<T: Clone>(
+        &self,
+        kind: ResourceKind,
+        id: &str,
+        resource: &T,
+    ) -> SourcedResource<T> {
+        SourcedResource {
+            resource: resource.clone(),
+            origin: self.resource_origins.get(&(kind, id.to_string())).cloned(),
+        }
+    }
+
+    pub fn sourced_themes(&self) -> Vec<SourcedResource<Theme>> {
+        self.ordered_themes()
+            .into_iter()
+            .map(|theme| self.sourced(ResourceKind::Theme, &theme.info.filename, theme))
+            .collect_vec()
+    }
+
+    pub fn sourced_placeholders(&self) -> Vec<SourcedResource<Placeholder>> {
+        self.placeholders
+            .iter()
+            .map(|(id, placeholder)| self.sourced(ResourceKind::Placeholder, id, placeholder))
+            .collect_vec()
+    }
+
+    pub fn sourced_layouts(&self) -> Vec<SourcedResource<WindowManagerLayout>> {
+        self.layouts
+            .iter()
+            .map(|(id, layout)| self.sourced(ResourceKind::Layout, id, layout))
+            .collect_vec()
+    }
+
+    pub fn sourced_plugins(&self) -> Vec<SourcedResource<Plugin>> {
+        self.plugins
+            .iter()
+            .map(|(id, plugin)| self.sourced(ResourceKind::Plugin, &id.to_string(), plugin))
+            .collect_vec()
+    }
+
+    pub fn sourced_widgets(&self) -> Vec<SourcedResource<Widget>> {
+        self.widgets
+            .iter()
+            .map(|(id, widget)| self.sourced(ResourceKind::Widget, &id.to_string(), widget))
+            .collect_vec()
+    }
+}
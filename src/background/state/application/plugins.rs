@@ -1,19 +1,27 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use seelen_core::{handlers::SeelenEvent, state::Plugin};
 use tauri::Emitter;
 
 use crate::{error_handler::Result, seelen::get_app_handle, utils::constants::SEELEN_COMMON};
 
-use super::FullState;
+use super::{super::domain::ResourceKind, FullState};
 
 impl FullState {
     pub(super) fn emit_plugins(&self) -> Result<()> {
-        get_app_handle().emit(SeelenEvent::StatePluginsChanged, &self.plugins)?;
+        let plugins: HashMap<_, _> = self
+            .plugins
+            .iter()
+            .map(|(id, plugin)| {
+                let sourced = self.sourced(ResourceKind::Plugin, &id.to_string(), plugin);
+                (id, sourced)
+            })
+            .collect();
+        get_app_handle().emit(SeelenEvent::StatePluginsChanged, plugins)?;
         Ok(())
     }
 
-    fn load_plugin_from_file(path: &Path) -> Result<Plugin> {
+    pub(super) fn load_plugin_from_file(path: &Path) -> Result<Plugin> {
         Ok(serde_yaml::from_str(&std::fs::read_to_string(path)?)?)
     }
 
@@ -21,8 +29,8 @@ impl FullState {
         let user_path = SEELEN_COMMON.user_plugins_path();
         let bundled_path = SEELEN_COMMON.bundled_plugins_path();
 
-        let entries = std::fs::read_dir(bundled_path)?.chain(std::fs::read_dir(user_path)?);
-        for entry in entries.flatten() {
+        let entries = Self::resource_entries(bundled_path, user_path)?;
+        for entry in entries {
             let path = entry.path();
             if path.is_dir() {
                 continue;
@@ -30,6 +38,14 @@ impl FullState {
             match Self::load_plugin_from_file(&path) {
                 Ok(mut plugin) => {
                     plugin.bundled = path.starts_with(bundled_path);
+                    if !self.resolve_resource(
+                        ResourceKind::Plugin,
+                        plugin.id.to_string(),
+                        &path,
+                        bundled_path,
+                    ) {
+                        continue;
+                    }
                     self.plugins.insert(plugin.id.clone(), plugin);
                 }
                 Err(e) => {
@@ -1,29 +1,130 @@
 use std::{fs::OpenOptions, io::Write};
 
 use seelen_core::{handlers::SeelenEvent, state::VirtualDesktopStrategy};
+use serde_json::Value;
 use tauri::Emitter;
 
 use crate::{
     error_handler::Result,
     seelen::{get_app_handle, SEELEN},
+    state::domain::{ExtendedSettings, Settings},
     trace_lock,
     utils::{constants::SEELEN_COMMON, is_virtual_desktop_supported},
 };
 
-use super::FullState;
+use super::{
+    migrations::{
+        migrate_settings, set_settings_schema_version, settings_schema_version,
+        SETTINGS_SCHEMA_VERSION,
+    },
+    FullState,
+};
+
+/// Writes `extended` over `base`, objects present on both are merged key by key
+fn merge_settings_value(base: &mut Value, extended: Value) {
+    match (base, extended) {
+        (Value::Object(base), Value::Object(extended)) => {
+            for (key, value) in extended {
+                match base.get_mut(&key) {
+                    Some(current) => merge_settings_value(current, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, extended) => *base = extended,
+    }
+}
+
+/// Content of `settings.json`, the seelen-core settings with the extended sections merged in
+pub fn settings_to_value(settings: &Settings, extended: &ExtendedSettings) -> Result<Value> {
+    let mut value = serde_json::to_value(settings)?;
+    merge_settings_value(&mut value, serde_json::to_value(extended)?);
+    set_settings_schema_version(&mut value);
+    Ok(value)
+}
+
+/// Each model takes its own keys from the same object and ignores the rest
+pub fn settings_from_value(value: Value) -> Result<(Settings, ExtendedSettings)> {
+    let extended = serde_json::from_value(value.clone())?;
+    Ok((serde_json::from_value(value)?, extended))
+}
+
+/// Inserts on `value` the keys of `sections` it lacks, taking them from `current`. Only the
+/// struct fields are walked (maps are empty by default), so entries removed from a map on
+/// `value` are not brought back.
+fn fill_missing_sections(value: &mut Value, current: &Value, sections: &Value) {
+    let (Value::Object(value), Value::Object(current), Value::Object(sections)) =
+        (value, current, sections)
+    else {
+        return;
+    };
+    for (key, section) in sections {
+        let Some(current) = current.get(key) else {
+            continue;
+        };
+        match value.get_mut(key) {
+            Some(value) => fill_missing_sections(value, current, section),
+            None => {
+                value.insert(key.clone(), current.clone());
+            }
+        }
+    }
+}
+
+/// Like `settings_from_value` but the extended sections missing on `value` keep their current
+/// value, so a client only aware of the seelen-core model doesn't reset them on save.
+pub fn settings_from_partial_value(
+    current: &ExtendedSettings,
+    mut value: Value,
+) -> Result<(Settings, ExtendedSettings)> {
+    fill_missing_sections(
+        &mut value,
+        &serde_json::to_value(current)?,
+        &serde_json::to_value(ExtendedSettings::default())?,
+    );
+    settings_from_value(value)
+}
 
 impl FullState {
+    pub fn settings_value(&self) -> Result<Value> {
+        settings_to_value(&self.settings, &self.extended_settings)
+    }
+
     pub(super) fn emit_settings(&self) -> Result<()> {
-        get_app_handle().emit(SeelenEvent::StateSettingsChanged, self.settings())?;
+        get_app_handle().emit(SeelenEvent::StateSettingsChanged, self.settings_value()?)?;
         trace_lock!(SEELEN).on_settings_change()?;
         Ok(())
     }
 
+    /// Pending migrations are applied after backing up the file, if any of them fails
+    /// the error is propagated instead of falling back to the default settings.
+    fn read_migrated_settings(&mut self) -> Result<()> {
+        let path = SEELEN_COMMON.settings_path();
+        let mut value: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+
+        let version = settings_schema_version(&value);
+        if version < SETTINGS_SCHEMA_VERSION {
+            let backup = path.with_file_name(format!("settings.v{version}.backup.json"));
+            std::fs::copy(path, &backup)?;
+            log::info!("Settings schema v{version} backed up to {backup:?} before migrating");
+        }
+
+        let applied = migrate_settings(&mut value)?;
+        (self.settings, self.extended_settings) = settings_from_value(value)?;
+        self.settings.sanitize();
+        if !applied.is_empty() {
+            self.write_settings()?;
+        }
+        self.settings_migrations = applied;
+        Ok(())
+    }
+
     pub(super) fn read_settings(&mut self) -> Result<()> {
         let path_exists = SEELEN_COMMON.settings_path().exists();
         if path_exists {
-            self.settings = Self::get_settings_from_path(SEELEN_COMMON.settings_path())?;
-            self.settings.sanitize();
+            self.read_migrated_settings()?;
         }
         if !is_virtual_desktop_supported() {
             self.settings.virtual_desktop_strategy = VirtualDesktopStrategy::Seelen;
@@ -41,8 +142,44 @@ impl FullState {
             .create(true)
             .truncate(true)
             .open(SEELEN_COMMON.settings_path())?;
-        file.write_all(serde_json::to_string_pretty(&self.settings)?.as_bytes())?;
+        let value = self.settings_value()?;
+        file.write_all(serde_json::to_string_pretty(&value)?.as_bytes())?;
         file.flush()?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn merge_settings_value_merges_nested_sections() {
+        let mut base = json!({ "wall": { "enabled": true }, "language": "en" });
+        merge_settings_value(&mut base, json!({ "wall": { "dim": 1 }, "weather": {} }));
+        assert_eq!(
+            base,
+            json!({ "wall": { "enabled": true, "dim": 1 }, "language": "en", "weather": {} })
+        );
+    }
+
+    #[test]
+    fn fill_missing_sections_keeps_current_sections_only() {
+        let sections = json!({ "wall": { "dim": {} }, "backdrops": {} });
+        let current = json!({ "wall": { "dim": { "enabled": true } }, "backdrops": { "a": 1 } });
+
+        let mut value = json!({ "wall": { "enabled": true } });
+        fill_missing_sections(&mut value, &current, &sections);
+        assert_eq!(
+            value,
+            json!({ "wall": { "enabled": true, "dim": { "enabled": true } }, "backdrops": { "a": 1 } })
+        );
+
+        // removed map entries are not restored
+        let mut value = json!({ "backdrops": {} });
+        fill_missing_sections(&mut value, &current, &sections);
+        assert_eq!(value["backdrops"], json!({}));
+    }
+}
@@ -0,0 +1,141 @@
+use std::{collections::HashMap, path::Path};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::system_state::UIColors;
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::system_settings::application::{is_dark_mode, SYSTEM_SETTINGS},
+    trace_lock,
+    utils::constants::SEELEN_COMMON,
+    windows_api::WindowsApi,
+};
+
+use super::{
+    super::domain::{ThemeVariant, ThemeVariants},
+    FullState, FULL_STATE,
+};
+
+lazy_static! {
+    /// variant that follows the system mode, lazily initialized on first use
+    static ref SYSTEM_THEME_VARIANT: Mutex<Option<ThemeVariant>> = Mutex::new(None);
+}
+
+fn variant_from_colors(colors: &UIColors) -> ThemeVariant {
+    if WindowsApi::is_high_contrast_enabled().unwrap_or(false) {
+        ThemeVariant::HighContrast
+    } else if is_dark_mode(colors) {
+        ThemeVariant::Dark
+    } else {
+        ThemeVariant::Light
+    }
+}
+
+fn system_theme_variant() -> ThemeVariant {
+    let mut cached = trace_lock!(SYSTEM_THEME_VARIANT);
+    if let Some(variant) = *cached {
+        return variant;
+    }
+    let variant = match trace_lock!(SYSTEM_SETTINGS).get_colors() {
+        Ok(colors) => variant_from_colors(&colors),
+        Err(_) => ThemeVariant::Dark,
+    };
+    *cached = Some(variant);
+    variant
+}
+
+impl FullState {
+    pub(super) fn load_theme_variants(path: &Path) -> Result<ThemeVariants> {
+        Ok(serde_yaml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub(super) fn load_theme_variant_overrides(&mut self) -> Result<()> {
+        let path = SEELEN_COMMON.theme_variants_path();
+        if path.exists() {
+            self.theme_variant_overrides = serde_yaml::from_str(&std::fs::read_to_string(path)?)?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn write_theme_variant_overrides(&self) -> Result<()> {
+        std::fs::write(
+            SEELEN_COMMON.theme_variants_path(),
+            serde_yaml::to_string(&self.theme_variant_overrides)?,
+        )?;
+        Ok(())
+    }
+
+    /// manual override has priority, then the system mode, falling back to the base theme
+    fn resolve_theme_variant(&self, theme_id: &str, system: ThemeVariant) -> Option<ThemeVariant> {
+        let variants = &self.theme_variants.get(theme_id)?.variants;
+        self.theme_variant_overrides
+            .get(theme_id)
+            .copied()
+            .filter(|variant| variants.contains_key(variant))
+            .or_else(|| variants.contains_key(&system).then_some(system))
+    }
+
+    pub(super) fn apply_theme_variants(&mut self) {
+        let system = system_theme_variant();
+        let resolved: HashMap<String, Option<ThemeVariant>> = self
+            .theme_variants
+            .keys()
+            .map(|id| (id.clone(), self.resolve_theme_variant(id, system)))
+            .collect();
+
+        for (id, variant) in resolved {
+            let (Some(theme), Some(variants)) =
+                (self.themes.get_mut(&id), self.theme_variants.get(&id))
+            else {
+                continue;
+            };
+            theme.styles = variants.base.clone();
+            let Some(styles) = variant.and_then(|v| variants.variants.get(&v)) else {
+                continue;
+            };
+            for (widget, css) in styles {
+                let style = theme.styles.entry(widget.clone()).or_default();
+                style.push('\n');
+                style.push_str(css);
+            }
+        }
+    }
+
+    /// `None` removes the manual override so the theme follows the system mode again
+    pub fn set_theme_variant(
+        &mut self,
+        theme_id: String,
+        variant: Option<ThemeVariant>,
+    ) -> Result<()> {
+        let variants = self
+            .theme_variants
+            .get(&theme_id)
+            .ok_or("Theme not found")?;
+        match variant {
+            Some(variant) if !variants.variants.contains_key(&variant) => {
+                return Err(format!("Theme {theme_id} has no {variant:?} variant").into());
+            }
+            Some(variant) => self.theme_variant_overrides.insert(theme_id, variant),
+            None => self.theme_variant_overrides.remove(&theme_id),
+        };
+        self.write_theme_variant_overrides()?;
+        self.apply_theme_variants();
+        self.emit_themes()
+    }
+
+    /// called on system colors changes, also triggered by the high contrast toggle
+    pub fn on_system_theme_change(colors: &UIColors) {
+        let variant = variant_from_colors(colors);
+        if trace_lock!(SYSTEM_THEME_VARIANT).replace(variant) == Some(variant) {
+            return;
+        }
+        FULL_STATE.rcu(|state| {
+            let mut state = state.cloned();
+            state.apply_theme_variants();
+            state
+        });
+        log_error!(FULL_STATE.load().emit_themes());
+    }
+}
@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use seelen_core::state::WidgetId;
+use serde_json::Value;
+use tauri::Emitter;
+
+use crate::{error_handler::Result, seelen::get_app_handle, utils::constants::SEELEN_COMMON};
+
+use super::{super::domain::ResourceKind, widget_themes::widget_labels, FullState};
+
+/// file declared by folder widgets to validate their config
+const CONFIG_SCHEMA_FILE: &str = "config.schema.json";
+
+/// widget ids can contain characters not allowed on file names, so they are encoded
+fn widget_config_path(widget: &WidgetId) -> PathBuf {
+    let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    SEELEN_COMMON
+        .widget_configs_path()
+        .join(format!("{}.json", engine.encode(widget.to_string())))
+}
+
+fn widget_id_from_config_path(path: &Path) -> Option<WidgetId> {
+    if path.extension()? != "json" {
+        return None;
+    }
+    let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    let stem = path.file_stem()?.to_string_lossy();
+    let decoded = engine.decode(stem.as_bytes()).ok()?;
+    Some(WidgetId(String::from_utf8(decoded).ok()?))
+}
+
+fn emit_widget_config(widget: &WidgetId, config: &Value) -> Result<()> {
+    for label in widget_labels(widget) {
+        get_app_handle().emit_to(&label, "widget-config-changed", config)?;
+    }
+    Ok(())
+}
+
+impl FullState {
+    fn widget_config_schema(&self, widget: &WidgetId) -> Result<Option<Value>> {
+        let Some(origin) = self
+            .resource_origins
+            .get(&(ResourceKind::Widget, widget.to_string()))
+        else {
+            return Ok(None);
+        };
+        let path = origin.path.join(CONFIG_SCHEMA_FILE);
+        if !origin.path.is_dir() || !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+    }
+
+    /// `null` when the widget has not saved a config yet
+    pub fn widget_config(&self, widget: &WidgetId) -> Result<Value> {
+        let path = widget_config_path(widget);
+        if !path.exists() {
+            return Ok(Value::Null);
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// the change is emitted to the widget by the files watcher, see `on_widget_config_changed`
+    pub fn set_widget_config(&self, widget: &WidgetId, config: Value) -> Result<()> {
+        if let Some(schema) = self.widget_config_schema(widget)? {
+            let validator = jsonschema::JSONSchema::compile(&schema)
+                .map_err(|e| format!("Invalid config schema of {widget}: {e}"))?;
+            if let Err(errors) = validator.validate(&config) {
+                let errors: Vec<String> = errors
+                    .map(|e| format!("{}: {e}", e.instance_path))
+                    .collect();
+                return Err(format!("Invalid config for {widget}: {}", errors.join(", ")).into());
+            }
+        }
+        std::fs::write(
+            widget_config_path(widget),
+            serde_json::to_string_pretty(&config)?,
+        )?;
+        Ok(())
+    }
+
+    /// called by the files watcher, also covers configs edited outside of Seelen
+    pub(super) fn on_widget_config_changed(&self, path: &Path) -> Result<()> {
+        let Some(widget) = widget_id_from_config_path(path) else {
+            return Ok(());
+        };
+        log::info!("Widget config changed: {widget}");
+        emit_widget_config(&widget, &self.widget_config(&widget)?)
+    }
+}
@@ -0,0 +1,90 @@
+use base64::Engine;
+use itertools::Itertools;
+use seelen_core::state::WidgetId;
+use tauri::{Emitter, Manager};
+
+use crate::{
+    error_handler::Result, seelen::get_app_handle, seelen_bar::FancyToolbar,
+    seelen_rofi::SeelenRofi, seelen_wall::SeelenWall, seelen_weg::SeelenWeg,
+    seelen_wm_v2::instance::WindowManagerV2,
+};
+
+use super::{super::domain::Theme, FullState};
+
+/// label prefix (before base64 encoding) of the webviews of a widget
+fn widget_target(widget: &WidgetId) -> String {
+    match widget.0.as_str() {
+        "weg" => SeelenWeg::TARGET.to_string(),
+        "toolbar" => FancyToolbar::TARGET.to_string(),
+        "wm" => WindowManagerV2::TARGET.to_string(),
+        "launcher" => SeelenRofi::TARGET.to_string(),
+        "wall" => SeelenWall::TARGET.to_string(),
+        _ => widget.to_string(),
+    }
+}
+
+/// true if the webview label is one of the instances of the widget
+pub fn is_widget_label(label: &str, widget: &WidgetId) -> bool {
+    let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    let Ok(decoded) = engine.decode(label) else {
+        return false;
+    };
+    let decoded = String::from_utf8_lossy(&decoded);
+    let target = widget_target(widget);
+    decoded == target || decoded.starts_with(&format!("{target}?"))
+}
+
+pub(super) fn widget_labels(widget: &WidgetId) -> Vec<String> {
+    get_app_handle()
+        .webview_windows()
+        .into_keys()
+        .filter(|label| is_widget_label(label, widget))
+        .collect_vec()
+}
+
+impl FullState {
+    /// Global themes followed by the themes assigned to the widget, so the widget
+    /// specific ones are applied last and take precedence on the cascade.
+    pub fn widget_theme_stack(&self, widget: &WidgetId) -> Vec<&Theme> {
+        let assigned = self.extended_settings.widget_themes.get(widget);
+        self.settings
+            .selected_themes
+            .iter()
+            .filter(|id| !assigned.is_some_and(|assigned| assigned.contains(id)))
+            .chain(assigned.into_iter().flatten())
+            .filter_map(|id| self.themes.get(id))
+            .collect_vec()
+    }
+
+    fn emit_widget_themes(&self, widget: &WidgetId) -> Result<()> {
+        let stack = self.widget_theme_stack(widget);
+        for label in widget_labels(widget) {
+            get_app_handle().emit_to(&label, "widget-themes-changed", &stack)?;
+        }
+        Ok(())
+    }
+
+    /// the stacks depend on the global themes so they are re-emitted every time the theme list
+    /// or the selected themes change, widgets without assignments only use the global event
+    pub(super) fn emit_widget_theme_stacks(&self) -> Result<()> {
+        for widget in self.extended_settings.widget_themes.keys() {
+            self.emit_widget_themes(widget)?;
+        }
+        Ok(())
+    }
+
+    /// an empty list removes the assignment, so the widget only uses the global themes
+    pub fn set_widget_themes(&mut self, widget: WidgetId, theme_ids: Vec<String>) -> Result<()> {
+        if let Some(id) = theme_ids.iter().find(|id| !self.themes.contains_key(*id)) {
+            return Err(format!("Theme not found: {id}").into());
+        }
+        let widget_themes = &mut self.extended_settings.widget_themes;
+        if theme_ids.is_empty() {
+            widget_themes.remove(&widget);
+        } else {
+            widget_themes.insert(widget.clone(), theme_ids.into_iter().unique().collect());
+        }
+        self.write_settings()?;
+        self.emit_widget_themes(&widget)
+    }
+}
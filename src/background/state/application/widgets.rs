@@ -5,19 +5,19 @@ use tauri::Emitter;
 
 use crate::{error_handler::Result, seelen::get_app_handle, utils::constants::SEELEN_COMMON};
 
-use super::FullState;
+use super::{super::domain::ResourceKind, FullState};
 
 impl FullState {
     pub(super) fn emit_widgets(&self) -> Result<()> {
-        get_app_handle().emit(SeelenEvent::StateWidgetsChanged, &self.plugins)?;
+        get_app_handle().emit(SeelenEvent::StateWidgetsChanged, self.sourced_widgets())?;
         Ok(())
     }
 
-    fn load_widget_from_file(path: PathBuf) -> Result<Widget> {
+    pub(super) fn load_widget_from_file(path: PathBuf) -> Result<Widget> {
         Ok(serde_yaml::from_str(&std::fs::read_to_string(&path)?)?)
     }
 
-    fn load_widget_from_folder(path: PathBuf) -> Result<Widget> {
+    pub(super) fn load_widget_from_folder(path: PathBuf) -> Result<Widget> {
         let mut widget = Self::load_widget_from_file(path.join("metadata.yml"))?;
         widget.js = Some(std::fs::read_to_string(path.join("index.js"))?);
         widget.css = Some(std::fs::read_to_string(path.join("index.css"))?);
@@ -29,16 +29,24 @@ impl FullState {
         let user_path = SEELEN_COMMON.user_widgets_path();
         let bundled_path = SEELEN_COMMON.bundled_widgets_path();
 
-        let entries = std::fs::read_dir(bundled_path)?.chain(std::fs::read_dir(user_path)?);
-        for entry in entries.flatten() {
+        let entries = Self::resource_entries(bundled_path, user_path)?;
+        for entry in entries {
             let path = entry.path();
             let widget = if path.is_dir() {
-                Self::load_widget_from_folder(path)
+                Self::load_widget_from_folder(path.clone())
             } else {
-                Self::load_widget_from_file(path)
+                Self::load_widget_from_file(path.clone())
             };
             match widget {
                 Ok(widget) => {
+                    if !self.resolve_resource(
+                        ResourceKind::Widget,
+                        widget.id.to_string(),
+                        &path,
+                        bundled_path,
+                    ) {
+                        continue;
+                    }
                     self.widgets.insert(widget.id.clone(), widget);
                 }
                 Err(e) => {
@@ -1 +1,158 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
+};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
 pub use seelen_core::state::*;
+
+use crate::{
+    hook::WinEventsConfig,
+    modules::{
+        notifications::domain::NotificationsConfig, power::domain::BatteryAlertSettings,
+        weather::domain::WeatherConfig, window_style::domain::BackdropKind,
+    },
+    seelen_wall::{
+        dim::WallDimConfig,
+        fit_mode::{WallFit, WallFitConfig},
+        slideshow::WallSlideshowConfig,
+    },
+};
+
+/// Sections of `settings.json` owned by this app on top of the seelen-core `Settings`, both are
+/// read from and written to the same file so these are loaded, saved and emitted together.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ExtendedSettings {
+    pub weather: WeatherConfig,
+    pub notifications: NotificationsConfig,
+    pub battery_alerts: BatteryAlertSettings,
+    pub resource_pins: ResourcePins,
+    /// backdrop of the Seelen webviews, by label
+    pub backdrops: HashMap<String, BackdropKind>,
+    /// normalized program paths that are always launched as administrator
+    pub elevated_items: HashSet<String>,
+    pub wall: ExtendedWallSettings,
+    /// themes applied to each widget on top of the global ones
+    #[schemars(with = "HashMap<String, Vec<String>>")]
+    pub widget_themes: WidgetThemes,
+    pub win_events: WinEventsConfig,
+}
+
+/// Keys of the `wall` section missing on the seelen-core wall settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ExtendedWallSettings {
+    pub slideshow: WallSlideshowConfig,
+    pub fit: WallFitConfig,
+    pub dim: WallDimConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ThemeVariant {
+    Light,
+    Dark,
+    HighContrast,
+}
+
+/// `variants` section of a theme manifest, each variant contains styles by widget
+/// that are appended to the base styles of the theme when the variant is selected.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ThemeVariants {
+    #[serde(default)]
+    pub variants: HashMap<ThemeVariant, HashMap<WidgetId, String>>,
+    /// styles of the theme without any variant applied
+    #[serde(skip)]
+    pub base: HashMap<WidgetId, String>,
+}
+
+/// Serialized in lowercase ("theme", "widget", ...) as used by the resource catalog, this is
+/// also the value taken and returned by the install/uninstall/pin commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceKind {
+    Theme,
+    Layout,
+    Placeholder,
+    Plugin,
+    Widget,
+}
+
+pub type WidgetThemes = HashMap<WidgetId, Vec<String>>;
+
+/// ids of the resources pinned to their bundled version, by kind
+pub type ResourcePins = HashMap<ResourceKind, HashSet<String>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResourceSource {
+    Bundled,
+    User,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceOrigin {
+    pub source: ResourceSource,
+    /// absolute path of the file or folder the resource was loaded from
+    pub path: PathBuf,
+    /// true when this is a user resource with the same id of a bundled one
+    pub overrides_bundled: bool,
+    /// path of the bundled resource hidden by this one
+    pub bundled_path: Option<PathBuf>,
+    /// bundled resource that is forced over any user resource with the same id
+    pub pinned: bool,
+}
+
+/// Resource tagged with its origin, the origin fields are flattened so the shape
+/// of the resource is kept for existing consumers.
+#[derive(Debug, Clone, Serialize)]
+pub struct SourcedResource<T> {
+    #[serde(flatten)]
+    pub resource: T,
+    #[serde(flatten)]
+    pub origin: Option<ResourceOrigin>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingSource {
+    /// global value not changed by the user
+    Default,
+    Global,
+    Monitor,
+    Workspace,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectiveValue<T> {
+    pub value: T,
+    pub source: SettingSource,
+}
+
+/// Settings resolved for a monitor/workspace context, same tree as the settings file
+/// where each leaf has the layer it comes from.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum EffectiveSettings {
+    Value(EffectiveValue<serde_json::Value>),
+    Section(BTreeMap<String, EffectiveSettings>),
+}
+
+/// JSON schema of the settings file, `schema_version` is the version of the file it describes.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsSchema {
+    pub schema_version: u32,
+    pub schema: schemars::schema::RootSchema,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Wallpaper {
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub fit: WallFit,
+}
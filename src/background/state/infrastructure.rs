@@ -1,17 +1,54 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 use itertools::Itertools;
 use seelen_core::state::{
-    IconPack, MonitorConfiguration, Plugin, Profile, WegItems, Widget, WindowManagerLayout,
+    IconPack, MonitorConfiguration, Plugin, Profile, WegItems, Widget, WidgetId,
+    WindowManagerLayout,
 };
+use serde_json::Value;
+use tauri::WebviewWindow;
 
-use crate::{error_handler::Result, trace_lock, windows_api::WindowsApi};
+use crate::{
+    error_handler::Result, modules::system_settings::application::SYSTEM_SETTINGS,
+    seelen_wall::fit_mode::WallFitConfig, trace_lock, windows_api::WindowsApi,
+};
 
 use super::{
-    application::{FullState, LauncherHistory, FULL_STATE},
-    domain::{AppConfig, Placeholder, Settings, Theme},
+    application::{
+        is_widget_label, settings_from_partial_value, settings_schema, settings_to_value,
+        FullState, LauncherHistory, FULL_STATE,
+    },
+    domain::{
+        AppConfig, EffectiveSettings, ExtendedSettings, Placeholder, ResourceKind, Settings,
+        SettingsSchema, SourcedResource, Theme, ThemeVariant, Wallpaper, WidgetThemes,
+    },
 };
 
+/// Applies `f` to a copy of the current state and stores it only on success. Uses `rcu` so
+/// concurrent updates are retried on top of each other instead of being overwritten.
+fn update_full_state<T>(mut f: impl FnMut(&mut FullState) -> Result<T>) -> Result<T> {
+    let mut output = None;
+    FULL_STATE.rcu(|current| {
+        let mut state = current.cloned();
+        let result = f(&mut state);
+        let next = match result {
+            Ok(_) => Arc::new(state),
+            Err(_) => Arc::clone(current),
+        };
+        output = Some(result);
+        next
+    });
+    output.expect("rcu runs the update at least once")
+}
+
+/// Edits the app owned sections of `settings.json`, the file watcher reloads and emits them.
+pub fn update_extended_settings(mut f: impl FnMut(&mut ExtendedSettings)) -> Result<()> {
+    update_full_state(|state| {
+        f(&mut state.extended_settings);
+        state.write_settings()
+    })
+}
+
 #[tauri::command(async)]
 pub fn state_get_icon_packs() -> Vec<IconPack> {
     let icon_packs = FULL_STATE.load().icon_packs.clone();
@@ -19,24 +56,73 @@ pub fn state_get_icon_packs() -> Vec<IconPack> {
     icon_packs.values().cloned().collect_vec()
 }
 
+/// with a widget id returns the effective themes stack of the widget, ordered by precedence
 #[tauri::command(async)]
-pub fn state_get_themes() -> Vec<Theme> {
-    FULL_STATE.load().themes().values().cloned().collect_vec()
+pub fn state_get_themes(widget_id: Option<WidgetId>) -> Vec<SourcedResource<Theme>> {
+    let state = FULL_STATE.load();
+    match widget_id {
+        Some(widget_id) => state
+            .widget_theme_stack(&widget_id)
+            .into_iter()
+            .map(|theme| state.sourced(ResourceKind::Theme, &theme.info.filename, theme))
+            .collect_vec(),
+        None => state.sourced_themes(),
+    }
 }
 
 #[tauri::command(async)]
-pub fn state_get_placeholders() -> Vec<Placeholder> {
-    FULL_STATE
-        .load()
-        .placeholders()
-        .values()
-        .cloned()
-        .collect_vec()
+pub fn reorder_themes(order: Vec<String>) -> Result<()> {
+    update_full_state(|state| state.reorder_themes(order.clone()))
+}
+
+#[tauri::command(async)]
+pub fn state_get_widget_themes() -> WidgetThemes {
+    FULL_STATE.load().extended_settings().widget_themes.clone()
+}
+
+#[tauri::command(async)]
+pub fn set_widget_themes(widget_id: WidgetId, theme_ids: Vec<String>) -> Result<()> {
+    update_full_state(|state| state.set_widget_themes(widget_id.clone(), theme_ids.clone()))
+}
+
+#[tauri::command(async)]
+pub fn set_theme_variant(theme_id: String, variant: Option<ThemeVariant>) -> Result<()> {
+    update_full_state(|state| state.set_theme_variant(theme_id.clone(), variant))
+}
+
+pub fn register_theme_variant_events() {
+    std::thread::spawn(move || {
+        let mut manager = trace_lock!(SYSTEM_SETTINGS);
+        manager.on_colors_change(Box::new(FullState::on_system_theme_change));
+    });
+}
+
+#[tauri::command(async)]
+pub async fn install_resource_from_url(
+    url: String,
+    checksum: Option<String>,
+) -> Result<ResourceKind> {
+    FullState::install_resource_from_url(&url, checksum.as_deref()).await
+}
+
+#[tauri::command(async)]
+pub fn uninstall_resource(kind: ResourceKind, id: String) -> Result<()> {
+    update_full_state(|state| state.uninstall_resource(kind, &id))
+}
+
+#[tauri::command(async)]
+pub fn set_resource_pinned(kind: ResourceKind, id: String, pinned: bool) -> Result<()> {
+    update_full_state(|state| state.set_resource_pinned(kind, id.clone(), pinned))
 }
 
 #[tauri::command(async)]
-pub fn state_get_layouts() -> Vec<WindowManagerLayout> {
-    FULL_STATE.load().layouts().values().cloned().collect_vec()
+pub fn state_get_placeholders() -> Vec<SourcedResource<Placeholder>> {
+    FULL_STATE.load().sourced_placeholders()
+}
+
+#[tauri::command(async)]
+pub fn state_get_layouts() -> Vec<SourcedResource<WindowManagerLayout>> {
+    FULL_STATE.load().sourced_layouts()
 }
 
 #[tauri::command(async)]
@@ -60,20 +146,42 @@ pub fn state_get_history() -> LauncherHistory {
     FULL_STATE.load().launcher_history().clone()
 }
 
+/// seelen-core settings with the extended sections merged in, as stored on `settings.json`
 #[tauri::command(async)]
-pub fn state_get_settings(path: Option<PathBuf>) -> Result<Settings> {
+pub fn state_get_settings(path: Option<PathBuf>) -> Result<Value> {
     if let Some(path) = path {
-        let mut settings = FullState::get_settings_from_path(&path)?;
+        let (mut settings, extended) = FullState::get_settings_from_path(&path)?;
         settings.sanitize();
-        Ok(settings)
+        settings_to_value(&settings, &extended)
     } else {
-        Ok(FULL_STATE.load().settings().clone())
+        FULL_STATE.load().settings_value()
     }
 }
 
 #[tauri::command(async)]
-pub fn state_get_default_settings() -> Settings {
-    Settings::default()
+pub fn state_get_effective_settings(
+    monitor_id: Option<String>,
+    workspace_idx: Option<usize>,
+) -> Result<EffectiveSettings> {
+    FULL_STATE
+        .load()
+        .effective_settings(monitor_id.as_deref(), workspace_idx)
+}
+
+/// JSON schema of the settings file, intended for editors and external config tools
+#[tauri::command(async)]
+pub fn get_settings_schema() -> SettingsSchema {
+    settings_schema()
+}
+
+#[tauri::command(async)]
+pub fn state_get_settings_migrations() -> Vec<String> {
+    FULL_STATE.load().settings_migrations.clone()
+}
+
+#[tauri::command(async)]
+pub fn state_get_default_settings() -> Result<Value> {
+    settings_to_value(&Settings::default(), &ExtendedSettings::default())
 }
 
 #[tauri::command(async)]
@@ -82,15 +190,36 @@ pub fn state_get_default_monitor_settings() -> MonitorConfiguration {
 }
 
 #[tauri::command(async)]
-pub fn state_write_settings(settings: Settings) -> Result<()> {
+pub fn state_write_settings(settings: Value) -> Result<()> {
+    let (settings, extended) =
+        settings_from_partial_value(FULL_STATE.load().extended_settings(), settings)?;
     FULL_STATE.rcu(move |state| {
         let mut state = state.cloned();
         state.settings = settings.clone();
+        state.extended_settings = extended.clone();
         state
     });
     FULL_STATE.load().write_settings()
 }
 
+/// without `confirm` only lists the configs that would be pruned
+#[tauri::command(async)]
+pub fn state_prune_monitor_configs(confirm: bool) -> Result<Vec<String>> {
+    if !confirm {
+        return FULL_STATE.load().cloned().prune_monitor_configs(false);
+    }
+    update_full_state(|state| state.prune_monitor_configs(true))
+}
+
+#[tauri::command(async)]
+pub fn state_copy_monitor_config(
+    from_device_id: String,
+    to_device_id: String,
+    force: bool,
+) -> Result<Option<MonitorConfiguration>> {
+    update_full_state(|state| state.copy_monitor_config(&from_device_id, &to_device_id, force))
+}
+
 #[tauri::command(async)]
 pub fn state_get_specific_apps_configurations() -> Vec<AppConfig> {
     FULL_STATE
@@ -101,27 +230,57 @@ pub fn state_get_specific_apps_configurations() -> Vec<AppConfig> {
         .collect_vec()
 }
 
+/// wallpaper with the fit mode the wall widget should render it with
 #[tauri::command(async)]
-pub fn state_get_wallpaper() -> Result<PathBuf> {
-    WindowsApi::get_wallpaper()
+pub fn state_get_wallpaper() -> Result<Wallpaper> {
+    Ok(Wallpaper {
+        path: WindowsApi::get_wallpaper()?,
+        fit: WallFitConfig::load().resolved(),
+    })
 }
 
+/// Wallpaper rendered by the Seelen wall, also set as the OS wallpaper on all the
+/// monitors and virtual desktops. See `set_system_wallpaper` for style or per monitor.
 #[tauri::command(async)]
 pub fn state_set_wallpaper(path: String) -> Result<()> {
     WindowsApi::set_wallpaper(path)
 }
 
 #[tauri::command(async)]
-pub fn state_get_plugins() -> Vec<Plugin> {
-    FULL_STATE.load().plugins().values().cloned().collect_vec()
+pub fn state_get_plugins() -> Vec<SourcedResource<Plugin>> {
+    FULL_STATE.load().sourced_plugins()
 }
 
 #[tauri::command(async)]
-pub fn state_get_widgets() -> Vec<Widget> {
-    FULL_STATE.load().widgets().values().cloned().collect_vec()
+pub fn state_get_widgets() -> Vec<SourcedResource<Widget>> {
+    FULL_STATE.load().sourced_widgets()
 }
 
 #[tauri::command(async)]
 pub fn state_get_profiles() -> Vec<Profile> {
     FULL_STATE.load().profiles.clone()
 }
+
+/// widgets can only access their own config
+fn ensure_widget_owner(webview: &WebviewWindow, widget_id: &WidgetId) -> Result<()> {
+    if !is_widget_label(webview.label(), widget_id) {
+        return Err(format!(
+            "{} is not allowed to access the config of {widget_id}",
+            webview.label()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[tauri::command(async)]
+pub fn widget_config_get(webview: WebviewWindow, widget_id: WidgetId) -> Result<Value> {
+    ensure_widget_owner(&webview, &widget_id)?;
+    FULL_STATE.load().widget_config(&widget_id)
+}
+
+#[tauri::command(async)]
+pub fn widget_config_set(webview: WebviewWindow, widget_id: WidgetId, value: Value) -> Result<()> {
+    ensure_widget_owner(&webview, &widget_id)?;
+    FULL_STATE.load().set_widget_config(&widget_id, value)
+}
@@ -5,9 +5,18 @@ pub mod infrastructure;
 use std::collections::HashMap;
 
 use application::FullState;
-use domain::AhkVar;
+use domain::{AhkVar, EffectiveSettings, EffectiveValue, SettingSource, Settings};
+use serde_json::Value;
 
-use crate::windows_api::monitor::Monitor;
+use crate::{error_handler::Result, windows_api::monitor::Monitor};
+
+/// sections of a monitor config and the global settings section they override
+const MONITOR_SECTIONS: [(&str, &str); 4] = [
+    ("tb", "fancyToolbar"),
+    ("weg", "seelenweg"),
+    ("wm", "windowManager"),
+    ("wall", "wall"),
+];
 
 impl FullState {
     pub fn is_weg_enabled(&self) -> bool {
@@ -76,4 +85,94 @@ impl FullState {
             None => default,
         }
     }
+
+    /// `default` is the value of the same setting on a fresh install, if any
+    fn resolve_global(value: Value, default: Option<&Value>) -> EffectiveSettings {
+        match value {
+            Value::Object(section) => EffectiveSettings::Section(
+                section
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let default = default.and_then(|default| default.get(&key));
+                        let resolved = Self::resolve_global(value, default);
+                        (key, resolved)
+                    })
+                    .collect(),
+            ),
+            value => {
+                let source = if default == Some(&value) {
+                    SettingSource::Default
+                } else {
+                    SettingSource::Global
+                };
+                EffectiveSettings::Value(EffectiveValue { value, source })
+            }
+        }
+    }
+
+    /// Replaces the leaves of `tree` with the non null values of `overrides` on the same path.
+    fn apply_overrides(tree: &mut EffectiveSettings, overrides: &Value, source: SettingSource) {
+        let (EffectiveSettings::Section(section), Value::Object(overrides)) = (tree, overrides)
+        else {
+            return;
+        };
+        for (key, value) in overrides {
+            let Some(node) = section.get_mut(key) else {
+                continue;
+            };
+            match node {
+                EffectiveSettings::Section(_) => Self::apply_overrides(node, value, source),
+                EffectiveSettings::Value(current) => {
+                    // as on `is_*_enabled_on_monitor`, disabled globally means disabled everywhere
+                    let globally_disabled = key == "enabled" && current.value == Value::Bool(false);
+                    if !value.is_null() && !globally_disabled {
+                        *current = EffectiveValue {
+                            value: value.clone(),
+                            source,
+                        };
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolves the whole settings tree as `is_*_enabled_on_monitor` and `get_wm_layout_id` do,
+    /// keeping track of the layer that decided each value.
+    pub fn effective_settings(
+        &self,
+        monitor_id: Option<&str>,
+        workspace_idx: Option<usize>,
+    ) -> Result<EffectiveSettings> {
+        let defaults = serde_json::to_value(Settings::default())?;
+        let mut settings = serde_json::to_value(&self.settings)?;
+        // monitor configs are layered below instead of being listed
+        if let Value::Object(settings) = &mut settings {
+            settings.remove("monitorsV2");
+        }
+        let mut tree = Self::resolve_global(settings, Some(&defaults));
+
+        let Some(monitor) = monitor_id.and_then(|id| self.settings.monitors_v2.get(id)) else {
+            return Ok(tree);
+        };
+        let monitor_value = serde_json::to_value(monitor)?;
+        for (monitor_key, global_key) in MONITOR_SECTIONS {
+            let (EffectiveSettings::Section(sections), Some(overrides)) =
+                (&mut tree, monitor_value.get(monitor_key))
+            else {
+                continue;
+            };
+            if let Some(section) = sections.get_mut(global_key) {
+                Self::apply_overrides(section, overrides, SettingSource::Monitor);
+            }
+        }
+
+        let workspace_layout = workspace_idx
+            .and_then(|idx| monitor.workspaces_v2.get(idx))
+            .and_then(|workspace| workspace.layout.clone());
+        if let Some(layout) = workspace_layout {
+            let overrides = serde_json::json!({ "windowManager": { "defaultLayout": layout } });
+            Self::apply_overrides(&mut tree, &overrides, SettingSource::Workspace);
+        }
+        Ok(tree)
+    }
 }
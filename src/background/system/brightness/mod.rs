@@ -1,3 +1,6 @@
+pub mod schedule;
+
+use schedule::BrightnessScheduler;
 use serde::Serialize;
 use windows::Win32::Devices::Display::{
     GetMonitorBrightness, GetMonitorCapabilities, SetMonitorBrightness,
@@ -50,8 +53,7 @@ pub fn get_main_monitor_brightness() -> Result<Brightness> {
     Ok(brightness)
 }
 
-#[tauri::command(async)]
-pub fn set_main_monitor_brightness(brightness: u32) -> Result<()> {
+fn apply_main_monitor_brightness(brightness: u32) -> Result<()> {
     let result = unsafe {
         let hmonitor = WindowsApi::primary_physical_monitor()?;
         SetMonitorBrightness(hmonitor.hPhysicalMonitor, brightness)
@@ -61,3 +63,17 @@ pub fn set_main_monitor_brightness(brightness: u32) -> Result<()> {
     }
     Ok(())
 }
+
+/// `percent` is mapped to the brightness range supported by the monitor
+pub fn set_main_monitor_brightness_percent(percent: u32) -> Result<()> {
+    let range = get_main_monitor_brightness()?;
+    let brightness = range.min + (range.max - range.min) * percent.min(100) / 100;
+    apply_main_monitor_brightness(brightness)
+}
+
+/// manual changes pause the brightness schedule until its next point
+#[tauri::command(async)]
+pub fn set_main_monitor_brightness(brightness: u32) -> Result<()> {
+    BrightnessScheduler::override_until_next_point();
+    apply_main_monitor_brightness(brightness)
+}
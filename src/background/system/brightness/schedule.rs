@@ -0,0 +1,327 @@
+use std::{
+    f64::consts::PI,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error_handler::Result, log_error, trace_lock, utils::constants::SEELEN_COMMON,
+    utils::spawn_named_thread,
+};
+
+use super::set_main_monitor_brightness_percent;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GeoLocation {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ScheduleTime {
+    #[serde(rename_all = "camelCase")]
+    Clock { hour: u32, minute: u32 },
+    #[serde(rename_all = "camelCase")]
+    Sunrise {
+        #[serde(default)]
+        offset_minutes: i64,
+    },
+    #[serde(rename_all = "camelCase")]
+    Sunset {
+        #[serde(default)]
+        offset_minutes: i64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePoint {
+    pub at: ScheduleTime,
+    /// percent of the monitor brightness range
+    pub brightness: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BrightnessSchedule {
+    pub enabled: bool,
+    /// required by sunrise/sunset points
+    pub location: Option<GeoLocation>,
+    pub points: Vec<SchedulePoint>,
+    /// time to smoothly move from the previous brightness to the one of the point
+    pub ramp_minutes: u32,
+}
+
+impl Default for BrightnessSchedule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            location: None,
+            points: Vec::new(),
+            ramp_minutes: 15,
+        }
+    }
+}
+
+impl BrightnessSchedule {
+    fn validate(&self) -> Result<()> {
+        if let Some(location) = &self.location {
+            if !(-90.0..=90.0).contains(&location.lat) || !(-180.0..=180.0).contains(&location.lon)
+            {
+                return Err("Invalid location".into());
+            }
+        }
+
+        for point in &self.points {
+            if point.brightness > 100 {
+                return Err("Brightness should be a percent between 0 and 100".into());
+            }
+            match point.at {
+                ScheduleTime::Clock { hour, minute } if hour > 23 || minute > 59 => {
+                    return Err(format!("Invalid time {:02}:{:02}", hour, minute).into());
+                }
+                ScheduleTime::Sunrise { .. } | ScheduleTime::Sunset { .. }
+                    if self.location.is_none() =>
+                {
+                    return Err("Sunrise and sunset points require a location".into());
+                }
+                _ => {}
+            }
+        }
+
+        if self.enabled && self.points.is_empty() {
+            return Err("Can not enable a schedule without points".into());
+        }
+        Ok(())
+    }
+
+    /// Points resolved to local times of `date`, sorted. Sun based points are skipped on
+    /// polar days/nights as there is no sunrise/sunset.
+    fn points_on(&self, date: NaiveDate) -> Vec<(DateTime<Local>, u32)> {
+        let sun = self
+            .location
+            .and_then(|location| sun_times(date, location.lat, location.lon));
+
+        let mut points: Vec<(DateTime<Local>, u32)> = self
+            .points
+            .iter()
+            .filter_map(|point| {
+                let at = match point.at {
+                    ScheduleTime::Clock { hour, minute } => Local
+                        .from_local_datetime(
+                            &date.and_time(NaiveTime::from_hms_opt(hour, minute, 0)?),
+                        )
+                        .earliest()?,
+                    ScheduleTime::Sunrise { offset_minutes } => {
+                        sun?.0 + chrono::Duration::minutes(offset_minutes)
+                    }
+                    ScheduleTime::Sunset { offset_minutes } => {
+                        sun?.1 + chrono::Duration::minutes(offset_minutes)
+                    }
+                };
+                Some((at, point.brightness))
+            })
+            .collect();
+        points.sort_by_key(|(at, _)| *at);
+        points
+    }
+
+    /// (brightness to apply now, time of the next scheduled point)
+    fn target_at(&self, now: DateTime<Local>) -> Option<(u32, DateTime<Local>)> {
+        let today = now.date_naive();
+        let mut timeline = self.points_on(today.pred_opt()?);
+        timeline.extend(self.points_on(today));
+        timeline.extend(self.points_on(today.succ_opt()?));
+
+        let current = timeline.iter().rposition(|(at, _)| *at <= now)?;
+        let (at, brightness) = timeline[current];
+        let next = timeline.get(current + 1)?.0;
+
+        let ramp = chrono::Duration::minutes(self.ramp_minutes as i64);
+        let elapsed = now - at;
+        if current == 0 || ramp.is_zero() || elapsed >= ramp {
+            return Some((brightness, next));
+        }
+
+        let (_, previous) = timeline[current - 1];
+        let progress = elapsed.num_seconds() as f64 / ramp.num_seconds() as f64;
+        let value = previous as f64 + (brightness as f64 - previous as f64) * progress;
+        Some((value.round() as u32, next))
+    }
+}
+
+/// https://en.wikipedia.org/wiki/Sunrise_equation
+fn sun_times(date: NaiveDate, lat: f64, lon: f64) -> Option<(DateTime<Local>, DateTime<Local>)> {
+    let to_rad = PI / 180.0;
+    let noon = date.and_hms_opt(12, 0, 0)?.and_utc().timestamp() as f64;
+    let julian_day = noon / 86400.0 + 2440587.5;
+
+    let n = (julian_day - 2451545.0 + 0.0008).ceil();
+    let mean_solar_noon = n - lon / 360.0;
+    let anomaly = (357.5291 + 0.98560028 * mean_solar_noon) % 360.0;
+    let center = 1.9148 * (anomaly * to_rad).sin()
+        + 0.02 * (2.0 * anomaly * to_rad).sin()
+        + 0.0003 * (3.0 * anomaly * to_rad).sin();
+    let ecliptic_longitude = (anomaly + center + 180.0 + 102.9372) % 360.0;
+    let transit = 2451545.0 + mean_solar_noon + 0.0053 * (anomaly * to_rad).sin()
+        - 0.0069 * (2.0 * ecliptic_longitude * to_rad).sin();
+
+    let declination_sin = (ecliptic_longitude * to_rad).sin() * (23.4397 * to_rad).sin();
+    let declination = declination_sin.asin();
+    let hour_angle_cos = ((-0.833 * to_rad).sin() - (lat * to_rad).sin() * declination_sin)
+        / ((lat * to_rad).cos() * declination.cos());
+    if !(-1.0..=1.0).contains(&hour_angle_cos) {
+        return None;
+    }
+    let hour_angle = hour_angle_cos.acos() / to_rad;
+
+    let to_local = |julian: f64| {
+        let timestamp = ((julian - 2440587.5) * 86400.0).round() as i64;
+        Local.timestamp_opt(timestamp, 0).single()
+    };
+    Some((
+        to_local(transit - hour_angle / 360.0)?,
+        to_local(transit + hour_angle / 360.0)?,
+    ))
+}
+
+lazy_static! {
+    static ref SCHEDULER: Mutex<BrightnessScheduler> = Mutex::new(BrightnessScheduler::new());
+}
+
+static THREAD_STARTED: AtomicBool = AtomicBool::new(false);
+
+pub struct BrightnessScheduler {
+    schedule: BrightnessSchedule,
+    /// set by manual changes, the schedule is paused until the next scheduled point
+    override_until: Option<DateTime<Local>>,
+    last_applied: Option<u32>,
+}
+
+impl BrightnessScheduler {
+    fn new() -> Self {
+        let schedule = Self::read_schedule().unwrap_or_else(|err| {
+            log::error!("Failed to read brightness schedule: {:?}", err);
+            BrightnessSchedule::default()
+        });
+        Self {
+            schedule,
+            override_until: None,
+            last_applied: None,
+        }
+    }
+
+    fn read_schedule() -> Result<BrightnessSchedule> {
+        let path = SEELEN_COMMON.brightness_schedule_path();
+        if !path.exists() {
+            return Ok(BrightnessSchedule::default());
+        }
+        Ok(serde_yaml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    fn tick(&mut self) -> Result<()> {
+        if !self.schedule.enabled {
+            return Ok(());
+        }
+
+        let now = Local::now();
+        if self.override_until.is_some_and(|until| now < until) {
+            return Ok(());
+        }
+        self.override_until = None;
+
+        if let Some((brightness, _)) = self.schedule.target_at(now) {
+            if self.last_applied != Some(brightness) {
+                set_main_monitor_brightness_percent(brightness)?;
+                self.last_applied = Some(brightness);
+            }
+        }
+        Ok(())
+    }
+
+    fn ensure_thread() -> Result<()> {
+        if THREAD_STARTED.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        spawn_named_thread("Brightness Scheduler", || loop {
+            log_error!(trace_lock!(SCHEDULER).tick());
+            std::thread::sleep(TICK_INTERVAL);
+        })?;
+        Ok(())
+    }
+
+    /// starts the scheduler if the persisted schedule is enabled
+    pub fn init() -> Result<()> {
+        if trace_lock!(SCHEDULER).schedule.enabled {
+            Self::ensure_thread()?;
+        }
+        Ok(())
+    }
+
+    pub fn schedule() -> BrightnessSchedule {
+        trace_lock!(SCHEDULER).schedule.clone()
+    }
+
+    pub fn set_schedule(schedule: BrightnessSchedule) -> Result<()> {
+        schedule.validate()?;
+        std::fs::write(
+            SEELEN_COMMON.brightness_schedule_path(),
+            serde_yaml::to_string(&schedule)?,
+        )?;
+
+        let enabled = schedule.enabled;
+        {
+            let mut scheduler = trace_lock!(SCHEDULER);
+            scheduler.schedule = schedule;
+            scheduler.override_until = None;
+            scheduler.last_applied = None;
+        }
+
+        if enabled {
+            Self::ensure_thread()?;
+            // apply right away instead of waiting for the next tick
+            trace_lock!(SCHEDULER).tick()?;
+        }
+        Ok(())
+    }
+
+    pub fn set_enabled(enabled: bool) -> Result<()> {
+        let mut schedule = Self::schedule();
+        schedule.enabled = enabled;
+        Self::set_schedule(schedule)
+    }
+
+    /// called on manual brightness changes
+    pub fn override_until_next_point() {
+        let mut scheduler = trace_lock!(SCHEDULER);
+        if !scheduler.schedule.enabled {
+            return;
+        }
+        let now = Local::now();
+        scheduler.override_until = scheduler.schedule.target_at(now).map(|(_, next)| next);
+        scheduler.last_applied = None;
+    }
+}
+
+#[tauri::command(async)]
+pub fn get_brightness_schedule() -> BrightnessSchedule {
+    BrightnessScheduler::schedule()
+}
+
+#[tauri::command(async)]
+pub fn set_brightness_schedule(schedule: BrightnessSchedule) -> Result<()> {
+    BrightnessScheduler::set_schedule(schedule)
+}
+
+#[tauri::command(async)]
+pub fn set_brightness_schedule_enabled(enabled: bool) -> Result<()> {
+    BrightnessScheduler::set_enabled(enabled)
+}
@@ -2,21 +2,36 @@ pub mod brightness;
 
 use tauri::Listener;
 
+use brightness::schedule::BrightnessScheduler;
+
 use crate::{
     error_handler::Result,
     log_error,
     modules::{
+        bluetooth::infrastructure::{register_bluetooth_events, release_bluetooth_events},
+        devices::infrastructure::register_device_events,
+        focus_assist::infrastructure::register_focus_assist_events,
+        input::infrastructure::register_keyboard_events,
+        language::register_language_events,
         media::infrastructure::{register_media_events, release_media_events},
         monitors::infrastructure::register_monitor_webview_events,
         network::infrastructure::register_network_events,
+        night_light::infrastructure::register_night_light_events,
         notifications::infrastructure::{
             register_notification_events, release_notification_events,
         },
-        power::infrastructure::PowerManager,
+        peripherals::infrastructure::register_peripheral_battery_events,
+        power::{application::PowerScheduler, infrastructure::PowerManager},
+        radios::infrastructure::{register_radio_events, release_radio_events},
+        recycle_bin::infrastructure::register_recycle_bin_events,
+        spatial_audio::infrastructure::register_spatial_audio_events,
         system_settings::infrastructure::{register_colors_events, release_colors_events},
+        theme_variables::application::ThemeVariables,
         tray::infrastructure::register_tray_events,
     },
     seelen::get_app_handle,
+    seelen_wall::slideshow::WallSlideshow,
+    state::infrastructure::register_theme_variant_events,
 };
 
 pub fn declare_system_events_handlers() -> Result<()> {
@@ -25,6 +40,7 @@ pub fn declare_system_events_handlers() -> Result<()> {
     handle.listen("register-power-events", move |_| {
         log_error!(PowerManager::register_power_events());
         log_error!(PowerManager::emit_system_power_info());
+        log_error!(PowerManager::emit_power_plans());
     });
 
     handle.listen("register-tray-events", move |_| register_tray_events());
@@ -34,7 +50,19 @@ pub fn declare_system_events_handlers() -> Result<()> {
     });
 
     handle.listen("register-bluetooth-events", move |_| {
-        // todo
+        register_bluetooth_events();
+    });
+
+    handle.listen("register-radio-events", move |_| {
+        register_radio_events();
+    });
+
+    handle.listen("register-keyboard-events", move |_| {
+        register_keyboard_events();
+    });
+
+    handle.listen("register-language-events", move |_| {
+        register_language_events();
     });
 
     handle.listen("register-media-events", move |_| {
@@ -45,13 +73,45 @@ pub fn declare_system_events_handlers() -> Result<()> {
         register_notification_events();
     });
 
+    handle.listen("register-night-light-events", move |_| {
+        register_night_light_events();
+    });
+
+    handle.listen("register-peripheral-battery-events", move |_| {
+        register_peripheral_battery_events();
+    });
+
+    handle.listen("register-device-events", move |_| {
+        register_device_events();
+    });
+
+    handle.listen("register-focus-assist-events", move |_| {
+        register_focus_assist_events();
+    });
+
+    handle.listen("register-spatial-audio-events", move |_| {
+        register_spatial_audio_events();
+    });
+
+    handle.listen("register-recycle-bin-events", move |_| {
+        register_recycle_bin_events();
+    });
+
     register_monitor_webview_events();
     register_colors_events();
+    register_theme_variant_events();
+    log_error!(BrightnessScheduler::init());
+    log_error!(PowerScheduler::init());
+    log_error!(PowerManager::restore_battery_saver_threshold());
+    log_error!(ThemeVariables::init());
+    log_error!(WallSlideshow::init());
     Ok(())
 }
 
 pub fn release_system_events_handlers() {
     release_media_events();
+    release_bluetooth_events();
+    release_radio_events();
     release_notification_events();
     release_colors_events();
 }
@@ -7,12 +7,23 @@ use itertools::Itertools;
 use lazy_static::lazy_static;
 use tauri::{path::BaseDirectory, Manager};
 
-use crate::{error_handler::Result, seelen::get_app_handle};
+use crate::{error_handler::Result, log_error, seelen::get_app_handle};
 
-lazy_static! {
-    static ref ICONS: Icons = Icons::instance().expect("Failed to load icons paths");
+/// embedded copy of `static/icons/missing.png` used when the resource is not found on disk
+static MISSING_APP_ICON: &[u8] = include_bytes!("../../../static/icons/missing.png");
 
-    pub static ref SEELEN_COMMON: Arc<SeelenCommon> = Arc::new(SeelenCommon::new());
+lazy_static! {
+    static ref ICONS: Icons = Icons::instance().unwrap_or_else(|err| {
+        log::error!("Failed to load icons paths: {}", err);
+        Icons::fallback()
+    });
+
+    pub static ref SEELEN_COMMON: Arc<SeelenCommon> = Arc::new(
+        SeelenCommon::new().unwrap_or_else(|err| {
+            log::error!("Failed to resolve app paths: {}", err);
+            SeelenCommon::fallback()
+        })
+    );
 
     /**
      * Some UWP apps like WhatsApp are resized after be opened,
@@ -46,11 +57,22 @@ pub struct Icons {
 impl Icons {
     fn instance() -> Result<Self> {
         let handle = get_app_handle();
-        Ok(Self {
-            missing_app: handle
-                .path()
-                .resolve("static/icons/missing.png", BaseDirectory::Resource)?,
-        })
+        let missing_app = handle
+            .path()
+            .resolve("static/icons/missing.png", BaseDirectory::Resource)?;
+        if !missing_app.exists() {
+            return Err(format!("{} not found", missing_app.display()).into());
+        }
+        Ok(Self { missing_app })
+    }
+
+    /// writes the embedded missing icon to the temp folder
+    fn fallback() -> Self {
+        let missing_app = std::env::temp_dir().join("seelen_missing_app.png");
+        if !missing_app.exists() {
+            log_error!(std::fs::write(&missing_app, MISSING_APP_ICON));
+        }
+        Self { missing_app }
     }
 
     pub fn missing_app() -> PathBuf {
@@ -58,7 +80,13 @@ impl Icons {
     }
 }
 
+/// marker file beside the executable that enables the portable mode
+const PORTABLE_MARKER: &str = "portable.txt";
+/// environment variable that enables the portable mode when set to anything other than `0`
+const PORTABLE_ENV_VAR: &str = "SEELEN_UI_PORTABLE";
+
 pub struct SeelenCommon {
+    data: PathBuf,
     history: PathBuf,
     settings: PathBuf,
     weg_items: PathBuf,
@@ -78,20 +106,66 @@ pub struct SeelenCommon {
     wallpapers: PathBuf,
     profiles: PathBuf,
     bundled_profiles: PathBuf,
+    snap_layouts: PathBuf,
+    brightness_schedule: PathBuf,
+    widget_monitors: PathBuf,
+    power_schedule: PathBuf,
+    battery_saver_override: PathBuf,
+    theme_variants: PathBuf,
+    resource_index_config: PathBuf,
+    resource_index_cache: PathBuf,
+    widget_configs: PathBuf,
+    favorites: PathBuf,
+    wall_slideshow_progress: PathBuf,
 }
 
 impl SeelenCommon {
-    pub fn new() -> Self {
+    /// On portable mode user data lives in a `data` folder beside the executable instead of
+    /// `%APPDATA%`, bundled resources are always read from the resource dir.
+    pub fn new() -> Result<Self> {
         let handle = get_app_handle();
-        let data_dir = handle
-            .path()
-            .app_data_dir()
-            .expect("Failed to get app data dir");
-        let resource_dir = handle
-            .path()
-            .resource_dir()
-            .expect("Failed to get resource dir");
+        let resource_dir = handle.path().resource_dir()?;
+        if let Some(data_dir) = Self::portable_data_dir() {
+            std::fs::create_dir_all(&data_dir)?;
+            log::info!("Portable mode enabled, using {}", data_dir.display());
+            return Ok(Self::from_dirs(data_dir, resource_dir));
+        }
+        let data_dir = handle.path().app_data_dir()?;
+        Ok(Self::from_dirs(data_dir, resource_dir))
+    }
+
+    fn exe_dir() -> Option<PathBuf> {
+        std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf))
+    }
 
+    /// enabled by the `SEELEN_UI_PORTABLE` env var or a `portable.txt` file beside the executable
+    fn portable_data_dir() -> Option<PathBuf> {
+        let exe_dir = Self::exe_dir()?;
+        let by_env = std::env::var_os(PORTABLE_ENV_VAR).is_some_and(|v| !v.is_empty() && v != "0");
+        if by_env || exe_dir.join(PORTABLE_MARKER).exists() {
+            return Some(exe_dir.join("data"));
+        }
+        None
+    }
+
+    /// portable folder or %APPDATA%/<identifier> for user data and the executable folder for
+    /// bundled resources, same locations used by tauri on a regular installation.
+    fn fallback() -> Self {
+        let resource_dir = Self::exe_dir().unwrap_or_default();
+        if let Some(data_dir) = Self::portable_data_dir() {
+            return Self::from_dirs(data_dir, resource_dir);
+        }
+        let identifier = get_app_handle().config().identifier.clone();
+        let data_dir = std::env::var_os("APPDATA")
+            .map(PathBuf::from)
+            .unwrap_or_else(std::env::temp_dir)
+            .join(identifier);
+        Self::from_dirs(data_dir, resource_dir)
+    }
+
+    fn from_dirs(data_dir: PathBuf, resource_dir: PathBuf) -> Self {
         Self {
             history: data_dir.join("history"),
             settings: data_dir.join("settings.json"),
@@ -112,9 +186,26 @@ impl SeelenCommon {
             wallpapers: data_dir.join("wallpapers"),
             profiles: data_dir.join("profiles"),
             bundled_profiles: resource_dir.join("static/profiles"),
+            snap_layouts: data_dir.join("snap_layouts.json"),
+            brightness_schedule: data_dir.join("brightness_schedule.yml"),
+            widget_monitors: data_dir.join("widget_monitors.yml"),
+            power_schedule: data_dir.join("power_schedule.yml"),
+            battery_saver_override: data_dir.join("battery_saver_override.yml"),
+            theme_variants: data_dir.join("theme_variants.yml"),
+            resource_index_config: data_dir.join("resource_index.yml"),
+            resource_index_cache: data_dir.join("resource_index_cache.json"),
+            widget_configs: data_dir.join("widget_configs"),
+            favorites: data_dir.join("favorites.json"),
+            wall_slideshow_progress: data_dir.join("wall_slideshow_progress.yml"),
+            data: data_dir,
         }
     }
 
+    /// root folder of the user data
+    pub fn data_path(&self) -> &Path {
+        &self.data
+    }
+
     pub fn settings_path(&self) -> &Path {
         &self.settings
     }
@@ -190,4 +281,48 @@ impl SeelenCommon {
     pub fn bundled_profiles_path(&self) -> &Path {
         &self.bundled_profiles
     }
+
+    pub fn snap_layouts_path(&self) -> &Path {
+        &self.snap_layouts
+    }
+
+    pub fn brightness_schedule_path(&self) -> &Path {
+        &self.brightness_schedule
+    }
+
+    pub fn widget_monitors_path(&self) -> &Path {
+        &self.widget_monitors
+    }
+
+    pub fn power_schedule_path(&self) -> &Path {
+        &self.power_schedule
+    }
+
+    pub fn battery_saver_override_path(&self) -> &Path {
+        &self.battery_saver_override
+    }
+
+    pub fn theme_variants_path(&self) -> &Path {
+        &self.theme_variants
+    }
+
+    pub fn resource_index_config_path(&self) -> &Path {
+        &self.resource_index_config
+    }
+
+    pub fn resource_index_cache_path(&self) -> &Path {
+        &self.resource_index_cache
+    }
+
+    pub fn widget_configs_path(&self) -> &Path {
+        &self.widget_configs
+    }
+
+    pub fn favorites_path(&self) -> &Path {
+        &self.favorites
+    }
+
+    pub fn wall_slideshow_progress_path(&self) -> &Path {
+        &self.wall_slideshow_progress
+    }
 }
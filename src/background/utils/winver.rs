@@ -8,6 +8,11 @@ pub fn is_windows_11() -> bool {
     matches!(os_info::get().version(), os_info::Version::Semantic(_, _, x) if x >= &22000)
 }
 
+/// system backdrops (Mica, Acrylic, Tabbed) are available since Windows 11 22H2
+pub fn is_system_backdrop_supported() -> bool {
+    matches!(os_info::get().version(), os_info::Version::Semantic(_, _, x) if x >= &22621)
+}
+
 /// this should be called before call any winvd function
 pub fn is_virtual_desktop_supported() -> bool {
     // disable virtual desktop for 24h2
@@ -1,7 +1,24 @@
+use std::collections::HashMap;
+
 use base64::Engine;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
 use seelen_core::state::Widget;
 
-use crate::{error_handler::Result, seelen::get_app_handle};
+use crate::{
+    error_handler::Result, log_error, seelen::get_app_handle, trace_lock,
+    utils::constants::SEELEN_COMMON, windows_api::monitor::Monitor,
+};
+
+lazy_static! {
+    /// widget id -> device id of the only monitor where the widget should be shown
+    static ref MONITOR_AFFINITY: Mutex<HashMap<String, String>> = Mutex::new(
+        WidgetInstance::read_monitor_affinity().unwrap_or_else(|err| {
+            log::error!("Failed to read widgets monitor affinity: {:?}", err);
+            HashMap::new()
+        })
+    );
+}
 
 #[allow(dead_code)]
 pub struct WidgetInstance {
@@ -9,12 +26,25 @@ pub struct WidgetInstance {
     window: tauri::WebviewWindow,
 }
 
+impl Drop for WidgetInstance {
+    fn drop(&mut self) {
+        log::info!("Dropping {}", self.window.label());
+        log_error!(self.window.destroy());
+    }
+}
+
 impl WidgetInstance {
-    pub fn load(widget: Widget) -> Result<Self> {
+    pub fn load(widget: Widget, monitor: &Monitor) -> Result<Self> {
         let window = Self::create_window(&widget)?;
+        let rect = monitor.work_area()?;
+        window.set_position(tauri::PhysicalPosition::new(rect.left, rect.top))?;
         Ok(Self { widget, window })
     }
 
+    pub fn label(&self) -> String {
+        self.window.label().to_owned()
+    }
+
     fn create_window(widget: &Widget) -> Result<tauri::WebviewWindow> {
         let label = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(widget.id.to_string());
 
@@ -27,4 +57,29 @@ impl WidgetInstance {
         .build()?;
         Ok(window)
     }
+
+    fn read_monitor_affinity() -> Result<HashMap<String, String>> {
+        let path = SEELEN_COMMON.widget_monitors_path();
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+        Ok(serde_yaml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Widgets without affinity are shown on all the monitors
+    pub fn should_load_on(widget_id: &str, device_id: &str) -> bool {
+        trace_lock!(MONITOR_AFFINITY)
+            .get(widget_id)
+            .map_or(true, |target| target == device_id)
+    }
+
+    pub fn set_monitor_affinity(widget_id: &str, device_id: &str) -> Result<()> {
+        let mut affinity = trace_lock!(MONITOR_AFFINITY);
+        affinity.insert(widget_id.to_owned(), device_id.to_owned());
+        std::fs::write(
+            SEELEN_COMMON.widget_monitors_path(),
+            serde_yaml::to_string(&*affinity)?,
+        )?;
+        Ok(())
+    }
 }
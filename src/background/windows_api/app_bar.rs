@@ -5,7 +5,8 @@ use windows::Win32::{
     Foundation::{HWND, LPARAM, RECT},
     UI::Shell::{
         SHAppBarMessage, ABE_BOTTOM, ABE_LEFT, ABE_RIGHT, ABE_TOP, ABM_GETSTATE, ABM_NEW,
-        ABM_REMOVE, ABM_SETPOS, ABM_SETSTATE, ABS_ALWAYSONTOP, ABS_AUTOHIDE, APPBARDATA,
+        ABM_QUERYPOS, ABM_REMOVE, ABM_SETPOS, ABM_SETSTATE, ABS_ALWAYSONTOP, ABS_AUTOHIDE,
+        APPBARDATA,
     },
 };
 
@@ -90,7 +91,9 @@ impl AppBarData {
         self.0.rc = rect;
     }
 
-    pub fn register_as_new_bar(&mut self) {
+    /// returns the rect reserved by the system, it can be smaller than the requested one
+    /// if other app bars are already docked on the same edge
+    pub fn register_as_new_bar(&mut self) -> RECT {
         let mut data = self.0;
         let mut registered = trace_lock!(RegisteredBars);
         let addr = data.hWnd.0 as isize;
@@ -98,7 +101,21 @@ impl AppBarData {
             registered.push(addr);
             unsafe { SHAppBarMessage(ABM_NEW, &mut data) };
         }
+        let requested = data.rc;
+        unsafe { SHAppBarMessage(ABM_QUERYPOS, &mut data) };
+        // QUERYPOS only moves the rect out of other bars, so the side opposite to the docked
+        // edge is recalculated to keep the requested thickness before setting the position
+        let rc = &mut data.rc;
+        match data.uEdge {
+            ABE_LEFT => rc.right = rc.left + (requested.right - requested.left),
+            ABE_RIGHT => rc.left = rc.right - (requested.right - requested.left),
+            ABE_TOP => rc.bottom = rc.top + (requested.bottom - requested.top),
+            ABE_BOTTOM => rc.top = rc.bottom - (requested.bottom - requested.top),
+            _ => {}
+        }
         unsafe { SHAppBarMessage(ABM_SETPOS, &mut data) };
+        self.0.rc = data.rc;
+        data.rc
     }
 
     pub fn unregister_bar(&mut self) {
@@ -106,4 +123,13 @@ impl AppBarData {
         unsafe { SHAppBarMessage(ABM_REMOVE, &mut data) };
         trace_lock!(RegisteredBars).retain(|x| *x != data.hWnd.0 as isize);
     }
+
+    /// releases the work area reserved by all our bars
+    pub fn unregister_all() {
+        let registered = std::mem::take(&mut *trace_lock!(RegisteredBars));
+        for addr in registered {
+            let mut data = Self::from_handle(HWND(addr as _)).0;
+            unsafe { SHAppBarMessage(ABM_REMOVE, &mut data) };
+        }
+    }
 }
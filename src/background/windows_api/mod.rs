@@ -17,6 +17,7 @@ use windows_core::Interface;
 
 use std::{
     ffi::{c_void, OsString},
+    hash::{DefaultHasher, Hash, Hasher},
     os::windows::ffi::OsStrExt,
     path::{Path, PathBuf},
     thread::sleep,
@@ -33,25 +34,32 @@ use windows::{
         Threading::{NtQueryInformationProcess, ProcessBasicInformation},
     },
     Win32::{
-        Devices::Display::{
-            GetNumberOfPhysicalMonitorsFromHMONITOR, GetPhysicalMonitorsFromHMONITOR,
-            PHYSICAL_MONITOR,
+        Devices::{
+            Bluetooth::{
+                BluetoothFindFirstRadio, BluetoothFindRadioClose, BLUETOOTH_FIND_RADIO_PARAMS,
+            },
+            Display::{
+                GetNumberOfPhysicalMonitorsFromHMONITOR, GetPhysicalMonitorsFromHMONITOR,
+                PHYSICAL_MONITOR,
+            },
         },
         Foundation::{
-            CloseHandle, FALSE, HANDLE, HMODULE, HWND, LPARAM, LUID, MAX_PATH, RECT,
-            STATUS_SUCCESS, WPARAM,
+            CloseHandle, LocalFree, COLORREF, ERROR_CANCELLED, ERROR_NO_MORE_ITEMS, E_ACCESSDENIED,
+            FALSE, HANDLE, HLOCAL, HMODULE, HWND, LPARAM, LUID, MAX_PATH, RECT, STATUS_SUCCESS,
+            WPARAM,
         },
         Graphics::{
             Dwm::{
-                DwmGetWindowAttribute, DWMWA_CLOAKED, DWMWA_EXTENDED_FRAME_BOUNDS,
-                DWMWA_VISIBLE_FRAME_BORDER_THICKNESS, DWMWINDOWATTRIBUTE, DWM_CLOAKED_APP,
-                DWM_CLOAKED_INHERITED, DWM_CLOAKED_SHELL,
+                DwmGetWindowAttribute, DwmSetWindowAttribute, DWMWA_CLOAKED,
+                DWMWA_EXTENDED_FRAME_BOUNDS, DWMWA_VISIBLE_FRAME_BORDER_THICKNESS,
+                DWMWINDOWATTRIBUTE, DWM_CLOAKED_APP, DWM_CLOAKED_INHERITED, DWM_CLOAKED_SHELL,
             },
             Gdi::{
-                EnumDisplayDevicesW, EnumDisplayMonitors, EnumDisplaySettingsW, GetMonitorInfoW,
-                MonitorFromPoint, MonitorFromWindow, DEVMODEW, DISPLAY_DEVICEW,
-                ENUM_CURRENT_SETTINGS, HDC, HMONITOR, MONITORENUMPROC, MONITORINFOEXW,
-                MONITOR_DEFAULTTOPRIMARY,
+                ChangeDisplaySettingsExW, EnumDisplayDevicesW, EnumDisplayMonitors,
+                EnumDisplaySettingsW, GetMonitorInfoW, MonitorFromPoint, MonitorFromWindow,
+                CDS_TYPE, DEVMODEW, DISPLAY_DEVICEW, DISP_CHANGE_RESTART, DISP_CHANGE_SUCCESSFUL,
+                ENUM_CURRENT_SETTINGS, ENUM_DISPLAY_SETTINGS_MODE, HDC, HMONITOR, MONITORENUMPROC,
+                MONITORINFOEXW, MONITOR_DEFAULTTOPRIMARY,
             },
         },
         Security::{
@@ -69,37 +77,63 @@ use windows::{
         System::{
             Com::{IPersistFile, STGM_READ},
             LibraryLoader::GetModuleHandleW,
-            Power::{GetSystemPowerStatus, SetSuspendState, SYSTEM_POWER_STATUS},
+            Power::{
+                GetPwrCapabilities, GetSystemPowerStatus, IsPwrHibernateAllowed, PowerEnumerate,
+                PowerGetActiveScheme, PowerReadDCValueIndex, PowerReadFriendlyName,
+                PowerSetActiveScheme, PowerWriteDCValueIndex, SetSuspendState, ACCESS_SCHEME,
+                SYSTEM_POWER_CAPABILITIES, SYSTEM_POWER_STATUS,
+            },
+            Registry::HKEY,
             RemoteDesktop::ProcessIdToSessionId,
             Shutdown::{ExitWindowsEx, EXIT_WINDOWS_FLAGS, SHUTDOWN_REASON},
             Threading::{
-                GetCurrentProcess, GetCurrentProcessId, OpenProcess, OpenProcessToken,
-                QueryFullProcessImageNameW, PROCESS_ACCESS_RIGHTS, PROCESS_NAME_WIN32,
-                PROCESS_QUERY_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION,
+                GetCurrentProcess, GetCurrentProcessId, GetPriorityClass, OpenProcess,
+                OpenProcessToken, ProcessPowerThrottling, QueryFullProcessImageNameW,
+                SetPriorityClass, SetProcessInformation, PROCESS_ACCESS_RIGHTS,
+                PROCESS_CREATION_FLAGS, PROCESS_NAME_WIN32,
+                PROCESS_POWER_THROTTLING_CURRENT_VERSION, PROCESS_POWER_THROTTLING_EXECUTION_SPEED,
+                PROCESS_POWER_THROTTLING_STATE, PROCESS_QUERY_INFORMATION,
+                PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION,
             },
+            IO::DeviceIoControl,
         },
         UI::{
-            HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI},
+            Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW},
+            HiDpi::{
+                GetDpiForMonitor, SetThreadDpiAwarenessContext,
+                DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2, MDT_EFFECTIVE_DPI,
+            },
+            Input::KeyboardAndMouse::{
+                ActivateKeyboardLayout, GetKeyboardLayout, LoadKeyboardLayoutW, HKL, KLF_ACTIVATE,
+                KLF_SETFORPROCESS,
+            },
             Shell::{
                 IShellItem2, IShellLinkW, IVirtualDesktopManager,
                 PropertiesSystem::{IPropertyStore, SHGetPropertyStoreForWindow, GPS_DEFAULT},
                 SHCreateItemFromParsingName, SHLoadIndirectString, SHQueryUserNotificationState,
-                ShellLink, VirtualDesktopManager, QUERY_USER_NOTIFICATION_STATE,
-                QUNS_RUNNING_D3D_FULL_SCREEN, SIGDN_NORMALDISPLAY,
+                ShellExecuteExW, ShellLink, VirtualDesktopManager, QUERY_USER_NOTIFICATION_STATE,
+                QUNS_RUNNING_D3D_FULL_SCREEN, SHELLEXECUTEINFOW, SIGDN_NORMALDISPLAY,
             },
             WindowsAndMessaging::{
-                EnumWindows, GetClassNameW, GetDesktopWindow, GetForegroundWindow, GetParent,
-                GetSystemMetrics, GetWindow, GetWindowLongW, GetWindowRect, GetWindowTextW,
-                GetWindowThreadProcessId, IsIconic, IsWindow, IsWindowVisible, IsZoomed,
-                PostMessageW, SetForegroundWindow, SetWindowPos, ShowWindow, ShowWindowAsync,
-                SystemParametersInfoW, ANIMATIONINFO, EDD_GET_DEVICE_INTERFACE_NAME, GWL_EXSTYLE,
-                GWL_STYLE, GW_OWNER, HWND_TOP, SET_WINDOW_POS_FLAGS, SHOW_WINDOW_CMD,
-                SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+                BeginDeferWindowPos, DeferWindowPos, EndDeferWindowPos, EnumWindows, FlashWindowEx,
+                GetAncestor, GetClassNameW, GetDesktopWindow, GetForegroundWindow,
+                GetLayeredWindowAttributes, GetParent, GetSystemMetrics, GetTopWindow, GetWindow,
+                GetWindowLongW, GetWindowRect, GetWindowTextW, GetWindowThreadProcessId,
+                IsHungAppWindow, IsIconic, IsWindow, IsWindowVisible, IsZoomed, PostMessageW,
+                SendMessageTimeoutW, SetForegroundWindow, SetLayeredWindowAttributes,
+                SetWindowLongW, SetWindowPos, ShowWindow, ShowWindowAsync, SystemParametersInfoW,
+                WindowFromPoint, ANIMATIONINFO, EDD_GET_DEVICE_INTERFACE_NAME, FLASHWINFO,
+                FLASHWINFO_FLAGS, GA_ROOT, GWL_EXSTYLE, GWL_STYLE, GW_HWNDNEXT, GW_OWNER, HDWP,
+                HWND_TOP, LAYERED_WINDOW_ATTRIBUTES_FLAGS, MINMAXINFO, SET_WINDOW_POS_FLAGS,
+                SHOW_WINDOW_CMD, SMTO_ABORTIFHUNG, SM_CXMINTRACK, SM_CXVIRTUALSCREEN,
+                SM_CYMINTRACK, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
                 SPIF_SENDCHANGE, SPIF_UPDATEINIFILE, SPI_GETANIMATION, SPI_GETDESKWALLPAPER,
-                SPI_SETANIMATION, SPI_SETDESKWALLPAPER, SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE,
-                SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SW_FORCEMINIMIZE, SW_MINIMIZE, SW_NORMAL,
-                SW_RESTORE, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, WINDOW_EX_STYLE, WINDOW_STYLE,
-                WNDENUMPROC, WS_SIZEBOX, WS_THICKFRAME,
+                SPI_GETHIGHCONTRAST, SPI_SETANIMATION, SPI_SETDESKWALLPAPER, SWP_ASYNCWINDOWPOS,
+                SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SW_FORCEMINIMIZE,
+                SW_MINIMIZE, SW_NORMAL, SW_RESTORE, SW_SHOWNORMAL,
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, WINDOW_EX_STYLE, WINDOW_STYLE,
+                WM_GETMINMAXINFO, WM_INPUTLANGCHANGEREQUEST, WNDENUMPROC, WS_SIZEBOX,
+                WS_THICKFRAME,
             },
         },
     },
@@ -109,7 +143,7 @@ use crate::{
     error_handler::{Result, WindowsResultExt},
     hook::HookManager,
     modules::input::{domain::Point, Mouse},
-    utils::{is_virtual_desktop_supported, is_windows_11},
+    utils::{constants::SEELEN_COMMON, is_virtual_desktop_supported, is_windows_11},
     winevent::WinEvent,
 };
 
@@ -140,6 +174,18 @@ impl WindowsApi {
         Ok(unsafe { GetModuleHandleW(None) }?)
     }
 
+    /// Runs `cb` with the thread marked as per monitor DPI aware, so screen coordinates
+    /// are physical pixels on every monitor instead of being virtualized by Windows.
+    pub fn with_physical_coordinates<T>(cb: impl FnOnce() -> Result<T>) -> Result<T> {
+        let previous =
+            unsafe { SetThreadDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2) };
+        let result = cb();
+        if !previous.is_invalid() {
+            unsafe { SetThreadDpiAwarenessContext(previous) };
+        }
+        result
+    }
+
     pub fn enum_display_monitors(
         callback: MONITORENUMPROC,
         callback_data_address: isize,
@@ -212,6 +258,29 @@ impl WindowsApi {
         unsafe { GetForegroundWindow() }
     }
 
+    /// keyboard layout of the given thread, use 0 for the current thread
+    pub fn get_keyboard_layout(thread_id: u32) -> HKL {
+        unsafe { GetKeyboardLayout(thread_id) }
+    }
+
+    /// `klid` is the keyboard layout identifier, ex: "00000409"
+    pub fn load_keyboard_layout(klid: &str) -> Result<HKL> {
+        let klid = WindowsString::from_str(klid);
+        Ok(unsafe { LoadKeyboardLayoutW(klid.as_pcwstr(), KLF_ACTIVATE)? })
+    }
+
+    /// ActivateKeyboardLayout only affects the calling process so we also
+    /// request the change to the foreground window
+    pub fn set_keyboard_layout(hkl: HKL) -> Result<()> {
+        unsafe { ActivateKeyboardLayout(hkl, KLF_SETFORPROCESS)? };
+        Self::post_message(
+            Self::get_foreground_window(),
+            WM_INPUTLANGCHANGEREQUEST,
+            0,
+            hkl.0 as isize,
+        )
+    }
+
     pub fn is_window(hwnd: HWND) -> bool {
         unsafe { IsWindow(hwnd) }.into()
     }
@@ -224,6 +293,11 @@ impl WindowsApi {
         unsafe { IsIconic(hwnd) }.into()
     }
 
+    /// the window is not responding to messages (for about 5 seconds)
+    pub fn is_hung_app_window(hwnd: HWND) -> bool {
+        unsafe { IsHungAppWindow(hwnd) }.into()
+    }
+
     pub fn is_maximized(hwnd: HWND) -> bool {
         unsafe { IsZoomed(hwnd) }.into()
     }
@@ -284,6 +358,34 @@ impl WindowsApi {
         WINDOW_EX_STYLE(unsafe { GetWindowLongW(hwnd, GWL_EXSTYLE) } as u32)
     }
 
+    pub fn set_ex_styles(hwnd: HWND, styles: WINDOW_EX_STYLE) {
+        unsafe { SetWindowLongW(hwnd, GWL_EXSTYLE, styles.0 as i32) };
+    }
+
+    /// returns None if the window is not layered or it was layered via `UpdateLayeredWindow`
+    pub fn get_layered_attributes(
+        hwnd: HWND,
+    ) -> Option<(COLORREF, u8, LAYERED_WINDOW_ATTRIBUTES_FLAGS)> {
+        let mut key = COLORREF::default();
+        let mut alpha = 0;
+        let mut flags = LAYERED_WINDOW_ATTRIBUTES_FLAGS::default();
+        unsafe {
+            GetLayeredWindowAttributes(hwnd, Some(&mut key), Some(&mut alpha), Some(&mut flags))
+        }
+        .ok()?;
+        Some((key, alpha, flags))
+    }
+
+    pub fn set_layered_attributes(
+        hwnd: HWND,
+        key: COLORREF,
+        alpha: u8,
+        flags: LAYERED_WINDOW_ATTRIBUTES_FLAGS,
+    ) -> Result<()> {
+        unsafe { SetLayeredWindowAttributes(hwnd, key, alpha, flags)? };
+        Ok(())
+    }
+
     fn _set_position(
         hwnd: HWND,
         order: HWND,
@@ -318,6 +420,43 @@ impl WindowsApi {
         Self::_set_position(hwnd, order.unwrap_or_default(), *rect, uflags)
     }
 
+    /// Starts a batch of window positions that will be applied in one pass by `end_defer_window_pos`
+    pub fn begin_defer_window_pos(count: usize) -> Result<HDWP> {
+        Ok(unsafe { BeginDeferWindowPos(count as i32)? })
+    }
+
+    /// On failure the whole batch should be abandoned, without calling `end_defer_window_pos`
+    pub fn defer_window_pos(
+        hdwp: HDWP,
+        hwnd: HWND,
+        order: Option<HWND>,
+        rect: &RECT,
+        flags: SET_WINDOW_POS_FLAGS,
+    ) -> Result<HDWP> {
+        let uflags = match order {
+            Some(_) => flags,
+            None => SWP_NOZORDER | flags,
+        };
+        let hdwp = unsafe {
+            DeferWindowPos(
+                hdwp,
+                hwnd,
+                order.unwrap_or_default(),
+                rect.left,
+                rect.top,
+                (rect.right - rect.left).abs(),
+                (rect.bottom - rect.top).abs(),
+                uflags,
+            )?
+        };
+        Ok(hdwp)
+    }
+
+    pub fn end_defer_window_pos(hdwp: HDWP) -> Result<()> {
+        unsafe { EndDeferWindowPos(hdwp).filter_fake_error()? };
+        Ok(())
+    }
+
     pub fn move_window(hwnd: HWND, rect: &RECT) -> Result<()> {
         Self::set_position(hwnd, None, rect, SWP_NOSIZE | SWP_NOACTIVATE)
     }
@@ -340,6 +479,18 @@ impl WindowsApi {
         Self::show_window(hwnd, SW_RESTORE)
     }
 
+    /// returns the state of the window before the call, true if it was active
+    pub fn flash_window(hwnd: HWND, flags: FLASHWINFO_FLAGS, count: u32) -> bool {
+        let info = FLASHWINFO {
+            cbSize: std::mem::size_of::<FLASHWINFO>() as u32,
+            hwnd,
+            dwFlags: flags,
+            uCount: count,
+            dwTimeout: 0,
+        };
+        unsafe { FlashWindowEx(&info).as_bool() }
+    }
+
     pub fn set_foreground(hwnd: HWND) -> Result<()> {
         unsafe { SetForegroundWindow(hwnd).ok()? };
         Ok(())
@@ -427,6 +578,26 @@ impl WindowsApi {
         unsafe { GetWindow(hwnd, GW_OWNER).unwrap_or_default() }
     }
 
+    /// topmost window of the z-order
+    pub fn get_top_window() -> HWND {
+        unsafe { GetTopWindow(HWND::default()).unwrap_or_default() }
+    }
+
+    /// window below `hwnd` on the z-order
+    pub fn get_next_window(hwnd: HWND) -> HWND {
+        unsafe { GetWindow(hwnd, GW_HWNDNEXT).unwrap_or_default() }
+    }
+
+    /// top-level window that contains `hwnd`, child windows are resolved to their root
+    pub fn get_root_ancestor(hwnd: HWND) -> HWND {
+        unsafe { GetAncestor(hwnd, GA_ROOT) }
+    }
+
+    /// can return a child window, use `get_root_ancestor` to get the top-level one
+    pub fn window_from_point(point: &Point) -> HWND {
+        unsafe { WindowFromPoint(*point.as_ref()) }
+    }
+
     pub fn get_desktop_window() -> HWND {
         unsafe { GetDesktopWindow() }
     }
@@ -461,6 +632,64 @@ impl WindowsApi {
         Ok(is_frozen)
     }
 
+    /// protected processes can not be opened even when running as admin
+    fn process_control_handle(process_id: u32) -> Result<HANDLE> {
+        let access = PROCESS_SET_INFORMATION | PROCESS_QUERY_LIMITED_INFORMATION;
+        match unsafe { OpenProcess(access, false, process_id) } {
+            Ok(handle) => Ok(handle),
+            Err(err) if err.code() == E_ACCESSDENIED => Err(format!(
+                "Access denied to process {}, it is protected or requires elevation",
+                process_id
+            )
+            .into()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// returns the previous priority class
+    pub fn set_priority_class(
+        process_id: u32,
+        priority: PROCESS_CREATION_FLAGS,
+    ) -> Result<PROCESS_CREATION_FLAGS> {
+        let handle = Self::process_control_handle(process_id)?;
+        let result = unsafe {
+            let previous = GetPriorityClass(handle);
+            if previous == 0 {
+                Err(windows::core::Error::from_win32().into())
+            } else {
+                SetPriorityClass(handle, priority)
+                    .map(|_| PROCESS_CREATION_FLAGS(previous))
+                    .map_err(Into::into)
+            }
+        };
+        Self::close_handle(handle)?;
+        result
+    }
+
+    /// EcoQoS, the process runs on efficient cores at lower clock speeds
+    pub fn set_power_throttling(process_id: u32, enabled: bool) -> Result<()> {
+        let state = PROCESS_POWER_THROTTLING_STATE {
+            Version: PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+            ControlMask: PROCESS_POWER_THROTTLING_EXECUTION_SPEED,
+            StateMask: if enabled {
+                PROCESS_POWER_THROTTLING_EXECUTION_SPEED
+            } else {
+                0
+            },
+        };
+        let handle = Self::process_control_handle(process_id)?;
+        let result = unsafe {
+            SetProcessInformation(
+                handle,
+                ProcessPowerThrottling,
+                &state as *const _ as _,
+                std::mem::size_of::<PROCESS_POWER_THROTTLING_STATE>() as u32,
+            )
+        };
+        Self::close_handle(handle)?;
+        Ok(result?)
+    }
+
     pub fn exe_path_by_process(process_id: u32) -> Result<String> {
         let mut len = 512_u32;
         let mut path: Vec<u16> = vec![0; len as usize];
@@ -544,7 +773,17 @@ impl WindowsApi {
         Ok(BSTR::try_from(&value)?.to_string())
     }
 
-    pub fn create_temp_shortcut(program: &str, args: &str) -> Result<PathBuf> {
+    /// Saves a shortcut to `program` on `lnk_path`, `configure` can set any other
+    /// property of the link before it is saved.
+    pub fn create_shortcut<F>(
+        lnk_path: &Path,
+        program: &str,
+        args: &str,
+        configure: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(&IShellLinkW) -> Result<()>,
+    {
         Com::run_with_context(|| unsafe {
             let shell_link: IShellLinkW = Com::create_instance(&ShellLink)?;
 
@@ -554,16 +793,40 @@ impl WindowsApi {
             let arguments = WindowsString::from_str(args);
             shell_link.SetArguments(arguments.as_pcwstr())?;
 
-            let temp_dir = std::env::temp_dir();
-            let lnk_path = temp_dir.join(format!("{}.lnk", uuid::Uuid::new_v4()));
-            let lnk_path_wide = WindowsString::from_os_string(lnk_path.as_os_str());
+            configure(&shell_link)?;
 
+            let lnk_path_wide = WindowsString::from_os_string(lnk_path.as_os_str());
             let persist_file: IPersistFile = shell_link.cast()?;
             persist_file.Save(lnk_path_wide.as_pcwstr(), true)?;
-            Ok(lnk_path)
+            Ok(())
         })
     }
 
+    pub fn create_temp_shortcut(program: &str, args: &str) -> Result<PathBuf> {
+        let lnk_path = std::env::temp_dir().join(format!("{}.lnk", uuid::Uuid::new_v4()));
+        Self::create_shortcut(&lnk_path, program, args, |_| Ok(()))?;
+        Ok(lnk_path)
+    }
+
+    /// returns false if the user cancelled the UAC prompt
+    pub fn shell_execute_as_admin(program: &str, args: &str) -> Result<bool> {
+        let program = WindowsString::from_str(program);
+        let args = WindowsString::from_str(args);
+        let mut info = SHELLEXECUTEINFOW {
+            cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+            lpVerb: pcwstr!("runas"),
+            lpFile: program.as_pcwstr(),
+            lpParameters: args.as_pcwstr(),
+            nShow: SW_SHOWNORMAL.0,
+            ..Default::default()
+        };
+        match unsafe { ShellExecuteExW(&mut info) } {
+            Ok(()) => Ok(true),
+            Err(err) if err.code() == ERROR_CANCELLED.to_hresult() => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
     /// return the program and arguments
     pub fn resolve_lnk_target(lnk_path: &Path) -> Result<(PathBuf, OsString)> {
         Com::run_with_context(|| {
@@ -646,6 +909,43 @@ impl WindowsApi {
         Ok(())
     }
 
+    pub fn dwm_set_window_attribute<T>(
+        hwnd: HWND,
+        attribute: DWMWINDOWATTRIBUTE,
+        value: &T,
+    ) -> Result<()> {
+        unsafe {
+            DwmSetWindowAttribute(
+                hwnd,
+                attribute,
+                (value as *const T).cast(),
+                u32::try_from(std::mem::size_of::<T>())?,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Minimum (width, height) of the outer rect the window accepts when being resized,
+    /// falls back to the system minimum if the window doesn't answer.
+    pub fn get_min_track_size(hwnd: HWND) -> (i32, i32) {
+        let mut info = MINMAXINFO::default();
+        unsafe {
+            info.ptMinTrackSize.x = GetSystemMetrics(SM_CXMINTRACK);
+            info.ptMinTrackSize.y = GetSystemMetrics(SM_CYMINTRACK);
+            // pointers on system messages are marshalled across processes
+            SendMessageTimeoutW(
+                hwnd,
+                WM_GETMINMAXINFO,
+                WPARAM(0),
+                LPARAM(&mut info as *mut _ as isize),
+                SMTO_ABORTIFHUNG,
+                100,
+                None,
+            );
+        }
+        (info.ptMinTrackSize.x, info.ptMinTrackSize.y)
+    }
+
     /// Get the window rect including drop shadow
     pub fn get_outer_window_rect(hwnd: HWND) -> Result<RECT> {
         let mut rect = RECT::default();
@@ -745,6 +1045,53 @@ impl WindowsApi {
         Ok(devmode)
     }
 
+    /// All the graphics modes supported by the display device of the monitor
+    pub fn get_display_device_modes(monitor: HMONITOR) -> Result<Vec<DEVMODEW>> {
+        let info = Self::monitor_info(monitor)?;
+        let lpdevice = PCWSTR::from_raw(info.szDevice.as_ptr());
+        let mut modes = Vec::new();
+        let mut index = 0;
+        loop {
+            let mut devmode = DEVMODEW {
+                dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+                ..DEVMODEW::default()
+            };
+            let found = unsafe {
+                EnumDisplaySettingsW(lpdevice, ENUM_DISPLAY_SETTINGS_MODE(index), &mut devmode)
+            };
+            if !found.as_bool() {
+                break;
+            }
+            modes.push(devmode);
+            index += 1;
+        }
+        Ok(modes)
+    }
+
+    /// `device` is the GDI device name (`szDevice` of the monitor info), if `None` the
+    /// changes queued with `CDS_NORESET` are applied.
+    pub fn change_display_settings(
+        device: Option<&[u16]>,
+        devmode: Option<&DEVMODEW>,
+        flags: CDS_TYPE,
+    ) -> Result<()> {
+        let device = device.map_or(PCWSTR::null(), |d| PCWSTR::from_raw(d.as_ptr()));
+        let result = unsafe {
+            ChangeDisplaySettingsExW(
+                device,
+                devmode.map(|d| d as *const DEVMODEW),
+                HWND::default(),
+                flags,
+                None,
+            )
+        };
+        match result {
+            DISP_CHANGE_SUCCESSFUL => Ok(()),
+            DISP_CHANGE_RESTART => Err("A restart is required to apply the display changes".into()),
+            code => Err(format!("Failed to change display settings: {:?}", code).into()),
+        }
+    }
+
     /// handle of PHYSICAL_MONITOR is bugged and will be always 0
     pub fn primary_physical_monitor() -> Result<PHYSICAL_MONITOR> {
         Ok(Self::physical_monitors(Self::primary_monitor())?[0])
@@ -829,6 +1176,22 @@ impl WindowsApi {
         ))
     }
 
+    pub fn is_high_contrast_enabled() -> Result<bool> {
+        let mut high_contrast = HIGHCONTRASTW {
+            cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+            ..Default::default()
+        };
+        unsafe {
+            SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                high_contrast.cbSize,
+                Some(&mut high_contrast as *mut _ as _),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+            )?;
+        }
+        Ok(high_contrast.dwFlags.contains(HCF_HIGHCONTRASTON))
+    }
+
     pub fn set_wallpaper(path: String) -> Result<()> {
         if !PathBuf::from(&path).exists() {
             return Err("File not found".into());
@@ -893,21 +1256,18 @@ impl WindowsApi {
         Ok(())
     }
 
-    pub fn set_suspend_state() -> Result<()> {
-        let success = unsafe { SetSuspendState(false, true, false).as_bool() };
+    pub fn set_suspend_state(hibernate: bool) -> Result<()> {
+        let success = unsafe { SetSuspendState(hibernate, true, false).as_bool() };
         if !success {
             return Err("Failed to set suspend state".into());
         }
         Ok(())
     }
 
-    pub fn is_elevated() -> Result<bool> {
+    fn is_token_elevated(token_handle: HANDLE) -> Result<bool> {
+        let mut elevation = TOKEN_ELEVATION::default();
+        let mut ret_len = 0;
         unsafe {
-            let mut elevation = TOKEN_ELEVATION::default();
-            let mut ret_len = 0;
-
-            let token_handle = Self::open_current_process_token()?;
-
             GetTokenInformation(
                 token_handle,
                 TokenElevation,
@@ -915,11 +1275,37 @@ impl WindowsApi {
                 std::mem::size_of::<TOKEN_ELEVATION>() as u32,
                 &mut ret_len,
             )?;
+        }
+        Ok(elevation.TokenIsElevated != 0)
+    }
 
-            CloseHandle(token_handle)?;
+    pub fn is_elevated() -> Result<bool> {
+        let token_handle = Self::open_current_process_token()?;
+        let elevated = Self::is_token_elevated(token_handle);
+        unsafe { CloseHandle(token_handle)? };
+        elevated
+    }
 
-            Ok(elevation.TokenIsElevated != 0)
+    /// the process handle requires at least `PROCESS_QUERY_LIMITED_INFORMATION`
+    pub fn is_process_elevated(process: HANDLE) -> Result<bool> {
+        let mut token_handle = HANDLE::default();
+        unsafe { OpenProcessToken(process, TOKEN_QUERY, &mut token_handle)? };
+        let elevated = Self::is_token_elevated(token_handle);
+        unsafe { CloseHandle(token_handle)? };
+        elevated
+    }
+
+    pub fn get_power_capabilities() -> Result<SYSTEM_POWER_CAPABILITIES> {
+        let mut capabilities = SYSTEM_POWER_CAPABILITIES::default();
+        if !unsafe { GetPwrCapabilities(&mut capabilities) }.as_bool() {
+            return Err("Failed to get power capabilities".into());
         }
+        Ok(capabilities)
+    }
+
+    /// false if hibernation is not supported or it was disabled
+    pub fn is_hibernate_allowed() -> bool {
+        unsafe { IsPwrHibernateAllowed() }.as_bool()
     }
 
     pub fn get_system_power_status() -> Result<SYSTEM_POWER_STATUS> {
@@ -930,9 +1316,145 @@ impl WindowsApi {
         Ok(power_status)
     }
 
-    pub fn stream_to_dynamic_image(
-        stream: IRandomAccessStreamWithContentType,
-    ) -> Result<image::DynamicImage> {
+    /// guids of the power schemes (plans) installed on the system
+    pub fn power_enumerate_schemes() -> Result<Vec<GUID>> {
+        let mut schemes = Vec::new();
+        for index in 0.. {
+            let mut scheme = GUID::zeroed();
+            let mut size = std::mem::size_of::<GUID>() as u32;
+            let result = unsafe {
+                PowerEnumerate(
+                    HKEY::default(),
+                    None,
+                    None,
+                    ACCESS_SCHEME,
+                    index,
+                    Some(&mut scheme as *mut GUID as *mut u8),
+                    &mut size,
+                )
+            };
+            if result == ERROR_NO_MORE_ITEMS {
+                break;
+            }
+            result.ok()?;
+            schemes.push(scheme);
+        }
+        Ok(schemes)
+    }
+
+    pub fn power_scheme_name(scheme: &GUID) -> Result<String> {
+        let mut size = 0;
+        unsafe {
+            PowerReadFriendlyName(
+                HKEY::default(),
+                Some(scheme as *const GUID),
+                None,
+                None,
+                None,
+                &mut size,
+            )
+            .ok()?
+        };
+        let mut name = vec![0u16; size as usize / 2];
+        unsafe {
+            PowerReadFriendlyName(
+                HKEY::default(),
+                Some(scheme as *const GUID),
+                None,
+                None,
+                Some(name.as_mut_ptr() as *mut u8),
+                &mut size,
+            )
+            .ok()?
+        };
+        Ok(String::from_utf16_lossy(&name)
+            .trim_end_matches('\0')
+            .to_string())
+    }
+
+    pub fn power_active_scheme() -> Result<GUID> {
+        let mut scheme: *mut GUID = std::ptr::null_mut();
+        unsafe {
+            PowerGetActiveScheme(HKEY::default(), &mut scheme).ok()?;
+            let active = *scheme;
+            let _ = LocalFree(HLOCAL(scheme as _));
+            Ok(active)
+        }
+    }
+
+    pub fn power_set_active_scheme(scheme: &GUID) -> Result<()> {
+        unsafe { PowerSetActiveScheme(HKEY::default(), Some(scheme as *const GUID)).ok()? };
+        Ok(())
+    }
+
+    pub fn power_read_dc_value(scheme: &GUID, subgroup: &GUID, setting: &GUID) -> Result<u32> {
+        let mut value = 0;
+        unsafe {
+            PowerReadDCValueIndex(
+                HKEY::default(),
+                Some(scheme as *const GUID),
+                Some(subgroup as *const GUID),
+                Some(setting as *const GUID),
+                &mut value,
+            )
+            .ok()?
+        };
+        Ok(value)
+    }
+
+    /// the scheme should be activated again to apply the changes
+    pub fn power_write_dc_value(
+        scheme: &GUID,
+        subgroup: &GUID,
+        setting: &GUID,
+        value: u32,
+    ) -> Result<()> {
+        unsafe {
+            PowerWriteDCValueIndex(
+                HKEY::default(),
+                scheme,
+                Some(subgroup as *const GUID),
+                Some(setting as *const GUID),
+                value,
+            )
+            .ok()?
+        };
+        Ok(())
+    }
+
+    /// Drops the connection of a device through the first bluetooth radio, the device is
+    /// still paired and can be connected again.
+    pub fn disconnect_bluetooth_device(address: u64) -> Result<()> {
+        // CTL_CODE(FILE_DEVICE_BLUETOOTH, 0x03, METHOD_BUFFERED, FILE_ANY_ACCESS) from bthioctl.h
+        const IOCTL_BTH_DISCONNECT_DEVICE: u32 = 0x41000C;
+
+        let params = BLUETOOTH_FIND_RADIO_PARAMS {
+            dwSize: std::mem::size_of::<BLUETOOTH_FIND_RADIO_PARAMS>() as u32,
+        };
+        let mut radio = HANDLE::default();
+        unsafe {
+            let find = BluetoothFindFirstRadio(&params, &mut radio)?;
+            let _ = BluetoothFindRadioClose(find);
+        }
+
+        let result = unsafe {
+            DeviceIoControl(
+                radio,
+                IOCTL_BTH_DISCONNECT_DEVICE,
+                Some(&address as *const u64 as *const _),
+                std::mem::size_of::<u64>() as u32,
+                None,
+                0,
+                None,
+                None,
+            )
+        };
+        unsafe { CloseHandle(radio)? };
+        result?;
+        Ok(())
+    }
+
+    pub fn stream_to_bytes(stream: IRandomAccessStreamWithContentType) -> Result<Vec<u8>> {
         let size = stream.Size()?;
         let mut buffer = vec![0u8; size as usize];
 
@@ -941,17 +1463,35 @@ impl WindowsApi {
 
         data_reader.LoadAsync(size as u32)?.get()?;
         data_reader.ReadBytes(&mut buffer)?;
+        Ok(buffer)
+    }
 
+    pub fn stream_to_dynamic_image(
+        stream: IRandomAccessStreamWithContentType,
+    ) -> Result<image::DynamicImage> {
+        let buffer = Self::stream_to_bytes(stream)?;
         let image = image::load_from_memory_with_format(&buffer, image::ImageFormat::Png)?;
         Ok(image)
     }
 
+    /// Thumbnails are cached by content so the same art is only written once
+    /// even if it is shared by many tracks (albums).
     pub fn extract_thumbnail_from_stream(
         stream: IRandomAccessStreamWithContentType,
     ) -> Result<PathBuf> {
-        let image = Self::stream_to_dynamic_image(stream)?;
-        let image_path = std::env::temp_dir().join(format!("{}.png", uuid::Uuid::new_v4()));
-        image.save(&image_path)?;
+        let buffer = Self::stream_to_bytes(stream)?;
+        let mut hasher = DefaultHasher::new();
+        buffer.hash(&mut hasher);
+
+        let cache_dir = SEELEN_COMMON.icons_path().join("cache");
+        let image_path = cache_dir.join(format!("{:016x}.png", hasher.finish()));
+        if image_path.exists() {
+            return Ok(image_path);
+        }
+
+        std::fs::create_dir_all(&cache_dir)?;
+        // players can provide the art as png, jpeg, bmp, etc
+        image::load_from_memory(&buffer)?.save(&image_path)?;
         Ok(image_path)
     }
 
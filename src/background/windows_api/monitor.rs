@@ -1,11 +1,20 @@
-use windows::Win32::Graphics::Gdi::HMONITOR;
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use windows::Win32::{Graphics::Gdi::HMONITOR, UI::WindowsAndMessaging::MONITORINFOF_PRIMARY};
 use windows_core::PCWSTR;
 
-use crate::{error_handler::Result, modules::input::domain::Point};
+use crate::{error_handler::Result, modules::input::domain::Point, trace_lock};
 use seelen_core::rect::Rect;
 
 use super::{MonitorEnumerator, WindowsApi};
 
+lazy_static! {
+    /// display devices by monitor handle, cleared on every display change
+    static ref DISPLAY_DEVICES: Mutex<HashMap<isize, DisplayDevice>> = Mutex::new(HashMap::new());
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Monitor(HMONITOR);
 unsafe impl Send for Monitor {}
@@ -46,7 +55,22 @@ impl Monitor {
         Ok(self.display_device()?.id)
     }
 
+    /// cached, use `Monitor::invalidate_display_devices` when the display configuration changes
     pub fn display_device(&self) -> Result<DisplayDevice> {
+        if let Some(device) = trace_lock!(DISPLAY_DEVICES).get(&(self.0 .0 as isize)) {
+            return Ok(device.clone());
+        }
+        let device = self.query_display_device()?;
+        trace_lock!(DISPLAY_DEVICES).insert(self.0 .0 as isize, device.clone());
+        Ok(device)
+    }
+
+    /// drops all cached display devices, handles of disconnected monitors can be reused by the system
+    pub fn invalidate_display_devices() {
+        trace_lock!(DISPLAY_DEVICES).clear();
+    }
+
+    fn query_display_device(&self) -> Result<DisplayDevice> {
         let device = WindowsApi::get_display_device(self.0)?;
         let buffer_id = device.DeviceID;
         let buffer_name = device.DeviceString;
@@ -65,6 +89,21 @@ impl Monitor {
         Ok(Rect::from(info.monitorInfo.rcMonitor))
     }
 
+    /// monitor rect minus taskbar and registered app bars
+    pub fn work_area(&self) -> Result<Rect> {
+        let info = WindowsApi::monitor_info(self.0)?;
+        Ok(Rect::from(info.monitorInfo.rcWork))
+    }
+
+    pub fn is_primary(&self) -> Result<bool> {
+        let info = WindowsApi::monitor_info(self.0)?;
+        Ok(info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0)
+    }
+
+    pub fn primary() -> Monitor {
+        Self(WindowsApi::primary_monitor())
+    }
+
     pub fn index(&self) -> Result<usize> {
         WindowsApi::monitor_index(self.0)
     }
@@ -7,7 +7,7 @@ use windows::{
         Storage::Packaging::Appx::{
             GetApplicationUserModelId, GetPackageFamilyName, GetPackageFullName,
         },
-        System::Threading::PROCESS_QUERY_INFORMATION,
+        System::Threading::{PROCESS_QUERY_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION},
     },
 };
 
@@ -51,6 +51,15 @@ impl Process {
         Ok(result)
     }
 
+    /// None when access is denied, as happens with protected or system processes
+    pub fn is_elevated(&self) -> Option<bool> {
+        let handle =
+            WindowsApi::open_process(PROCESS_QUERY_LIMITED_INFORMATION, false, self.0).ok()?;
+        let elevated = WindowsApi::is_process_elevated(handle);
+        let _ = WindowsApi::close_handle(handle);
+        elevated.ok()
+    }
+
     pub fn package_family_name(&self) -> Result<String> {
         self.with_handle(|hprocess| {
             let mut len = 1024_u32;
@@ -12,6 +12,7 @@ use windows::Win32::{
 use crate::{
     error_handler::Result,
     modules::{
+        pip::application::PictureInPicture,
         start::application::START_MENU_ITEMS,
         virtual_desk::{get_vd_manager, VirtualDesktop},
     },
@@ -221,6 +222,7 @@ impl Window {
                     SeelenWeg::TITLE,
                     SeelenRofi::TITLE,
                     SeelenWall::TITLE,
+                    PictureInPicture::TITLE,
                 ]
                 .contains(&self.title().as_str());
         }
@@ -203,6 +203,10 @@ pub enum WinEvent {
     // ================== Synthetic events ==================
     SyntheticFullscreenStart(SyntheticFullscreenData),
     SyntheticFullscreenEnd(SyntheticFullscreenData),
+    /// window was pinned on top by Seelen
+    SyntheticTopmostStart,
+    /// window was unpinned from top by Seelen
+    SyntheticTopmostEnd,
 }
 
 impl From<u32> for WinEvent {
@@ -300,6 +304,11 @@ impl From<u32> for WinEvent {
 }
 
 impl WinEvent {
+    /// the window that started the current synthetic fullscreen, if any
+    pub fn synthetic_fullscreened() -> Option<SyntheticFullscreenData> {
+        *trace_lock!(FULLSCREENED)
+    }
+
     pub fn get_synthetics(&self, origin: HWND) -> Result<Vec<WinEvent>> {
         let mut synthetics = Vec::new();
         match self {